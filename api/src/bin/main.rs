@@ -1,4 +1,9 @@
-use api::auth::handlers::{sign_in, user_exists};
+use api::{
+    auth::handlers::{sign_in, user_exists},
+    explored::handlers::{get_explored, save_explored},
+    settings::handlers::{get_settings, save_settings},
+    world_time::handlers::{get_world_time, save_world_time},
+};
 use axum::{routing::post, Router};
 use dotenv::dotenv;
 use std::{env, net::SocketAddr, str::FromStr};
@@ -11,7 +16,13 @@ async fn main() {
     let addr = SocketAddr::from_str(&api_url).expect("Could not create socket addr");
     let app = Router::new()
         .route("/user_exists", post(user_exists))
-        .route("/sign_in", post(sign_in));
+        .route("/sign_in", post(sign_in))
+        .route("/explored", post(get_explored))
+        .route("/save_explored", post(save_explored))
+        .route("/settings", post(get_settings))
+        .route("/save_settings", post(save_settings))
+        .route("/world_time", post(get_world_time))
+        .route("/save_world_time", post(save_world_time));
 
     axum::Server::bind(&addr)
         .serve(app.into_make_service())
@@ -0,0 +1,84 @@
+use axum::{http::StatusCode, Json};
+use database::{establish_connection, models::Explored, schema::explored::dsl::*};
+use diesel::{pg::upsert::excluded, prelude::*};
+use serde::{Deserialize, Serialize};
+
+/// The cells a character has explored in a zone, as a bitset of rows `width`
+/// cells wide.
+#[derive(Serialize, Deserialize)]
+pub struct ExploredZone {
+    pub zone: String,
+    pub width: i32,
+    pub cells: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetExploredRequest {
+    pub character_id: i32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetExploredResponse {
+    pub zones: Vec<ExploredZone>,
+}
+
+pub async fn get_explored(
+    Json(input): Json<GetExploredRequest>,
+) -> Result<Json<GetExploredResponse>, StatusCode> {
+    let connection = establish_connection();
+
+    match explored
+        .filter(character_id.eq(input.character_id))
+        .load::<Explored>(&connection)
+    {
+        Ok(rows) => Ok(Json(GetExploredResponse {
+            zones: rows
+                .into_iter()
+                .map(|row| ExploredZone {
+                    zone: row.zone,
+                    width: row.width,
+                    cells: row.cells,
+                })
+                .collect(),
+        })),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SaveExploredRequest {
+    pub character_id: i32,
+    pub zones: Vec<ExploredZone>,
+}
+
+pub async fn save_explored(Json(input): Json<SaveExploredRequest>) -> StatusCode {
+    if input.zones.is_empty() {
+        return StatusCode::OK;
+    }
+
+    let connection = establish_connection();
+
+    let rows = input
+        .zones
+        .iter()
+        .map(|explored_zone| {
+            (
+                character_id.eq(input.character_id),
+                zone.eq(&explored_zone.zone),
+                width.eq(explored_zone.width),
+                cells.eq(explored_zone.cells.as_slice()),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    match diesel::insert_into(explored)
+        .values(&rows)
+        .on_conflict((character_id, zone))
+        .do_update()
+        .set((width.eq(excluded(width)), cells.eq(excluded(cells))))
+        .execute(&connection)
+    {
+        Ok(_) => StatusCode::OK,
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
@@ -1 +1,4 @@
 pub mod auth;
+pub mod explored;
+pub mod settings;
+pub mod world_time;
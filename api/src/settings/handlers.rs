@@ -0,0 +1,57 @@
+use axum::{http::StatusCode, Json};
+use database::{establish_connection, models::Settings, schema::settings::dsl::*};
+use diesel::{pg::upsert::excluded, prelude::*};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct GetSettingsRequest {
+    pub character_id: i32,
+}
+
+/// What a character has chosen for themselves, with the defaults for anyone
+/// who hasn't chosen anything yet.
+#[derive(Serialize, Deserialize)]
+pub struct GetSettingsResponse {
+    pub refuses_gifts: bool,
+}
+
+pub async fn get_settings(
+    Json(input): Json<GetSettingsRequest>,
+) -> Result<Json<GetSettingsResponse>, StatusCode> {
+    let connection = establish_connection();
+
+    match settings
+        .find(input.character_id)
+        .first::<Settings>(&connection)
+        .optional()
+    {
+        Ok(row) => Ok(Json(GetSettingsResponse {
+            refuses_gifts: row.map_or(false, |row| row.refuses_gifts),
+        })),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SaveSettingsRequest {
+    pub character_id: i32,
+    pub refuses_gifts: bool,
+}
+
+pub async fn save_settings(Json(input): Json<SaveSettingsRequest>) -> StatusCode {
+    let connection = establish_connection();
+
+    match diesel::insert_into(settings)
+        .values((
+            character_id.eq(input.character_id),
+            refuses_gifts.eq(input.refuses_gifts),
+        ))
+        .on_conflict(character_id)
+        .do_update()
+        .set(refuses_gifts.eq(excluded(refuses_gifts)))
+        .execute(&connection)
+    {
+        Ok(_) => StatusCode::OK,
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
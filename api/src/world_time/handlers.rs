@@ -0,0 +1,47 @@
+use axum::{http::StatusCode, Json};
+use database::{establish_connection, models::WorldTime, schema::world_time::dsl::*};
+use diesel::{pg::upsert::excluded, prelude::*};
+use serde::{Deserialize, Serialize};
+
+/// There's only ever one clock, so it's always stored in the same row.
+const CLOCK_ID: i32 = 1;
+
+#[derive(Serialize, Deserialize)]
+pub struct GetWorldTimeResponse {
+    pub minutes: f64,
+}
+
+pub async fn get_world_time() -> Result<Json<GetWorldTimeResponse>, StatusCode> {
+    let connection = establish_connection();
+
+    match world_time
+        .find(CLOCK_ID)
+        .first::<WorldTime>(&connection)
+        .optional()
+    {
+        Ok(row) => Ok(Json(GetWorldTimeResponse {
+            minutes: row.map_or(0.0, |row| row.minutes),
+        })),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SaveWorldTimeRequest {
+    pub minutes: f64,
+}
+
+pub async fn save_world_time(Json(input): Json<SaveWorldTimeRequest>) -> StatusCode {
+    let connection = establish_connection();
+
+    match diesel::insert_into(world_time)
+        .values((id.eq(CLOCK_ID), minutes.eq(input.minutes)))
+        .on_conflict(id)
+        .do_update()
+        .set(minutes.eq(excluded(minutes)))
+        .execute(&connection)
+    {
+        Ok(_) => StatusCode::OK,
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
@@ -0,0 +1,7 @@
+CREATE TABLE explored (
+  character_id INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+  zone VARCHAR NOT NULL,
+  width INTEGER NOT NULL,
+  cells BYTEA NOT NULL,
+  PRIMARY KEY (character_id, zone)
+)
@@ -0,0 +1,4 @@
+CREATE TABLE world_time (
+  id INTEGER PRIMARY KEY,
+  minutes DOUBLE PRECISION NOT NULL
+)
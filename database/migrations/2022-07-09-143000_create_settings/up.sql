@@ -0,0 +1,4 @@
+CREATE TABLE settings (
+  character_id INTEGER PRIMARY KEY REFERENCES users (id) ON DELETE CASCADE,
+  refuses_gifts BOOLEAN NOT NULL DEFAULT FALSE
+)
\ No newline at end of file
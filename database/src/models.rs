@@ -6,3 +6,23 @@ pub struct User {
     pub name: String,
     pub password: String,
 }
+
+#[derive(Queryable)]
+pub struct Explored {
+    pub character_id: i32,
+    pub zone: String,
+    pub width: i32,
+    pub cells: Vec<u8>,
+}
+
+#[derive(Queryable)]
+pub struct Settings {
+    pub character_id: i32,
+    pub refuses_gifts: bool,
+}
+
+#[derive(Queryable)]
+pub struct WorldTime {
+    pub id: i32,
+    pub minutes: f64,
+}
@@ -1,3 +1,19 @@
+table! {
+    explored (character_id, zone) {
+        character_id -> Int4,
+        zone -> Varchar,
+        width -> Int4,
+        cells -> Bytea,
+    }
+}
+
+table! {
+    settings (character_id) {
+        character_id -> Int4,
+        refuses_gifts -> Bool,
+    }
+}
+
 table! {
     users (id) {
         id -> Int4,
@@ -5,3 +21,15 @@ table! {
         password -> Varchar,
     }
 }
+
+table! {
+    world_time (id) {
+        id -> Int4,
+        minutes -> Float8,
+    }
+}
+
+joinable!(explored -> users (character_id));
+joinable!(settings -> users (character_id));
+
+allow_tables_to_appear_in_same_query!(explored, settings, users, world_time,);
@@ -1,4 +1,7 @@
-use api::auth::handlers::SignInResponse;
+use api::{
+    auth::handlers::SignInResponse, explored::handlers::GetExploredResponse,
+    settings::handlers::GetSettingsResponse,
+};
 use bevy::prelude::*;
 use reqwest::StatusCode;
 
@@ -7,16 +10,30 @@ use crate::{
         components::authenticating::{AuthState, Authenticating},
         utils::api::{sign_in, user_exists},
     },
-    items::components::backpack::Backpack,
+    items::components::{
+        backpack::Backpack, capacity::Capacity, equipment::Equipment, refuses_gifts::RefusesGifts,
+    },
     network::{
         events::{NetworkInput, NetworkOutput},
         server::{NetworkServer, TelnetCommand::*},
     },
     player::{
-        components::{character::Character, client::NetworkClient, online::Online},
+        components::{
+            action_queue::ActionQueue,
+            buffs::Buffs,
+            character::Character,
+            client::NetworkClient,
+            explored::{Cells, Explored},
+            online::Online,
+            vitals::Vitals,
+        },
         events::prompt_event::PromptEvent,
+        utils::{
+            api::{get_explored, get_settings},
+            players::Players,
+        },
     },
-    spatial::components::position::Position,
+    spatial::components::{position::Position, zone::Zone},
     visual::components::sprite::Sprite,
     world::resources::new_player_spawn::NewPlayerSpawn,
 };
@@ -30,12 +47,10 @@ pub fn perform_authentication(
     mut input: EventReader<NetworkInput>,
     mut output: EventWriter<NetworkOutput>,
     mut prompts: EventWriter<PromptEvent>,
-    mut players: Query<(Entity, &NetworkClient, &mut Authenticating)>,
+    mut players: Players<(Entity, &NetworkClient, &mut Authenticating), ()>,
 ) {
     for message in input.iter() {
-        if let Some((entity, client, mut authenticating)) =
-            players.iter_mut().find(|(_, c, _)| c.id == message.id)
-        {
+        if let Some((entity, client, mut authenticating)) = players.sender_mut(&message.id) {
             match authenticating.state {
                 AuthState::AwaitingName => {
                     // Validate the name. Currently, that just means it's
@@ -117,6 +132,41 @@ pub fn perform_authentication(
                             // Send the prompt.
                             prompts.send(PromptEvent(client.id));
 
+                            // Load what they've explored so far, starting from scratch
+                            // if we can't for whatever reason.
+                            let mut explored = Explored::default();
+
+                            match get_explored(json.id)
+                                .and_then(|response| response.json::<GetExploredResponse>())
+                            {
+                                Ok(response) => {
+                                    for zone in response.zones {
+                                        explored.0.insert(
+                                            zone.zone,
+                                            Cells {
+                                                width: zone.width,
+                                                bits: zone.cells,
+                                            },
+                                        );
+                                    }
+                                }
+                                Err(error) => {
+                                    warn!("Could not load explored map for {}: {error}", json.name);
+                                }
+                            }
+
+                            // Likewise their settings, leaving gifts on if we can't.
+                            let refuses_gifts = match get_settings(json.id)
+                                .and_then(|response| response.json::<GetSettingsResponse>())
+                            {
+                                Ok(response) => response.refuses_gifts,
+                                Err(error) => {
+                                    warn!("Could not load settings for {}: {error}", json.name);
+
+                                    false
+                                }
+                            };
+
                             // Remove `Authenticating` now that we're done.
                             commands.entity(entity).remove::<Authenticating>();
 
@@ -128,13 +178,24 @@ pub fn perform_authentication(
                                     name: json.name,
                                 },
                                 Backpack(Vec::new()),
-                                Position(new_player_spawn.0),
+                                Equipment::default(),
+                                Capacity::default(),
+                                ActionQueue::default(),
+                                Vitals::default(),
+                                Buffs::default(),
+                                explored,
+                                Position(new_player_spawn.position),
+                                Zone(new_player_spawn.zone.clone()),
                                 Sprite {
                                     character: "@".to_string(),
                                     color: "FAFAFA".to_string(),
                                     background: None,
                                 },
                             ));
+
+                            if refuses_gifts {
+                                commands.entity(entity).insert(RefusesGifts);
+                            }
                         }
                         StatusCode::FORBIDDEN => {
                             // If their password was not correct, let them try again.
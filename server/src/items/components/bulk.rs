@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+use bevy_proto::ProtoComponent;
+use serde::{Deserialize, Serialize};
+
+/// How much room a single item takes up in a [`Backpack`](super::backpack::Backpack),
+/// however light it is.
+#[derive(Clone, Serialize, Deserialize, ProtoComponent, Component, Debug)]
+pub struct Bulk(pub f32);
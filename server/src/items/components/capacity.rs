@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+use bevy_proto::ProtoComponent;
+use serde::{Deserialize, Serialize};
+
+/// How much a character can carry in their [`Backpack`](super::backpack::Backpack),
+/// by total [`Weight`](super::weight::Weight) and [`Bulk`](super::bulk::Bulk).
+#[derive(Clone, Serialize, Deserialize, ProtoComponent, Component, Debug)]
+pub struct Capacity {
+    pub weight: f32,
+    pub bulk: f32,
+}
+
+impl Default for Capacity {
+    fn default() -> Self {
+        Self {
+            weight: 25.0,
+            bulk: 40.0,
+        }
+    }
+}
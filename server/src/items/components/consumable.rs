@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+use bevy_proto::ProtoComponent;
+use serde::{Deserialize, Serialize};
+
+use crate::player::components::vitals::Stat;
+
+/// How something [`Consumable`] is consumed, and so which command does it.
+/// Anything consumable can be `use`d.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum Method {
+    Eat,
+    Drink,
+    Use,
+}
+
+impl Method {
+    pub fn verb(&self) -> &'static str {
+        match self {
+            Method::Eat => "eat",
+            Method::Drink => "drink",
+            Method::Use => "use",
+        }
+    }
+}
+
+/// Something that happens when a [`Consumable`] is consumed.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub enum Effect {
+    /// Raise one of the consumer's vitals, up to its maximum.
+    Restore { stat: Stat, amount: u32 },
+    /// Leave the consumer feeling `name`, like "well fed", for a while.
+    Buff { name: String, seconds: f32 },
+    /// Tell the consumer something.
+    Message(String),
+}
+
+/// Something that's used up when eaten, drunk or used, one at a time from
+/// a stack, with each of its `effects` happening in order.
+#[derive(Clone, Serialize, Deserialize, ProtoComponent, Component, Debug)]
+pub struct Consumable {
+    pub method: Method,
+    pub effects: Vec<Effect>,
+}
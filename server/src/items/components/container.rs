@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+use bevy_proto::ProtoComponent;
+use serde::{Deserialize, Serialize};
+
+use super::capacity::Capacity;
+
+/// Something that can hold items, like a pouch carried in a
+/// [`Backpack`](super::backpack::Backpack) or a chest sitting in the world.
+#[derive(Clone, Serialize, Deserialize, ProtoComponent, Component, Debug)]
+pub struct Container {
+    /// How much [`Bulk`](super::bulk::Bulk) fits inside.
+    pub bulk: f32,
+    #[serde(skip)]
+    pub items: Vec<Entity>,
+}
+
+impl Container {
+    /// What fits inside, for [`Load::fits`](crate::items::utils::load::Load::fits).
+    /// Containers only ever run out of room, however heavy their contents.
+    pub fn capacity(&self) -> Capacity {
+        Capacity {
+            weight: f32::INFINITY,
+            bulk: self.bulk,
+        }
+    }
+}
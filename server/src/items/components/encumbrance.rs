@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+
+/// How weighed down a character is by what they're carrying, which slows
+/// them down as they move.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Encumbrance {
+    Unburdened,
+    Burdened,
+    Strained,
+}
+
+impl Encumbrance {
+    /// How encumbered carrying `weight` is for someone who can carry `capacity`.
+    pub fn new(weight: f32, capacity: f32) -> Self {
+        let ratio = if capacity > 0.0 {
+            weight / capacity
+        } else {
+            1.0
+        };
+
+        if ratio > 0.75 {
+            Encumbrance::Strained
+        } else if ratio > 0.5 {
+            Encumbrance::Burdened
+        } else {
+            Encumbrance::Unburdened
+        }
+    }
+
+    /// How much longer each step takes.
+    pub fn slowdown(&self) -> f32 {
+        match self {
+            Encumbrance::Unburdened => 1.0,
+            Encumbrance::Burdened => 1.5,
+            Encumbrance::Strained => 2.0,
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Encumbrance::Unburdened => "unburdened",
+            Encumbrance::Burdened => "burdened",
+            Encumbrance::Strained => "straining under the weight",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Encumbrance;
+
+    #[test]
+    fn new() {
+        assert_eq!(Encumbrance::new(5.0, 25.0), Encumbrance::Unburdened);
+        assert_eq!(Encumbrance::new(15.0, 25.0), Encumbrance::Burdened);
+        assert_eq!(Encumbrance::new(25.0, 25.0), Encumbrance::Strained);
+        assert_eq!(Encumbrance::new(0.0, 0.0), Encumbrance::Strained);
+    }
+}
@@ -0,0 +1,40 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::visual::components::{
+    details::Details,
+    sprite::{Sprite, SpritePaint},
+};
+
+use super::equippable::Slot;
+
+/// What a character is wearing and wielding, by [`Slot`]. Equipped items are
+/// kept out of the [`Backpack`](super::backpack::Backpack), but still weigh on
+/// whoever has them on.
+#[derive(Component, Default, Debug)]
+pub struct Equipment(pub HashMap<Slot, Entity>);
+
+impl Equipment {
+    /// Everything equipped, in [`Slot::ALL`] order.
+    pub fn items(&self) -> Vec<(Slot, Entity)> {
+        Slot::ALL
+            .iter()
+            .filter_map(|slot| self.0.get(slot).map(|entity| (*slot, *entity)))
+            .collect()
+    }
+
+    /// A line for each slot in use, like `head: ^ Leather Cap`, where `lookup`
+    /// gives the details and sprite of whatever's in it.
+    pub fn describe<'a, F>(&self, lookup: F) -> Vec<String>
+    where
+        F: Fn(Entity) -> Option<(&'a Details, &'a Sprite)>,
+    {
+        self.items()
+            .into_iter()
+            .filter_map(|(slot, entity)| {
+                lookup(entity).map(|(details, sprite)| {
+                    format!("{}: {} {}", slot.name(), sprite.paint(), details.name)
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+use bevy_proto::ProtoComponent;
+use serde::{Deserialize, Serialize};
+
+/// Where on a character something is worn or wielded.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+pub enum Slot {
+    Head,
+    Body,
+    Hands,
+    Back,
+    /// Whatever's in hand to work with, like a hatchet or a fishing rod.
+    Tool,
+}
+
+impl Slot {
+    /// Every slot, in the order they're listed.
+    pub const ALL: [Slot; 5] = [Slot::Head, Slot::Body, Slot::Hands, Slot::Back, Slot::Tool];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Slot::Head => "head",
+            Slot::Body => "body",
+            Slot::Hands => "hands",
+            Slot::Back => "back",
+            Slot::Tool => "tool",
+        }
+    }
+
+    /// Tools are wielded, and everything else is worn.
+    pub fn wielded(&self) -> bool {
+        *self == Slot::Tool
+    }
+}
+
+/// Something that can be worn or wielded in a [`Slot`].
+#[derive(Clone, Serialize, Deserialize, ProtoComponent, Component, Debug)]
+pub struct Equippable {
+    pub slot: Slot,
+}
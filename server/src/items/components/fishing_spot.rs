@@ -0,0 +1,11 @@
+use bevy::prelude::*;
+use bevy_proto::ProtoComponent;
+use serde::{Deserialize, Serialize};
+
+/// Somewhere to `fish`, with a `chance` (from 0 to 1) of a bite in fair weather,
+/// and the prototype of whatever's caught.
+#[derive(Clone, Serialize, Deserialize, ProtoComponent, Component, Debug)]
+pub struct FishingSpot {
+    pub catch: String,
+    pub chance: f32,
+}
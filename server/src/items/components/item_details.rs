@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use bevy_proto::ProtoComponent;
+use serde::{Deserialize, Serialize};
+
+/// How well made something is.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum Quality {
+    Poor,
+    Common,
+    Fine,
+    Masterwork,
+}
+
+impl Quality {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Quality::Poor => "poor",
+            Quality::Common => "common",
+            Quality::Fine => "fine",
+            Quality::Masterwork => "masterwork",
+        }
+    }
+}
+
+/// How much wear something has left in it, out of what it had new.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct Durability {
+    pub current: u32,
+    pub max: u32,
+}
+
+/// Extra details for an item's card when it's looked at, beyond the name and
+/// description in its [`Details`](crate::visual::components::details::Details).
+#[derive(Clone, Default, Serialize, Deserialize, ProtoComponent, Component, Debug)]
+pub struct ItemDetails {
+    pub quality: Option<Quality>,
+    pub crafted_by: Option<String>,
+    pub durability: Option<Durability>,
+    /// A line of lore or colour, shown after the description.
+    pub flavour: Option<String>,
+}
@@ -1,3 +1,17 @@
 pub mod backpack;
+pub mod bulk;
 pub mod can_take;
+pub mod capacity;
+pub mod consumable;
+pub mod container;
+pub mod encumbrance;
+pub mod equipment;
+pub mod equippable;
+pub mod fishing_spot;
 pub mod item;
+pub mod item_details;
+pub mod refuses_gifts;
+pub mod stack;
+pub mod trade;
+pub mod trade_request;
+pub mod weight;
@@ -0,0 +1,6 @@
+use bevy::prelude::*;
+
+/// Marks a player who has turned off `gifts`, so nobody can `give` them anything.
+/// Saved with their character, so it lasts between sessions.
+#[derive(Component)]
+pub struct RefusesGifts;
@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+use bevy_proto::ProtoComponent;
+use serde::{Deserialize, Serialize};
+
+/// How many identical items an entity stands for. Items without one are just
+/// the one item.
+#[derive(Clone, Serialize, Deserialize, ProtoComponent, Component, Debug)]
+pub struct Stack(pub u32);
@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+/// One side of a trade between two players, started once each has sent the
+/// other `trade <player>`. Offered items are held here rather than in the
+/// [`Backpack`](super::backpack::Backpack) until the trade goes through or is
+/// cancelled.
+#[derive(Component, Debug)]
+pub struct Trade {
+    pub partner: Entity,
+    pub offer: Vec<Entity>,
+    /// Whether this side has accepted the offers as they stand.
+    pub accepted: bool,
+}
+
+impl Trade {
+    pub fn with(partner: Entity) -> Self {
+        Self {
+            partner,
+            offer: Vec::new(),
+            accepted: false,
+        }
+    }
+}
@@ -0,0 +1,6 @@
+use bevy::prelude::*;
+
+/// An invitation to [`Trade`](super::trade::Trade) sent with `trade <player>`,
+/// waiting on them to send `trade <player>` back.
+#[derive(Component, Debug)]
+pub struct TradeRequest(pub Entity);
@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+use bevy_proto::ProtoComponent;
+use serde::{Deserialize, Serialize};
+
+/// How heavy a single item is, in kilograms. Items without one weigh nothing.
+#[derive(Clone, Serialize, Deserialize, ProtoComponent, Component, Debug)]
+pub struct Weight(pub f32);
@@ -1,9 +1,13 @@
 use bevy::prelude::*;
 
-use self::systems::{backpack::*, drop::*, take::*};
+use self::systems::{
+    backpack::*, cancel_trades::*, consume::*, drop::*, encumbrance::*, equipment::*, fish::*,
+    get::*, gifts::*, give::*, look_in::*, put::*, remove::*, take::*, trade::*, wear::*,
+};
 
 pub mod components;
 pub mod systems;
+pub mod utils;
 
 pub struct ItemsPlugin;
 
@@ -14,7 +18,20 @@ impl Plugin for ItemsPlugin {
                 .label("items")
                 .with_system(take)
                 .with_system(drop)
-                .with_system(backpack),
+                .with_system(put)
+                .with_system(get)
+                .with_system(look_in)
+                .with_system(give)
+                .with_system(gifts)
+                .with_system(trade)
+                .with_system(cancel_trades)
+                .with_system(wear)
+                .with_system(remove)
+                .with_system(equipment)
+                .with_system(consume)
+                .with_system(fish)
+                .with_system(backpack)
+                .with_system(encumbrance),
         );
     }
 }
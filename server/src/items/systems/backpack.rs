@@ -1,68 +1,70 @@
-use bevy::{prelude::*, utils::HashMap};
-use inflector::Inflector;
+use bevy::prelude::*;
 use lazy_static::lazy_static;
 use regex::Regex;
 
 use crate::{
-    items::components::{backpack::Backpack, can_take::CanTake},
+    items::{
+        components::{
+            backpack::Backpack, can_take::CanTake, capacity::Capacity, container::Container,
+            encumbrance::Encumbrance, equipment::Equipment, stack::Stack,
+        },
+        utils::{
+            load::Loads,
+            stacks::{listing, quantity},
+        },
+    },
     network::events::{NetworkInput, NetworkOutput},
-    player::components::{client::NetworkClient, online::Online},
-    spatial::components::position::Position,
-    visual::components::{
-        details::Details,
-        sprite::{Sprite, SpritePaint},
+    player::{
+        components::{client::NetworkClient, online::Online},
+        utils::players::Players,
     },
+    spatial::components::position::Position,
+    visual::components::{details::Details, sprite::Sprite},
 };
 
+/// List what's in a player's [`Backpack`], along with how much it all weighs
+/// against their [`Capacity`].
 pub fn backpack(
     mut input: EventReader<NetworkInput>,
     mut output: EventWriter<NetworkOutput>,
-    players: Query<(&NetworkClient, &Backpack), With<Online>>,
-    entities: Query<(Entity, &Details, &Sprite), (With<CanTake>, Without<Position>)>,
+    players: Players<
+        (
+            &NetworkClient,
+            &Backpack,
+            Option<&Capacity>,
+            Option<&Equipment>,
+        ),
+        With<Online>,
+    >,
+    entities: Query<
+        (&Details, &Sprite, Option<&Stack>, Option<&Container>),
+        (With<CanTake>, Without<Position>),
+    >,
+    loads: Loads,
 ) {
     lazy_static! {
         static ref CMD: Regex = Regex::new("^(backpack|pack|bp|inventory|inv|i)$").unwrap();
     }
 
     for message in input.iter() {
-        if let Some((client, backpack)) = players.iter().find(|(c, _)| c.id == message.id) {
+        if let Some((client, backpack, capacity, equipment)) = players.sender(&message.id) {
             if CMD.is_match(&message.body.to_lowercase()) {
                 let mut items = Vec::new();
-                let mut counted: HashMap<String, (&Details, &Sprite, i32)> = HashMap::new();
+                let load = loads.of_player(&backpack.0, equipment);
 
                 for entity in &backpack.0 {
-                    if let Some((_, details, sprite)) =
-                        entities.iter().find(|(e, _, _)| e == entity)
-                    {
-                        if counted.contains_key(&details.name) {
-                            counted.insert(
-                                details.name.clone(),
-                                (
-                                    details,
-                                    sprite,
-                                    counted
-                                        .get(&details.name)
-                                        .unwrap_or(&(details, sprite, 0))
-                                        .2
-                                        + 1,
-                                ),
-                            );
+                    let (details, sprite, stack, container) =
+                        if let Ok(item) = entities.get(*entity) {
+                            item
                         } else {
-                            counted.insert(details.name.clone(), (details, sprite, 1));
-                        }
-                    }
-                }
-
-                for (_, (details, sprite, count)) in counted.iter() {
-                    items.push(format!(
-                        "{} {} {}",
-                        sprite.paint(),
-                        count,
-                        if *count > 1 {
-                            details.name.to_plural()
-                        } else {
-                            details.name.clone()
-                        }
+                            continue;
+                        };
+
+                    items.push(listing(
+                        sprite,
+                        &details.name,
+                        quantity(stack),
+                        container.map(|container| container.items.len()),
                     ));
                 }
 
@@ -72,6 +74,17 @@ pub fn backpack(
                         body: "Your backpack is empty.".into(),
                     });
                 } else {
+                    if let Some(capacity) = capacity {
+                        items.push(format!(
+                            "Carrying {:.1} of {:.1} kg and {:.1} of {:.1} bulk, so you're {}.",
+                            load.weight,
+                            capacity.weight,
+                            load.bulk,
+                            capacity.bulk,
+                            Encumbrance::new(load.weight, capacity.weight).describe()
+                        ));
+                    }
+
                     output.send(NetworkOutput {
                         id: client.id,
                         body: format!("Your backpack contains:\r\n{}", items.join("\r\n")),
@@ -89,7 +102,10 @@ mod tests {
 
     use crate::{
         network::events::{NetworkInput, NetworkOutput},
-        player::components::client::NetworkClient,
+        player::{
+            components::client::NetworkClient, resources::player_index::PlayerIndex,
+            systems::index_players::index_players,
+        },
         test::bundles::utils::{item_in_backpack_bundle, player_bundle, ItemBundle, PlayerBundle},
     };
 
@@ -101,6 +117,8 @@ mod tests {
 
         app.add_event::<NetworkInput>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::backpack);
 
         let item = app
@@ -141,7 +159,7 @@ mod tests {
         assert_eq!(output.id, player_client_id);
         assert_eq!(
             output.body,
-            "Your backpack contains:\r\no 1 Apple".to_string()
+            "Your backpack contains:\r\no 1 Apple\r\nCarrying 0.0 of 25.0 kg and 0.0 of 40.0 bulk, so you're unburdened.".to_string()
         );
     }
 
@@ -151,6 +169,8 @@ mod tests {
 
         app.add_event::<NetworkInput>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::backpack);
 
         let player = app
@@ -0,0 +1,196 @@
+use bevy::{prelude::*, utils::HashSet};
+
+use crate::{
+    items::{
+        components::{
+            backpack::Backpack, can_take::CanTake, container::Container, stack::Stack,
+            trade::Trade, trade_request::TradeRequest,
+        },
+        utils::stacks::{quantity, stacks_by, stow, Stacking},
+    },
+    network::events::NetworkOutput,
+    player::components::client::NetworkClient,
+    spatial::components::{position::Position, zone::Zone},
+};
+
+/// Call off any [`Trade`] where either side has moved away, or the other side
+/// has disconnected or stopped trading, returning the offer to its [`Backpack`].
+/// A [`TradeRequest`] lapses the same way.
+pub fn cancel_trades(
+    mut commands: Commands,
+    mut output: EventWriter<NetworkOutput>,
+    mut players: Query<(Entity, &NetworkClient, &mut Backpack, &Trade)>,
+    requests: Query<(Entity, &NetworkClient, &TradeRequest)>,
+    clients: Query<&NetworkClient>,
+    moved: Query<Entity, (With<NetworkClient>, Or<(Changed<Position>, Changed<Zone>)>)>,
+    items: Query<
+        (Option<&Stack>, Option<&Container>, Stacking),
+        (With<CanTake>, Without<Position>),
+    >,
+) {
+    let moved = moved.iter().collect::<HashSet<_>>();
+
+    let cancelled = players
+        .iter()
+        .filter(|(entity, _, _, trade)| {
+            moved.contains(entity)
+                || moved.contains(&trade.partner)
+                || players
+                    .get(trade.partner)
+                    .map_or(true, |(_, _, _, other)| other.partner != *entity)
+        })
+        .map(|(entity, _, _, _)| entity)
+        .collect::<Vec<_>>();
+
+    let lookup = |entity| {
+        items.get(entity).ok().map(|(stack, container, by)| {
+            (
+                stacks_by(by).filter(|_| container.is_none()),
+                quantity(stack),
+            )
+        })
+    };
+
+    for (entity, client, request) in requests.iter() {
+        if moved.contains(&entity) || moved.contains(&request.0) || clients.get(request.0).is_err()
+        {
+            commands.entity(entity).remove::<TradeRequest>();
+
+            output.send(NetworkOutput {
+                id: client.id,
+                body: "Your invitation to trade lapses.".into(),
+            });
+        }
+    }
+
+    for entity in cancelled {
+        if let Ok((_, client, mut backpack, trade)) = players.get_mut(entity) {
+            stow(&mut commands, &mut backpack.0, &trade.offer, &lookup);
+
+            commands.entity(entity).remove::<Trade>();
+
+            output.send(NetworkOutput {
+                id: client.id,
+                body: "The trade is cancelled, and your offer is returned to you.".into(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::{
+        items::components::{backpack::Backpack, trade::Trade},
+        network::events::NetworkOutput,
+        spatial::components::position::Position,
+        test::bundles::utils::{item_in_backpack_bundle, player_bundle, ItemBundle, PlayerBundle},
+    };
+
+    #[test]
+    fn moved_away() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkOutput>();
+        app.add_system(super::cancel_trades);
+
+        let apple = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Apple".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        let alice = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let bob = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        app.update();
+
+        app.world.entity_mut(alice).insert(Trade {
+            partner: bob,
+            offer: vec![apple],
+            accepted: true,
+        });
+        app.world.entity_mut(bob).insert(Trade::with(alice));
+
+        app.update();
+
+        assert!(app.world.get::<Trade>(alice).is_some());
+
+        app.world.get_mut::<Position>(bob).unwrap().0 = IVec2::new(1, 0);
+
+        app.update();
+
+        assert!(app.world.get::<Trade>(alice).is_none());
+        assert!(app.world.get::<Trade>(bob).is_none());
+        assert_eq!(app.world.get::<Backpack>(alice).unwrap().0, vec![apple]);
+    }
+
+    #[test]
+    fn disconnected() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkOutput>();
+        app.add_system(super::cancel_trades);
+
+        let apple = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Apple".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        let alice = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let bob = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        app.update();
+
+        app.world.entity_mut(alice).insert(Trade {
+            partner: bob,
+            offer: vec![apple],
+            accepted: false,
+        });
+        app.world.entity_mut(bob).insert(Trade::with(alice));
+
+        app.update();
+
+        // Just as `handle_network_events` does when the connection is lost.
+        app.world.entity_mut(bob).despawn();
+
+        app.update();
+
+        assert!(app.world.get::<Trade>(alice).is_none());
+        assert_eq!(app.world.get::<Backpack>(alice).unwrap().0, vec![apple]);
+    }
+}
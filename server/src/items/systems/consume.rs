@@ -0,0 +1,245 @@
+use bevy::prelude::*;
+use inflector::Inflector;
+
+use crate::{
+    items::{
+        components::{
+            backpack::Backpack,
+            can_take::CanTake,
+            consumable::{Consumable, Effect},
+            stack::Stack,
+        },
+        utils::stacks::{quantity, Target},
+    },
+    network::events::NetworkOutput,
+    player::{
+        components::{buffs::Buffs, client::NetworkClient, online::Online, vitals::Vitals},
+        events::action_event::ActionEvent,
+        utils::{actions::Action, players::Players},
+    },
+    spatial::components::position::Position,
+    visual::components::details::Details,
+};
+
+/// Eat, drink or use one of something [`Consumable`] from the [`Backpack`],
+/// like `eat apple`, applying its effects and using it up.
+pub fn consume(
+    mut commands: Commands,
+    mut actions: EventReader<ActionEvent>,
+    mut output: EventWriter<NetworkOutput>,
+    mut players: Players<(&NetworkClient, &mut Backpack, &mut Vitals, &mut Buffs), With<Online>>,
+    items: Query<
+        (&Details, Option<&Stack>, Option<&Consumable>),
+        (With<CanTake>, Without<Position>),
+    >,
+) {
+    for message in actions.iter() {
+        let body = message.body.to_lowercase();
+
+        let captures = if let Some(captures) = Action::Consume.captures(&body) {
+            captures
+        } else {
+            continue;
+        };
+
+        let (client, mut backpack, mut vitals, mut buffs) =
+            if let Some(player) = players.sender_mut(&message.id) {
+                player
+            } else {
+                continue;
+            };
+
+        let verb = &captures[1];
+
+        let target = if let Some(target) = captures.get(3) {
+            target
+        } else {
+            output.send(NetworkOutput {
+                id: client.id,
+                body: format!("{} what?", verb.to_sentence_case()),
+            });
+
+            continue;
+        };
+
+        let picked = Target::parse(target.as_str()).pick(backpack.0.iter().filter_map(|entity| {
+            items
+                .get(*entity)
+                .ok()
+                .map(|(details, stack, _)| (*entity, details.name.as_str(), quantity(stack)))
+        }));
+
+        let (entity, details, consumable, total) = if let Some(item) =
+            picked.first().and_then(|(entity, _, total)| {
+                items
+                    .get(*entity)
+                    .ok()
+                    .map(|(details, _, consumable)| (*entity, details, consumable, *total))
+            }) {
+            item
+        } else {
+            output.send(NetworkOutput {
+                id: client.id,
+                body: "You don't have that.".into(),
+            });
+
+            continue;
+        };
+
+        let consumable = match consumable {
+            Some(consumable) if verb == "use" || consumable.method.verb() == verb => consumable,
+            _ => {
+                output.send(NetworkOutput {
+                    id: client.id,
+                    body: format!("You can't {verb} that."),
+                });
+
+                continue;
+            }
+        };
+
+        if total > 1 {
+            commands.entity(entity).insert(Stack(total - 1));
+        } else {
+            backpack.0.retain(|e| *e != entity);
+            commands.entity(entity).despawn();
+        }
+
+        let mut lines = vec![format!(
+            "You {} the {}.",
+            consumable.method.verb(),
+            details.name
+        )];
+
+        for effect in &consumable.effects {
+            match effect {
+                Effect::Restore { stat, amount } => {
+                    let restored = vitals.restore(*stat, *amount);
+
+                    if restored > 0 {
+                        lines.push(format!("You recover {restored} {}.", stat.name()));
+                    }
+                }
+                Effect::Buff { name, seconds } => {
+                    buffs.apply(name, *seconds);
+
+                    lines.push(format!("You are {name}."));
+                }
+                Effect::Message(text) => lines.push(text.clone()),
+            }
+        }
+
+        output.send(NetworkOutput {
+            id: client.id,
+            body: lines.join("\r\n"),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::{
+        items::components::{
+            backpack::Backpack,
+            consumable::{Consumable, Effect, Method},
+        },
+        network::events::NetworkOutput,
+        player::{
+            components::{
+                buffs::Buffs,
+                client::NetworkClient,
+                vitals::{Stat, Vitals},
+            },
+            events::action_event::ActionEvent,
+            resources::player_index::PlayerIndex,
+            systems::index_players::index_players,
+        },
+        test::bundles::utils::{item_in_backpack_bundle, player_bundle, ItemBundle, PlayerBundle},
+    };
+
+    #[test]
+    fn consume() {
+        let mut app = App::new();
+
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::consume);
+
+        let apples = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Apple".into(),
+                quantity: 2,
+                ..Default::default()
+            }))
+            .insert(Consumable {
+                method: Method::Eat,
+                effects: vec![
+                    Effect::Restore {
+                        stat: Stat::Health,
+                        amount: 5,
+                    },
+                    Effect::Buff {
+                        name: "well fed".into(),
+                        seconds: 60.0,
+                    },
+                    Effect::Message("Crisp and sweet.".into()),
+                ],
+            })
+            .id();
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                items: vec![apples],
+                ..Default::default()
+            }))
+            .id();
+
+        app.world.get_mut::<Vitals>(player).unwrap().health = 90;
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        // Each takes effect at the end of the update, so the stack is only
+        // down to one for the last.
+        for bodies in [vec!["drink apple", "eat apple"], vec!["use apple"]] {
+            for body in bodies {
+                app.world
+                    .resource_mut::<Events<ActionEvent>>()
+                    .send(ActionEvent {
+                        id: player_client_id,
+                        body: body.into(),
+                    });
+            }
+
+            app.update();
+        }
+
+        assert!(app.world.get_entity(apples).is_none());
+        assert!(app.world.get::<Backpack>(player).unwrap().0.is_empty());
+        assert_eq!(app.world.get::<Vitals>(player).unwrap().health, 100);
+        assert_eq!(app.world.get::<Buffs>(player).unwrap().0.len(), 1);
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let outputs = output_reader
+            .iter(output_events)
+            .map(|output| output.body.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            outputs,
+            vec![
+                "You can't drink that.",
+                "You eat the Apple.\r\nYou recover 5 health.\r\nYou are well fed.\r\nCrisp and sweet.",
+                "You eat the Apple.\r\nYou recover 5 health.\r\nYou are well fed.\r\nCrisp and sweet.",
+            ]
+        );
+    }
+}
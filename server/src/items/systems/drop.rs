@@ -1,61 +1,111 @@
-use bevy::prelude::*;
-use lazy_static::lazy_static;
-use regex::Regex;
+use bevy::{prelude::*, utils::HashMap};
 
 use crate::{
-    items::components::{backpack::Backpack, can_take::CanTake},
-    network::events::{NetworkInput, NetworkOutput},
-    player::components::{client::NetworkClient, online::Online},
-    spatial::components::position::Position,
+    items::{
+        components::{backpack::Backpack, can_take::CanTake, container::Container, stack::Stack},
+        utils::stacks::{describe, move_stack, quantity, stacks_by, Stacking, Target},
+    },
+    network::events::NetworkOutput,
+    player::{
+        components::{client::NetworkClient, online::Online},
+        events::action_event::ActionEvent,
+        utils::{actions::Action, players::Players},
+    },
+    spatial::components::{position::Position, zone::Zone},
     visual::components::details::Details,
 };
 
-/// Drop an item from an entitys backpack.
+/// Drop items from an entity's backpack, like `drop apple`, `drop 5 apple` or
+/// `drop all banana`.
 ///
-/// We do this by removing the item from the backpack and adding giving it
-/// a [`Position`].
+/// We do this by removing the item from the backpack and giving it a [`Position`]
+/// and [`Zone`], or adding it to a matching stack that's already on the ground.
 pub fn drop(
     mut commands: Commands,
-    mut input: EventReader<NetworkInput>,
+    mut actions: EventReader<ActionEvent>,
     mut output: EventWriter<NetworkOutput>,
-    mut players: Query<(&NetworkClient, &Position, &mut Backpack), With<Online>>,
-    entities: Query<(Entity, &Details), (With<CanTake>, Without<Position>)>,
+    mut players: Players<(&NetworkClient, &Position, &Zone, &mut Backpack), With<Online>>,
+    entities: Query<
+        (&Details, Option<&Stack>, Option<&Container>, Stacking),
+        (With<CanTake>, Without<Position>),
+    >,
+    ground: Query<
+        (
+            Entity,
+            &Position,
+            &Zone,
+            Option<&Stack>,
+            Option<&Container>,
+            Stacking,
+        ),
+        With<CanTake>,
+    >,
 ) {
-    lazy_static! {
-        static ref CMD: Regex = Regex::new("^(drop)(( +)(.+))?$").unwrap();
-    }
-
-    for message in input.iter() {
-        if let Some((client, position, mut backpack)) =
-            players.iter_mut().find(|(c, _, _)| c.id == message.id)
-        {
-            if let Some(captures) = CMD.captures(&message.body.to_lowercase()) {
+    for message in actions.iter() {
+        if let Some((client, position, zone, mut backpack)) = players.sender_mut(&message.id) {
+            if let Some(captures) = Action::Drop.captures(&message.body.to_lowercase()) {
                 match captures.get(4) {
-                    Some(name_or_id) => {
-                        for i in 0..backpack.0.len() {
-                            let entity = backpack.0[i];
-
-                            if let Ok((_, details)) = entities.get(entity) {
-                                if details.name.to_lowercase()
-                                    == name_or_id.as_str().to_lowercase().trim()
-                                    || entity.id().to_string() == name_or_id.as_str()
-                                {
-                                    commands.entity(entity).insert(Position(position.0));
-                                    backpack.0.remove(i);
-
-                                    output.send(NetworkOutput {
-                                        id: client.id,
-                                        body: format!("You drop the {}.", details.name),
-                                    });
-                                }
-
-                                break;
+                    Some(target) => {
+                        let picked = Target::parse(target.as_str()).pick(
+                            backpack.0.iter().filter_map(|entity| {
+                                entities.get(*entity).ok().map(|(details, stack, _, _)| {
+                                    (*entity, details.name.as_str(), quantity(stack))
+                                })
+                            }),
+                        );
+
+                        if picked.is_empty() {
+                            output.send(NetworkOutput {
+                                id: client.id,
+                                body: "You don't have that.".into(),
+                            });
+
+                            continue;
+                        }
+
+                        // Stacks already on the ground here, and how many each holds.
+                        let mut stacks = ground
+                            .iter()
+                            .filter(|(_, p, z, _, container, _)| {
+                                p.0 == position.0 && z.0 == zone.0 && container.is_none()
+                            })
+                            .filter_map(|(entity, _, _, stack, _, by)| {
+                                stacks_by(by)
+                                    .map(|key| (key.to_string(), (entity, quantity(stack))))
+                            })
+                            .collect::<HashMap<_, _>>();
+
+                        let mut dropped = Vec::new();
+
+                        for (entity, amount, total) in picked {
+                            let (name, key) =
+                                if let Ok((details, _, container, by)) = entities.get(entity) {
+                                    (
+                                        details.name.clone(),
+                                        stacks_by(by).filter(|_| container.is_none()),
+                                    )
+                                } else {
+                                    continue;
+                                };
+
+                            if amount == total {
+                                backpack.0.retain(|e| *e != entity);
+                            }
+
+                            if let Some(moved) =
+                                move_stack(&mut commands, &mut stacks, key, entity, amount, total)
+                            {
+                                commands
+                                    .entity(moved)
+                                    .insert_bundle((Position(position.0), zone.clone()));
                             }
+
+                            dropped.push((name, amount));
                         }
 
                         output.send(NetworkOutput {
                             id: client.id,
-                            body: "You don't have that.".into(),
+                            body: format!("You drop {}.", describe(&dropped)),
                         });
                     }
                     None => {
@@ -75,18 +125,26 @@ mod tests {
     use bevy::{ecs::event::Events, prelude::*};
 
     use crate::{
-        items::components::backpack::Backpack,
-        network::events::{NetworkInput, NetworkOutput},
-        player::components::client::NetworkClient,
-        test::bundles::utils::{item_in_backpack_bundle, player_bundle, ItemBundle, PlayerBundle},
+        items::components::{backpack::Backpack, stack::Stack},
+        network::events::NetworkOutput,
+        player::{
+            components::client::NetworkClient, events::action_event::ActionEvent,
+            resources::player_index::PlayerIndex, systems::index_players::index_players,
+        },
+        spatial::components::position::Position,
+        test::bundles::utils::{
+            item_bundle, item_in_backpack_bundle, player_bundle, ItemBundle, PlayerBundle,
+        },
     };
 
     #[test]
     fn drop() {
         let mut app = App::new();
 
-        app.add_event::<NetworkInput>();
+        app.add_event::<ActionEvent>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::drop);
 
         let item = app
@@ -110,11 +168,10 @@ mod tests {
         let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
 
         app.world
-            .resource_mut::<Events<NetworkInput>>()
-            .send(NetworkInput {
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
                 id: player_client_id,
                 body: format!("drop apple"),
-                internal: false,
             });
 
         app.update();
@@ -129,12 +186,75 @@ mod tests {
         assert_eq!(output.body, "You drop the Apple.");
     }
 
+    #[test]
+    fn onto_stack() {
+        let mut app = App::new();
+
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::drop);
+
+        let carried = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Banana".into(),
+                quantity: 4,
+                ..Default::default()
+            }))
+            .id();
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                items: vec![carried],
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        let ground = app
+            .world
+            .spawn()
+            .insert_bundle(item_bundle(ItemBundle {
+                name: "Banana".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: format!("drop 3 bananas"),
+            });
+
+        app.update();
+
+        assert_eq!(app.world.get::<Stack>(ground).unwrap().0, 4);
+        assert_eq!(app.world.get::<Stack>(carried).unwrap().0, 1);
+        assert!(app.world.get::<Position>(carried).is_none());
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let outputs = output_reader.iter(output_events).collect::<Vec<_>>();
+
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(outputs[0].body, "You drop 3 Bananas.");
+    }
+
     #[test]
     fn entity_not_found() {
         let mut app = App::new();
 
-        app.add_event::<NetworkInput>();
+        app.add_event::<ActionEvent>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::drop);
 
         let player = app
@@ -148,11 +268,10 @@ mod tests {
         let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
 
         app.world
-            .resource_mut::<Events<NetworkInput>>()
-            .send(NetworkInput {
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
                 id: player_client_id,
                 body: format!("drop apple"),
-                internal: false,
             });
 
         app.update();
@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+
+use crate::{
+    items::{
+        components::{
+            backpack::Backpack, capacity::Capacity, encumbrance::Encumbrance, equipment::Equipment,
+        },
+        utils::load::Loads,
+    },
+    network::events::NetworkOutput,
+    player::components::{client::NetworkClient, online::Online},
+};
+
+/// Keep each player's [`Encumbrance`] in line with what's in their [`Backpack`],
+/// letting them know when it changes.
+pub fn encumbrance(
+    mut commands: Commands,
+    mut output: EventWriter<NetworkOutput>,
+    players: Query<
+        (
+            Entity,
+            &NetworkClient,
+            &Backpack,
+            &Capacity,
+            Option<&Equipment>,
+            Option<&Encumbrance>,
+        ),
+        With<Online>,
+    >,
+    loads: Loads,
+) {
+    for (entity, client, backpack, capacity, equipment, current) in players.iter() {
+        let load = loads.of_player(&backpack.0, equipment);
+
+        let encumbrance = Encumbrance::new(load.weight, capacity.weight);
+
+        if current == Some(&encumbrance) {
+            continue;
+        }
+
+        commands.entity(entity).insert(encumbrance);
+
+        // Nobody needs telling they're unburdened when they've only just logged in.
+        if current.is_none() && encumbrance == Encumbrance::Unburdened {
+            continue;
+        }
+
+        output.send(NetworkOutput {
+            id: client.id,
+            body: match encumbrance {
+                Encumbrance::Unburdened => "Your load feels light again.",
+                Encumbrance::Burdened => "Your load is starting to slow you down.",
+                Encumbrance::Strained => "You strain under the weight of your load.",
+            }
+            .to_string(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::{
+        items::components::{encumbrance::Encumbrance, weight::Weight},
+        network::events::NetworkOutput,
+        test::bundles::utils::{item_in_backpack_bundle, player_bundle, ItemBundle, PlayerBundle},
+    };
+
+    #[test]
+    fn encumbrance() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkOutput>();
+        app.add_system(super::encumbrance);
+
+        let logs = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Log".into(),
+                quantity: 2,
+                ..Default::default()
+            }))
+            .insert(Weight(8.0))
+            .id();
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                items: vec![logs],
+                ..Default::default()
+            }))
+            .id();
+
+        app.update();
+
+        assert_eq!(
+            app.world.get::<Encumbrance>(player),
+            Some(&Encumbrance::Burdened)
+        );
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(output.body, "Your load is starting to slow you down.");
+    }
+}
@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::{
+    items::components::{can_take::CanTake, equipment::Equipment},
+    network::events::{NetworkInput, NetworkOutput},
+    player::{
+        components::{client::NetworkClient, online::Online},
+        utils::players::Players,
+    },
+    spatial::components::position::Position,
+    visual::components::{details::Details, sprite::Sprite},
+};
+
+/// List what a player is wearing and wielding, slot by slot.
+pub fn equipment(
+    mut input: EventReader<NetworkInput>,
+    mut output: EventWriter<NetworkOutput>,
+    players: Players<(&NetworkClient, &Equipment), With<Online>>,
+    items: Query<(&Details, &Sprite), (With<CanTake>, Without<Position>)>,
+) {
+    lazy_static! {
+        static ref CMD: Regex = Regex::new("^(equipment|eq)$").unwrap();
+    }
+
+    for message in input.iter() {
+        if let Some((client, equipment)) = players.sender(&message.id) {
+            if CMD.is_match(&message.body.to_lowercase()) {
+                let lines = equipment.describe(|entity| items.get(entity).ok());
+
+                output.send(NetworkOutput {
+                    id: client.id,
+                    body: if lines.is_empty() {
+                        "You aren't wearing or wielding anything.".into()
+                    } else {
+                        format!("You have on:\r\n{}", lines.join("\r\n"))
+                    },
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+    use yansi::Paint;
+
+    use crate::{
+        items::components::{equipment::Equipment, equippable::Slot},
+        network::events::{NetworkInput, NetworkOutput},
+        player::{
+            components::client::NetworkClient, resources::player_index::PlayerIndex,
+            systems::index_players::index_players,
+        },
+        test::bundles::utils::{item_in_backpack_bundle, player_bundle, ItemBundle, PlayerBundle},
+    };
+
+    #[test]
+    fn equipment() {
+        Paint::disable();
+
+        let mut app = App::new();
+
+        app.add_event::<NetworkInput>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::equipment);
+
+        let cap = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Cap".into(),
+                character: "^".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        let hatchet = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Hatchet".into(),
+                character: "/".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let mut equipment = app.world.get_mut::<Equipment>(player).unwrap();
+
+        equipment.0.insert(Slot::Tool, hatchet);
+        equipment.0.insert(Slot::Head, cap);
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        app.world
+            .resource_mut::<Events<NetworkInput>>()
+            .send(NetworkInput {
+                id: player_client_id,
+                body: "eq".into(),
+                internal: false,
+            });
+
+        app.update();
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(
+            output.body,
+            "You have on:\r\nhead: ^ Cap\r\ntool: / Hatchet"
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+use bevy_proto::prelude::ProtoData;
+
+use crate::{
+    items::components::fishing_spot::FishingSpot,
+    network::events::NetworkOutput,
+    player::{
+        components::{client::NetworkClient, online::Online},
+        events::action_event::ActionEvent,
+        utils::{actions::Action, players::Players},
+    },
+    spatial::{
+        components::{position::Position, zone::Zone},
+        utils::direction::adjacent,
+    },
+    world::{components::prototype::Prototype, resources::weather::Weather},
+};
+
+/// Cast a line into a [`FishingSpot`] next to the player with `fish`. Bites are
+/// likelier or rarer depending on the [`Weather`], and anything caught lands at
+/// the player's feet.
+pub fn fish(
+    mut commands: Commands,
+    mut actions: EventReader<ActionEvent>,
+    mut output: EventWriter<NetworkOutput>,
+    (prototypes, asset_server): (Res<ProtoData>, Res<AssetServer>),
+    weather: Res<Weather>,
+    players: Players<(&NetworkClient, &Position, &Zone), With<Online>>,
+    spots: Query<(&Position, &Zone, &FishingSpot)>,
+) {
+    for message in actions.iter() {
+        if Action::Fish
+            .captures(&message.body.to_lowercase())
+            .is_none()
+        {
+            continue;
+        }
+
+        let (client, position, zone) = if let Some(player) = players.sender(&message.id) {
+            player
+        } else {
+            continue;
+        };
+
+        let targets = adjacent(position.0, None);
+
+        let spot = if let Some((_, _, spot)) = spots
+            .iter()
+            .find(|(p, z, _)| z.0 == zone.0 && targets.contains(&p.0))
+        {
+            spot
+        } else {
+            output.send(NetworkOutput {
+                id: client.id,
+                body: "There's nowhere to fish here.".into(),
+            });
+
+            continue;
+        };
+
+        let chance = spot.chance * weather.get(&zone.0).bite_rate();
+
+        let caught = if rand::random::<f32>() < chance {
+            prototypes.get_prototype(&spot.catch)
+        } else {
+            None
+        };
+
+        let body = if let Some(prototype) = caught {
+            prototype
+                .spawn(&mut commands, &prototypes, &asset_server)
+                .insert_bundle((
+                    Position(position.0),
+                    Zone(zone.0.clone()),
+                    Prototype(spot.catch.clone()),
+                ));
+
+            format!("You reel in a {}!", spot.catch)
+        } else {
+            "Nothing bites.".into()
+        };
+
+        output.send(NetworkOutput {
+            id: client.id,
+            body,
+        });
+    }
+}
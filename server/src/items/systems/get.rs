@@ -0,0 +1,403 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{
+    items::{
+        components::{
+            backpack::Backpack, bulk::Bulk, can_take::CanTake, capacity::Capacity,
+            container::Container, equipment::Equipment, stack::Stack, weight::Weight,
+        },
+        utils::{
+            load::Load,
+            nesting::{enclosing, find_container, parents},
+            stacks::{describe, move_stack, quantity, stacks_by, Stacking, Target},
+        },
+    },
+    network::events::NetworkOutput,
+    player::{
+        components::{client::NetworkClient, online::Online},
+        events::action_event::ActionEvent,
+        utils::{actions::Action, players::Players},
+    },
+    spatial::components::{position::Position, zone::Zone},
+    visual::components::details::Details,
+};
+
+/// Take items out of a [`Container`] that's either in the [`Backpack`] or on the
+/// ground, like `get apple from chest` or `take all from pouch`. Only as much
+/// as fits within the player's [`Capacity`] is taken.
+pub fn get(
+    mut commands: Commands,
+    mut actions: EventReader<ActionEvent>,
+    mut output: EventWriter<NetworkOutput>,
+    mut players: Players<
+        (
+            &NetworkClient,
+            &Position,
+            &Zone,
+            &mut Backpack,
+            Option<&Capacity>,
+            Option<&Equipment>,
+        ),
+        With<Online>,
+    >,
+    items: Query<
+        (
+            &Details,
+            Option<&Weight>,
+            Option<&Bulk>,
+            Option<&Stack>,
+            Stacking,
+        ),
+        (With<CanTake>, Without<Position>),
+    >,
+    mut containers: Query<(
+        Entity,
+        &Details,
+        &mut Container,
+        Option<&Position>,
+        Option<&Zone>,
+    )>,
+) {
+    for message in actions.iter() {
+        if let Some((client, position, zone, mut backpack, capacity, equipment)) =
+            players.sender_mut(&message.id)
+        {
+            if let Some(captures) = Action::Get.captures(&message.body.to_lowercase()) {
+                let (target, container) = match (captures.get(3), captures.get(5)) {
+                    (Some(target), Some(container)) => (target, container),
+                    // Taking things off the ground is handled by `take`.
+                    _ if &captures[1] == "take" => continue,
+                    (Some(_), None) => {
+                        output.send(NetworkOutput {
+                            id: client.id,
+                            body: "Get it from what?".into(),
+                        });
+
+                        continue;
+                    }
+                    _ => {
+                        output.send(NetworkOutput {
+                            id: client.id,
+                            body: "Get what?".into(),
+                        });
+
+                        continue;
+                    }
+                };
+
+                let container = find_container(
+                    container.as_str(),
+                    &backpack.0,
+                    position,
+                    zone,
+                    containers
+                        .iter()
+                        .map(|(entity, details, _, p, z)| (entity, details.name.as_str(), p, z)),
+                );
+
+                let (container, container_name, held) = if let Some((entity, details, held, _, _)) =
+                    container.and_then(|entity| containers.get(entity).ok())
+                {
+                    (entity, details.name.clone(), held)
+                } else {
+                    output.send(NetworkOutput {
+                        id: client.id,
+                        body: "You don't see anything like that to take it from.".into(),
+                    });
+
+                    continue;
+                };
+
+                let picked =
+                    Target::parse(target.as_str()).pick(held.items.iter().filter_map(|entity| {
+                        items.get(*entity).ok().map(|(details, _, _, stack, _)| {
+                            (*entity, details.name.as_str(), quantity(stack))
+                        })
+                    }));
+
+                if picked.is_empty() {
+                    output.send(NetworkOutput {
+                        id: client.id,
+                        body: format!("There's nothing like that in the {container_name}."),
+                    });
+
+                    continue;
+                }
+
+                let contents = |entity| {
+                    containers
+                        .get(entity)
+                        .ok()
+                        .map(|(_, _, container, _, _)| container.items.as_slice())
+                };
+                let lookup = |entity| {
+                    items.get(entity).ok().map(|(_, weight, bulk, stack, _)| {
+                        (
+                            weight,
+                            bulk,
+                            quantity(stack),
+                            contents(entity).unwrap_or_default(),
+                        )
+                    })
+                };
+
+                // Whatever's in a container the player is carrying already
+                // weighs on them, so only its bulk matters.
+                let parents = parents(
+                    containers
+                        .iter()
+                        .map(|(entity, _, container, _, _)| (entity, container)),
+                );
+                let carried = enclosing(container, &parents)
+                    .into_iter()
+                    .chain([container])
+                    .any(|entity| backpack.0.contains(&entity));
+
+                // Stacks already in the backpack, and how many each holds.
+                let mut stacks = backpack
+                    .0
+                    .iter()
+                    .filter(|entity| contents(**entity).is_none())
+                    .filter_map(|entity| {
+                        items.get(*entity).ok().and_then(|(_, _, _, stack, by)| {
+                            stacks_by(by).map(|key| (key.to_string(), (*entity, quantity(stack))))
+                        })
+                    })
+                    .collect::<HashMap<_, _>>();
+
+                let mut load = Load::of_player(&backpack.0, equipment, &lookup);
+                let mut removed = Vec::new();
+                let mut taken = Vec::new();
+                let mut refused = None;
+
+                for (entity, wanted, total) in picked {
+                    let (name, key) = if let Ok((details, _, _, _, by)) = items.get(entity) {
+                        (details.name.clone(), stacks_by(by))
+                    } else {
+                        continue;
+                    };
+
+                    let (weight, bulk) = Load::each(entity, total, &lookup);
+                    let weight = (!carried).then(|| weight);
+
+                    let amount = capacity.map_or(wanted, |capacity| {
+                        load.fits(capacity, weight.as_ref(), Some(&bulk), wanted)
+                    });
+
+                    if amount < wanted {
+                        refused = Some(
+                            if capacity.map_or(false, |capacity| {
+                                load.fits(capacity, weight.as_ref(), None, 1) == 0
+                            }) {
+                                "That's too heavy for you to carry."
+                            } else {
+                                "You don't have room for that."
+                            },
+                        );
+                    }
+
+                    if amount == 0 {
+                        continue;
+                    }
+
+                    load.add(weight.as_ref(), Some(&bulk), amount);
+
+                    if amount == total {
+                        removed.push(entity);
+                    }
+
+                    if let Some(moved) = move_stack(
+                        &mut commands,
+                        &mut stacks,
+                        key.filter(|_| contents(entity).is_none()),
+                        entity,
+                        amount,
+                        total,
+                    ) {
+                        backpack.0.push(moved);
+                    }
+
+                    taken.push((name, amount));
+                }
+
+                if let Ok((_, _, mut held, _, _)) = containers.get_mut(container) {
+                    held.items.retain(|entity| !removed.contains(entity));
+                }
+
+                output.send(NetworkOutput {
+                    id: client.id,
+                    body: match (taken.is_empty(), refused) {
+                        (true, refused) => refused.unwrap_or("You can't take that.").to_string(),
+                        (false, Some(_)) => format!(
+                            "You take {} from the {container_name}, but can't carry any more.",
+                            describe(&taken)
+                        ),
+                        (false, None) => {
+                            format!("You take {} from the {container_name}.", describe(&taken))
+                        }
+                    },
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::{
+        items::components::{
+            backpack::Backpack, container::Container, stack::Stack, weight::Weight,
+        },
+        network::events::NetworkOutput,
+        player::{
+            components::client::NetworkClient, events::action_event::ActionEvent,
+            resources::player_index::PlayerIndex, systems::index_players::index_players,
+        },
+        test::bundles::utils::{
+            item_bundle, item_in_backpack_bundle, player_bundle, ItemBundle, PlayerBundle,
+        },
+    };
+
+    #[test]
+    fn get() {
+        let mut app = App::new();
+
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::get);
+
+        let carried = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Apple".into(),
+                quantity: 2,
+                ..Default::default()
+            }))
+            .id();
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                items: vec![carried],
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        let apples = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Apple".into(),
+                quantity: 3,
+                ..Default::default()
+            }))
+            .id();
+
+        let chest = app
+            .world
+            .spawn()
+            .insert_bundle(item_bundle(ItemBundle {
+                name: "Chest".into(),
+                ..Default::default()
+            }))
+            .insert(Container {
+                bulk: 10.0,
+                items: vec![apples],
+            })
+            .id();
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: "take all apples from chest".into(),
+            });
+
+        app.update();
+
+        assert!(app.world.get::<Container>(chest).unwrap().items.is_empty());
+        assert!(app.world.get_entity(apples).is_none());
+        assert_eq!(app.world.get::<Stack>(carried).unwrap().0, 5);
+        assert_eq!(app.world.get::<Backpack>(player).unwrap().0, vec![carried]);
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(output.id, player_client_id);
+        assert_eq!(output.body, "You take 3 Apples from the Chest.");
+    }
+
+    #[test]
+    fn too_heavy() {
+        let mut app = App::new();
+
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::get);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        let anvil = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Anvil".into(),
+                ..Default::default()
+            }))
+            .insert(Weight(100.0))
+            .id();
+
+        let chest = app
+            .world
+            .spawn()
+            .insert_bundle(item_bundle(ItemBundle {
+                name: "Chest".into(),
+                ..Default::default()
+            }))
+            .insert(Container {
+                bulk: 10.0,
+                items: vec![anvil],
+            })
+            .id();
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: "get anvil from chest".into(),
+            });
+
+        app.update();
+
+        assert_eq!(
+            app.world.get::<Container>(chest).unwrap().items,
+            vec![anvil]
+        );
+        assert!(app.world.get::<Backpack>(player).unwrap().0.is_empty());
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(output.body, "That's too heavy for you to carry.");
+    }
+}
@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::{
+    items::components::refuses_gifts::RefusesGifts,
+    network::events::{NetworkInput, NetworkOutput},
+    player::{
+        components::{client::NetworkClient, online::Online},
+        utils::players::Players,
+    },
+};
+
+/// Turn accepting gifts from other players on or off with `gifts on` and
+/// `gifts off`, or check which it is with `gifts`.
+pub fn gifts(
+    mut commands: Commands,
+    mut input: EventReader<NetworkInput>,
+    mut output: EventWriter<NetworkOutput>,
+    players: Players<(Entity, &NetworkClient, Option<&RefusesGifts>), With<Online>>,
+) {
+    lazy_static! {
+        static ref CMD: Regex = Regex::new("^gifts( +(on|off))?$").unwrap();
+    }
+
+    for message in input.iter() {
+        if let Some(captures) = CMD.captures(&message.body.to_lowercase()) {
+            if let Some((entity, client, refuses)) = players.sender(&message.id) {
+                let body = match captures.get(2).map(|m| m.as_str()) {
+                    Some("on") => {
+                        commands.entity(entity).remove::<RefusesGifts>();
+
+                        "You're now accepting gifts."
+                    }
+                    Some(_) => {
+                        commands.entity(entity).insert(RefusesGifts);
+
+                        "You're now refusing gifts."
+                    }
+                    None if refuses.is_some() => "You're refusing gifts.",
+                    None => "You're accepting gifts.",
+                };
+
+                output.send(NetworkOutput {
+                    id: client.id,
+                    body: body.to_string(),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::{
+        items::components::refuses_gifts::RefusesGifts,
+        network::events::{NetworkInput, NetworkOutput},
+        player::{
+            components::client::NetworkClient, resources::player_index::PlayerIndex,
+            systems::index_players::index_players,
+        },
+        test::bundles::utils::{player_bundle, PlayerBundle},
+    };
+
+    #[test]
+    fn gifts() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkInput>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::gifts);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        app.world
+            .resource_mut::<Events<NetworkInput>>()
+            .send(NetworkInput {
+                id: player_client_id,
+                body: "gifts off".into(),
+                internal: false,
+            });
+
+        app.update();
+
+        assert!(app.world.get::<RefusesGifts>(player).is_some());
+
+        app.world
+            .resource_mut::<Events<NetworkInput>>()
+            .send(NetworkInput {
+                id: player_client_id,
+                body: "gifts on".into(),
+                internal: false,
+            });
+
+        app.update();
+
+        assert!(app.world.get::<RefusesGifts>(player).is_none());
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let outputs = output_reader
+            .iter(output_events)
+            .map(|output| output.body.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            outputs,
+            vec!["You're now refusing gifts.", "You're now accepting gifts."]
+        );
+    }
+}
@@ -0,0 +1,516 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{
+    items::{
+        components::{
+            backpack::Backpack, can_take::CanTake, capacity::Capacity, container::Container,
+            equipment::Equipment, refuses_gifts::RefusesGifts, stack::Stack,
+        },
+        utils::{
+            load::Loads,
+            stacks::{describe, move_stack, quantity, stacks_by, Stacking, Target},
+        },
+    },
+    network::events::NetworkOutput,
+    player::{
+        components::{character::Character, client::NetworkClient, online::Online},
+        events::action_event::ActionEvent,
+        utils::{actions::Action, players::Players},
+    },
+    spatial::components::{position::Position, zone::Zone},
+    visual::components::details::Details,
+};
+
+/// Hand items from one [`Backpack`] to another player on the same tile, like
+/// `give apple to Bob` or `give 3 apples to bob`. Only as much as fits within
+/// their [`Capacity`] changes hands, and nothing does if they're [`RefusesGifts`].
+pub fn give(
+    mut commands: Commands,
+    mut actions: EventReader<ActionEvent>,
+    mut output: EventWriter<NetworkOutput>,
+    mut players: Players<
+        (
+            Entity,
+            &NetworkClient,
+            &Character,
+            &Position,
+            &Zone,
+            &mut Backpack,
+            Option<&Capacity>,
+            Option<&Equipment>,
+            Option<&RefusesGifts>,
+        ),
+        With<Online>,
+    >,
+    items: Query<
+        (&Details, Option<&Stack>, Option<&Container>, Stacking),
+        (With<CanTake>, Without<Position>),
+    >,
+    loads: Loads,
+) {
+    for message in actions.iter() {
+        let body = message.body.to_lowercase();
+
+        let captures = if let Some(captures) = Action::Give.captures(&body) {
+            captures
+        } else {
+            continue;
+        };
+
+        let (giver, client, character, position, zone, backpack, _, _, _) =
+            if let Some(player) = players.sender(&message.id) {
+                player
+            } else {
+                continue;
+            };
+
+        let (target, receiver_name) = match (captures.get(2), captures.get(4)) {
+            (Some(target), Some(name)) => (target, name.as_str().trim()),
+            (Some(_), None) => {
+                output.send(NetworkOutput {
+                    id: client.id,
+                    body: "Give it to whom?".into(),
+                });
+
+                continue;
+            }
+            _ => {
+                output.send(NetworkOutput {
+                    id: client.id,
+                    body: "Give what?".into(),
+                });
+
+                continue;
+            }
+        };
+
+        if character.name.to_lowercase() == receiver_name {
+            output.send(NetworkOutput {
+                id: client.id,
+                body: "You can't give things to yourself.".into(),
+            });
+
+            continue;
+        }
+
+        let (
+            receiver,
+            receiver_client,
+            receiver_character,
+            _,
+            _,
+            receiver_backpack,
+            capacity,
+            receiver_equipment,
+            refuses,
+        ) = if let Some(player) = players.iter().find(|(e, _, c, p, z, _, _, _, _)| {
+            *e != giver
+                && p.0 == position.0
+                && z.0 == zone.0
+                && c.name.to_lowercase() == receiver_name
+        }) {
+            player
+        } else {
+            output.send(NetworkOutput {
+                id: client.id,
+                body: "You don't see them here.".into(),
+            });
+
+            continue;
+        };
+
+        if refuses.is_some() {
+            output.send(NetworkOutput {
+                id: client.id,
+                body: format!("{} isn't accepting gifts.", receiver_character.name),
+            });
+
+            continue;
+        }
+
+        let picked = Target::parse(target.as_str()).pick(backpack.0.iter().filter_map(|entity| {
+            items
+                .get(*entity)
+                .ok()
+                .map(|(details, stack, _, _)| (*entity, details.name.as_str(), quantity(stack)))
+        }));
+
+        if picked.is_empty() {
+            output.send(NetworkOutput {
+                id: client.id,
+                body: "You don't have that.".into(),
+            });
+
+            continue;
+        }
+
+        // Stacks already in the receiver's backpack, and how many each holds.
+        let mut stacks = receiver_backpack
+            .0
+            .iter()
+            .filter_map(|entity| {
+                items
+                    .get(*entity)
+                    .ok()
+                    .and_then(|(_, stack, container, by)| {
+                        stacks_by(by)
+                            .filter(|_| container.is_none())
+                            .map(|key| (key.to_string(), (*entity, quantity(stack))))
+                    })
+            })
+            .collect::<HashMap<_, _>>();
+
+        let mut load = loads.of_player(&receiver_backpack.0, receiver_equipment);
+        let mut removed = Vec::new();
+        let mut received = Vec::new();
+        let mut given = Vec::new();
+        let mut refused = None;
+
+        for (entity, wanted, total) in picked {
+            let (name, key) = if let Ok((details, _, container, by)) = items.get(entity) {
+                (
+                    details.name.clone(),
+                    stacks_by(by).filter(|_| container.is_none()),
+                )
+            } else {
+                continue;
+            };
+
+            let (weight, bulk) = loads.each(entity, total);
+
+            let amount = capacity.map_or(wanted, |capacity| {
+                load.fits(capacity, Some(&weight), Some(&bulk), wanted)
+            });
+
+            if amount < wanted {
+                refused = Some(
+                    if capacity.map_or(false, |capacity| {
+                        load.fits(capacity, Some(&weight), None, 1) == 0
+                    }) {
+                        format!("That's too heavy for {} to carry.", receiver_character.name)
+                    } else {
+                        format!("{} doesn't have room for that.", receiver_character.name)
+                    },
+                );
+            }
+
+            if amount == 0 {
+                continue;
+            }
+
+            load.add(Some(&weight), Some(&bulk), amount);
+
+            if amount == total {
+                removed.push(entity);
+            }
+
+            if let Some(moved) = move_stack(&mut commands, &mut stacks, key, entity, amount, total)
+            {
+                received.push(moved);
+            }
+
+            given.push((name, amount));
+        }
+
+        if given.is_empty() {
+            output.send(NetworkOutput {
+                id: client.id,
+                body: refused.unwrap_or_else(|| "You don't have that.".into()),
+            });
+
+            continue;
+        }
+
+        let described = describe(&given);
+
+        output.send(NetworkOutput {
+            id: client.id,
+            body: match refused {
+                Some(_) => format!(
+                    "You give {described} to {}, but they can't carry any more.",
+                    receiver_character.name
+                ),
+                None => format!("You give {described} to {}.", receiver_character.name),
+            },
+        });
+
+        output.send(NetworkOutput {
+            id: receiver_client.id,
+            body: format!("{} gives you {described}.", character.name),
+        });
+
+        for (_, other_client, _, _, _, _, _, _, _) in
+            players.iter().filter(|(e, _, _, p, z, _, _, _, _)| {
+                *e != giver && *e != receiver && p.0 == position.0 && z.0 == zone.0
+            })
+        {
+            output.send(NetworkOutput {
+                id: other_client.id,
+                body: format!(
+                    "{} gives {described} to {}.",
+                    character.name, receiver_character.name
+                ),
+            });
+        }
+
+        if let Ok((_, _, _, _, _, mut backpack, _, _, _)) = players.get_mut(giver) {
+            backpack.0.retain(|entity| !removed.contains(entity));
+        }
+
+        if let Ok((_, _, _, _, _, mut backpack, _, _, _)) = players.get_mut(receiver) {
+            backpack.0.extend(received);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::{
+        items::components::{
+            backpack::Backpack, refuses_gifts::RefusesGifts, stack::Stack, weight::Weight,
+        },
+        network::events::NetworkOutput,
+        player::{
+            components::client::NetworkClient, events::action_event::ActionEvent,
+            resources::player_index::PlayerIndex, systems::index_players::index_players,
+        },
+        test::{
+            bundles::utils::{item_in_backpack_bundle, player_bundle, ItemBundle, PlayerBundle},
+            prototypes::utils::insert_prototypes,
+        },
+    };
+
+    fn setup() -> App {
+        let mut app = App::new();
+
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::give);
+        insert_prototypes(&mut app);
+
+        app
+    }
+
+    #[test]
+    fn give() {
+        let mut app = setup();
+
+        let apples = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Apple".into(),
+                quantity: 3,
+                ..Default::default()
+            }))
+            .id();
+
+        let giver = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                name: "Alice".into(),
+                items: vec![apples],
+                ..Default::default()
+            }))
+            .id();
+
+        let receiver = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                name: "Bob".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        let onlooker = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                name: "Carol".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        let giver_client_id = app.world.get::<NetworkClient>(giver).unwrap().id;
+        let receiver_client_id = app.world.get::<NetworkClient>(receiver).unwrap().id;
+        let onlooker_client_id = app.world.get::<NetworkClient>(onlooker).unwrap().id;
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: giver_client_id,
+                body: "give 2 apples to bob".into(),
+            });
+
+        app.update();
+
+        let given = app.world.get::<Backpack>(receiver).unwrap().0.clone();
+
+        assert_eq!(given.len(), 1);
+        assert_eq!(app.world.get::<Stack>(given[0]).unwrap().0, 2);
+        assert_eq!(app.world.get::<Stack>(apples).unwrap().0, 1);
+        assert_eq!(app.world.get::<Backpack>(giver).unwrap().0, vec![apples]);
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let outputs = output_reader
+            .iter(output_events)
+            .map(|output| (output.id, output.body.as_str()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            outputs,
+            vec![
+                (giver_client_id, "You give 2 Apples to Bob."),
+                (receiver_client_id, "Alice gives you 2 Apples."),
+                (onlooker_client_id, "Alice gives 2 Apples to Bob."),
+            ]
+        );
+    }
+
+    #[test]
+    fn same_tick() {
+        let mut app = setup();
+
+        let mut givers = Vec::new();
+
+        for name in ["Alice", "Bob"] {
+            let apple = app
+                .world
+                .spawn()
+                .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                    name: "Apple".into(),
+                    ..Default::default()
+                }))
+                .id();
+
+            givers.push(
+                app.world
+                    .spawn()
+                    .insert_bundle(player_bundle(PlayerBundle {
+                        name: name.into(),
+                        items: vec![apple],
+                        ..Default::default()
+                    }))
+                    .id(),
+            );
+        }
+
+        let apples = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Apple".into(),
+                quantity: 3,
+                ..Default::default()
+            }))
+            .id();
+
+        let receiver = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                name: "Carol".into(),
+                items: vec![apples],
+                ..Default::default()
+            }))
+            .id();
+
+        // Both land in the same frame, so neither sees what the other added.
+        for giver in givers {
+            let id = app.world.get::<NetworkClient>(giver).unwrap().id;
+
+            app.world
+                .resource_mut::<Events<ActionEvent>>()
+                .send(ActionEvent {
+                    id,
+                    body: "give apple to carol".into(),
+                });
+        }
+
+        app.update();
+
+        assert_eq!(app.world.get::<Backpack>(receiver).unwrap().0, vec![apples]);
+        assert_eq!(app.world.get::<Stack>(apples).unwrap().0, 5);
+    }
+
+    #[test]
+    fn refused() {
+        let mut app = setup();
+
+        let anvil = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Anvil".into(),
+                ..Default::default()
+            }))
+            .insert(Weight(100.0))
+            .id();
+
+        let giver = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                name: "Alice".into(),
+                items: vec![anvil],
+                ..Default::default()
+            }))
+            .id();
+
+        let receiver = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                name: "Bob".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        let giver_client_id = app.world.get::<NetworkClient>(giver).unwrap().id;
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: giver_client_id,
+                body: "give anvil to bob".into(),
+            });
+
+        app.update();
+
+        app.world.entity_mut(receiver).insert(RefusesGifts);
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: giver_client_id,
+                body: "give anvil to bob".into(),
+            });
+
+        app.update();
+
+        assert_eq!(app.world.get::<Backpack>(giver).unwrap().0, vec![anvil]);
+        assert!(app.world.get::<Backpack>(receiver).unwrap().0.is_empty());
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let outputs = output_reader
+            .iter(output_events)
+            .map(|output| output.body.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            outputs,
+            vec![
+                "That's too heavy for Bob to carry.",
+                "Bob isn't accepting gifts.",
+            ]
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use bevy::prelude::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::{
+    items::{
+        components::{backpack::Backpack, can_take::CanTake, container::Container, stack::Stack},
+        utils::{
+            load::Loads,
+            nesting::find_container,
+            stacks::{listing, quantity},
+        },
+    },
+    network::events::{NetworkInput, NetworkOutput},
+    player::{
+        components::{client::NetworkClient, online::Online},
+        utils::players::Players,
+    },
+    spatial::components::{position::Position, zone::Zone},
+    visual::components::{details::Details, sprite::Sprite},
+};
+
+/// List what's inside a [`Container`] in the [`Backpack`] or on the ground,
+/// like `look in chest`, and how full it is.
+pub fn look_in(
+    mut input: EventReader<NetworkInput>,
+    mut output: EventWriter<NetworkOutput>,
+    players: Players<(&NetworkClient, &Position, &Zone, &Backpack), With<Online>>,
+    containers: Query<(
+        Entity,
+        &Details,
+        &Container,
+        Option<&Position>,
+        Option<&Zone>,
+    )>,
+    items: Query<
+        (&Details, &Sprite, Option<&Stack>, Option<&Container>),
+        (With<CanTake>, Without<Position>),
+    >,
+    loads: Loads,
+) {
+    lazy_static! {
+        static ref CMD: Regex = Regex::new("^(look|l) +in +(.+)$").unwrap();
+    }
+
+    for message in input.iter() {
+        if let Some((client, position, zone, backpack)) = players.sender(&message.id) {
+            if let Some(captures) = CMD.captures(&message.body.to_lowercase()) {
+                let container = find_container(
+                    &captures[2],
+                    &backpack.0,
+                    position,
+                    zone,
+                    containers
+                        .iter()
+                        .map(|(entity, details, _, p, z)| (entity, details.name.as_str(), p, z)),
+                )
+                .and_then(|entity| containers.get(entity).ok());
+
+                let (details, container) = if let Some((_, details, container, _, _)) = container {
+                    (details, container)
+                } else {
+                    output.send(NetworkOutput {
+                        id: client.id,
+                        body: "You don't see anything like that to look in.".into(),
+                    });
+
+                    continue;
+                };
+
+                let mut lines = Vec::new();
+
+                for entity in &container.items {
+                    if let Ok((details, sprite, stack, inner)) = items.get(*entity) {
+                        lines.push(listing(
+                            sprite,
+                            &details.name,
+                            quantity(stack),
+                            inner.map(|inner| inner.items.len()),
+                        ));
+                    }
+                }
+
+                if lines.is_empty() {
+                    output.send(NetworkOutput {
+                        id: client.id,
+                        body: format!("The {} is empty.", details.name),
+                    });
+
+                    continue;
+                }
+
+                let load = loads.of(&container.items);
+
+                lines.push(format!(
+                    "Filled to {:.1} of {:.1} bulk.",
+                    load.bulk, container.bulk
+                ));
+
+                output.send(NetworkOutput {
+                    id: client.id,
+                    body: format!("The {} contains:\r\n{}", details.name, lines.join("\r\n")),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+    use yansi::Paint;
+
+    use crate::{
+        items::components::{bulk::Bulk, container::Container},
+        network::events::{NetworkInput, NetworkOutput},
+        player::{
+            components::client::NetworkClient, resources::player_index::PlayerIndex,
+            systems::index_players::index_players,
+        },
+        test::bundles::utils::{
+            item_bundle, item_in_backpack_bundle, player_bundle, ItemBundle, PlayerBundle,
+        },
+    };
+
+    #[test]
+    fn look_in() {
+        Paint::disable();
+
+        let mut app = App::new();
+
+        app.add_event::<NetworkInput>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::look_in);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        let apples = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Apple".into(),
+                character: "o".into(),
+                quantity: 3,
+                ..Default::default()
+            }))
+            .insert(Bulk(1.0))
+            .id();
+
+        app.world
+            .spawn()
+            .insert_bundle(item_bundle(ItemBundle {
+                name: "Chest".into(),
+                ..Default::default()
+            }))
+            .insert(Container {
+                bulk: 10.0,
+                items: vec![apples],
+            });
+
+        app.world
+            .resource_mut::<Events<NetworkInput>>()
+            .send(NetworkInput {
+                id: player_client_id,
+                body: "look in chest".into(),
+                internal: false,
+            });
+
+        app.update();
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(output.id, player_client_id);
+        assert_eq!(
+            output.body,
+            "The Chest contains:\r\no 3 Apples\r\nFilled to 3.0 of 10.0 bulk."
+        );
+    }
+}
@@ -1,3 +1,16 @@
 pub mod backpack;
+pub mod cancel_trades;
+pub mod consume;
 pub mod drop;
+pub mod encumbrance;
+pub mod equipment;
+pub mod fish;
+pub mod get;
+pub mod gifts;
+pub mod give;
+pub mod look_in;
+pub mod put;
+pub mod remove;
 pub mod take;
+pub mod trade;
+pub mod wear;
@@ -0,0 +1,473 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{
+    items::{
+        components::{
+            backpack::Backpack, bulk::Bulk, can_take::CanTake, container::Container, stack::Stack,
+            weight::Weight,
+        },
+        utils::{
+            load::Load,
+            nesting::{can_nest, find_container, parents},
+            stacks::{describe, move_stack, quantity, stacks_by, Stacking, Target},
+        },
+    },
+    network::events::NetworkOutput,
+    player::{
+        components::{client::NetworkClient, online::Online},
+        events::action_event::ActionEvent,
+        utils::{actions::Action, players::Players},
+    },
+    spatial::components::{position::Position, zone::Zone},
+    visual::components::details::Details,
+};
+
+/// Put items from the [`Backpack`] into a [`Container`] that's either in the
+/// backpack too or on the ground, like `put apple in pouch` or `put all into chest`.
+/// Only as much as the container has room for goes in, and containers can only
+/// be nested so deep.
+pub fn put(
+    mut commands: Commands,
+    mut actions: EventReader<ActionEvent>,
+    mut output: EventWriter<NetworkOutput>,
+    mut players: Players<(&NetworkClient, &Position, &Zone, &mut Backpack), With<Online>>,
+    items: Query<
+        (
+            &Details,
+            Option<&Weight>,
+            Option<&Bulk>,
+            Option<&Stack>,
+            Stacking,
+        ),
+        (With<CanTake>, Without<Position>),
+    >,
+    mut containers: Query<(
+        Entity,
+        &Details,
+        &mut Container,
+        Option<&Position>,
+        Option<&Zone>,
+    )>,
+) {
+    for message in actions.iter() {
+        if let Some((client, position, zone, mut backpack)) = players.sender_mut(&message.id) {
+            if let Some(captures) = Action::Put.captures(&message.body.to_lowercase()) {
+                let (target, container) = match (captures.get(2), captures.get(5)) {
+                    (Some(target), Some(container)) => (target, container),
+                    (Some(_), None) => {
+                        output.send(NetworkOutput {
+                            id: client.id,
+                            body: "Put it in what?".into(),
+                        });
+
+                        continue;
+                    }
+                    _ => {
+                        output.send(NetworkOutput {
+                            id: client.id,
+                            body: "Put what?".into(),
+                        });
+
+                        continue;
+                    }
+                };
+
+                let container = find_container(
+                    container.as_str(),
+                    &backpack.0,
+                    position,
+                    zone,
+                    containers
+                        .iter()
+                        .map(|(entity, details, _, p, z)| (entity, details.name.as_str(), p, z)),
+                );
+
+                let (container, container_name, held) = if let Some((entity, details, held, _, _)) =
+                    container.and_then(|entity| containers.get(entity).ok())
+                {
+                    (entity, details.name.clone(), held)
+                } else {
+                    output.send(NetworkOutput {
+                        id: client.id,
+                        body: "You don't see anything like that to put it in.".into(),
+                    });
+
+                    continue;
+                };
+
+                let target = Target::parse(target.as_str());
+
+                // Leave out the container itself, so `put all in pouch` doesn't
+                // try to put the pouch in too.
+                let picked = target.pick(
+                    backpack
+                        .0
+                        .iter()
+                        .filter(|entity| **entity != container)
+                        .filter_map(|entity| {
+                            items.get(*entity).ok().map(|(details, _, _, stack, _)| {
+                                (*entity, details.name.as_str(), quantity(stack))
+                            })
+                        }),
+                );
+
+                if picked.is_empty() {
+                    output.send(NetworkOutput {
+                        id: client.id,
+                        body:
+                            if target.name.is_some() && target.matches(container, &container_name) {
+                                "You can't put something inside itself."
+                            } else {
+                                "You don't have that."
+                            }
+                            .into(),
+                    });
+
+                    continue;
+                }
+
+                let parents = parents(
+                    containers
+                        .iter()
+                        .map(|(entity, _, container, _, _)| (entity, container)),
+                );
+                let contents = |entity| {
+                    containers
+                        .get(entity)
+                        .ok()
+                        .map(|(_, _, container, _, _)| container.items.as_slice())
+                };
+                let lookup = |entity| {
+                    items.get(entity).ok().map(|(_, weight, bulk, stack, _)| {
+                        (
+                            weight,
+                            bulk,
+                            quantity(stack),
+                            contents(entity).unwrap_or_default(),
+                        )
+                    })
+                };
+
+                // Stacks already in the container, and how many each holds.
+                let mut stacks = held
+                    .items
+                    .iter()
+                    .filter(|entity| contents(**entity).is_none())
+                    .filter_map(|entity| {
+                        items.get(*entity).ok().and_then(|(_, _, _, stack, by)| {
+                            stacks_by(by).map(|key| (key.to_string(), (*entity, quantity(stack))))
+                        })
+                    })
+                    .collect::<HashMap<_, _>>();
+
+                let capacity = held.capacity();
+                let mut load = Load::of(&held.items, &lookup);
+                let mut added = Vec::new();
+                let mut put = Vec::new();
+                let mut refused = None;
+
+                for (entity, wanted, total) in picked {
+                    let (name, bulk, key) = if let Ok((details, _, bulk, _, by)) = items.get(entity)
+                    {
+                        (details.name.clone(), bulk, stacks_by(by))
+                    } else {
+                        continue;
+                    };
+
+                    if let Err(reason) = can_nest(entity, container, &parents, &contents) {
+                        refused = Some(reason.to_string());
+
+                        continue;
+                    }
+
+                    let amount = load.fits(&capacity, None, bulk, wanted);
+
+                    if amount < wanted {
+                        refused =
+                            Some(format!("There's no room for that in the {container_name}."));
+                    }
+
+                    if amount == 0 {
+                        continue;
+                    }
+
+                    load.add(None, bulk, amount);
+
+                    if amount == total {
+                        backpack.0.retain(|e| *e != entity);
+                    }
+
+                    if let Some(moved) = move_stack(
+                        &mut commands,
+                        &mut stacks,
+                        key.filter(|_| contents(entity).is_none()),
+                        entity,
+                        amount,
+                        total,
+                    ) {
+                        added.push(moved);
+                    }
+
+                    put.push((name, amount));
+                }
+
+                if let Ok((_, _, mut held, _, _)) = containers.get_mut(container) {
+                    held.items.extend(added);
+                }
+
+                output.send(NetworkOutput {
+                    id: client.id,
+                    body: match (put.is_empty(), refused) {
+                        (true, refused) => refused.unwrap_or_else(|| "You don't have that.".into()),
+                        (false, Some(_)) => format!(
+                            "You put {} in the {container_name}, but the rest won't fit.",
+                            describe(&put)
+                        ),
+                        (false, None) => {
+                            format!("You put {} in the {container_name}.", describe(&put))
+                        }
+                    },
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::{
+        items::components::{backpack::Backpack, bulk::Bulk, container::Container, stack::Stack},
+        network::events::NetworkOutput,
+        player::{
+            components::client::NetworkClient, events::action_event::ActionEvent,
+            resources::player_index::PlayerIndex, systems::index_players::index_players,
+        },
+        test::{
+            bundles::utils::{
+                item_bundle, item_in_backpack_bundle, player_bundle, ItemBundle, PlayerBundle,
+            },
+            prototypes::utils::insert_prototypes,
+        },
+    };
+
+    #[test]
+    fn put() {
+        let mut app = App::new();
+
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::put);
+        insert_prototypes(&mut app);
+
+        let apples = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Apple".into(),
+                quantity: 3,
+                ..Default::default()
+            }))
+            .id();
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                items: vec![apples],
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        let chest = app
+            .world
+            .spawn()
+            .insert_bundle(item_bundle(ItemBundle {
+                name: "Chest".into(),
+                ..Default::default()
+            }))
+            .insert(Container {
+                bulk: 10.0,
+                items: Vec::new(),
+            })
+            .id();
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: "put 2 apples in chest".into(),
+            });
+
+        app.update();
+
+        let inside = app.world.get::<Container>(chest).unwrap().items.clone();
+
+        assert_eq!(inside.len(), 1);
+        assert_eq!(app.world.get::<Stack>(inside[0]).unwrap().0, 2);
+        assert_eq!(app.world.get::<Stack>(apples).unwrap().0, 1);
+        assert_eq!(app.world.get::<Backpack>(player).unwrap().0, vec![apples]);
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(output.id, player_client_id);
+        assert_eq!(output.body, "You put 2 Apples in the Chest.");
+    }
+
+    #[test]
+    fn no_room() {
+        let mut app = App::new();
+
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::put);
+        insert_prototypes(&mut app);
+
+        let logs = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Log".into(),
+                quantity: 2,
+                ..Default::default()
+            }))
+            .insert(Bulk(4.0))
+            .id();
+
+        let pouch = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Pouch".into(),
+                ..Default::default()
+            }))
+            .insert(Container {
+                bulk: 5.0,
+                items: Vec::new(),
+            })
+            .id();
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                items: vec![logs, pouch],
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: "put all log in pouch".into(),
+            });
+
+        app.update();
+
+        assert_eq!(app.world.get::<Container>(pouch).unwrap().items.len(), 1);
+        assert_eq!(app.world.get::<Stack>(logs).unwrap().0, 1);
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: "put pouch in pouch".into(),
+            });
+
+        app.update();
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let outputs = output_reader
+            .iter(output_events)
+            .map(|output| output.body.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            outputs,
+            vec![
+                "You put the Log in the Pouch, but the rest won't fit.",
+                "You can't put something inside itself.",
+            ]
+        );
+    }
+
+    #[test]
+    fn all_in_carried() {
+        let mut app = App::new();
+
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::put);
+        insert_prototypes(&mut app);
+
+        let apples = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Apple".into(),
+                quantity: 2,
+                ..Default::default()
+            }))
+            .id();
+
+        let pouch = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Pouch".into(),
+                ..Default::default()
+            }))
+            .insert(Container {
+                bulk: 5.0,
+                items: Vec::new(),
+            })
+            .id();
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                items: vec![apples, pouch],
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: "put all in pouch".into(),
+            });
+
+        app.update();
+
+        assert_eq!(
+            app.world.get::<Container>(pouch).unwrap().items,
+            vec![apples]
+        );
+        assert_eq!(app.world.get::<Backpack>(player).unwrap().0, vec![pouch]);
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(output.body, "You put 2 Apples in the Pouch.");
+    }
+}
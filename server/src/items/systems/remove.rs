@@ -0,0 +1,236 @@
+use bevy::prelude::*;
+
+use crate::{
+    items::{
+        components::{
+            backpack::Backpack, can_take::CanTake, capacity::Capacity, container::Container,
+            equipment::Equipment, stack::Stack,
+        },
+        utils::{
+            load::Loads,
+            stacks::{quantity, stacks_by, stow, Stacking, Target},
+        },
+    },
+    network::events::NetworkOutput,
+    player::{
+        components::{client::NetworkClient, online::Online},
+        events::action_event::ActionEvent,
+        utils::{actions::Action, players::Players},
+    },
+    spatial::components::position::Position,
+    visual::components::details::Details,
+};
+
+/// Take off something worn or put away something wielded, like `remove cap`,
+/// returning it to the [`Backpack`] as long as there's room for it.
+pub fn remove(
+    mut commands: Commands,
+    mut actions: EventReader<ActionEvent>,
+    mut output: EventWriter<NetworkOutput>,
+    mut players: Players<
+        (
+            &NetworkClient,
+            &mut Backpack,
+            &mut Equipment,
+            Option<&Capacity>,
+        ),
+        With<Online>,
+    >,
+    items: Query<
+        (&Details, Option<&Stack>, Option<&Container>, Stacking),
+        (With<CanTake>, Without<Position>),
+    >,
+    loads: Loads,
+) {
+    for message in actions.iter() {
+        let body = message.body.to_lowercase();
+
+        let captures = if let Some(captures) = Action::Remove.captures(&body) {
+            captures
+        } else {
+            continue;
+        };
+
+        let (client, mut backpack, mut equipment, capacity) =
+            if let Some(player) = players.sender_mut(&message.id) {
+                player
+            } else {
+                continue;
+            };
+
+        let target = if let Some(target) = captures.get(2) {
+            target
+        } else {
+            output.send(NetworkOutput {
+                id: client.id,
+                body: "Remove what?".into(),
+            });
+
+            continue;
+        };
+
+        let equipped = equipment.items();
+
+        let picked =
+            Target::parse(target.as_str()).pick(equipped.iter().filter_map(|(_, entity)| {
+                items
+                    .get(*entity)
+                    .ok()
+                    .map(|(details, _, _, _)| (*entity, details.name.as_str(), 1))
+            }));
+
+        let (slot, entity) = if let Some(equipped) = picked
+            .first()
+            .and_then(|(picked, _, _)| equipped.iter().find(|(_, e)| e == picked))
+        {
+            *equipped
+        } else {
+            output.send(NetworkOutput {
+                id: client.id,
+                body: "You don't have that on.".into(),
+            });
+
+            continue;
+        };
+
+        let (_, bulk) = loads.each(entity, 1);
+
+        if capacity.map_or(false, |capacity| {
+            loads.of(&backpack.0).fits(capacity, None, Some(&bulk), 1) == 0
+        }) {
+            output.send(NetworkOutput {
+                id: client.id,
+                body: "You don't have room for that in your backpack.".into(),
+            });
+
+            continue;
+        }
+
+        equipment.0.remove(&slot);
+
+        stow(&mut commands, &mut backpack.0, &[entity], &|entity| {
+            items.get(entity).ok().map(|(_, stack, container, by)| {
+                (
+                    stacks_by(by).filter(|_| container.is_none()),
+                    quantity(stack),
+                )
+            })
+        });
+
+        if let Ok((details, _, _, _)) = items.get(entity) {
+            output.send(NetworkOutput {
+                id: client.id,
+                body: if slot.wielded() {
+                    format!("You put away the {}.", details.name)
+                } else {
+                    format!("You take off the {}.", details.name)
+                },
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::{
+        items::components::{
+            backpack::Backpack,
+            equipment::Equipment,
+            equippable::{Equippable, Slot},
+            item_details::{Durability, ItemDetails},
+        },
+        network::events::NetworkOutput,
+        player::{
+            components::client::NetworkClient, events::action_event::ActionEvent,
+            resources::player_index::PlayerIndex, systems::index_players::index_players,
+        },
+        test::bundles::utils::{item_in_backpack_bundle, player_bundle, ItemBundle, PlayerBundle},
+    };
+
+    #[test]
+    fn remove() {
+        let mut app = App::new();
+
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::remove);
+
+        let caps = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Cap".into(),
+                ..Default::default()
+            }))
+            .insert(Equippable { slot: Slot::Head })
+            .id();
+
+        let worn = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Cap".into(),
+                ..Default::default()
+            }))
+            .insert(Equippable { slot: Slot::Head })
+            .insert(ItemDetails {
+                durability: Some(Durability {
+                    current: 3,
+                    max: 10,
+                }),
+                ..Default::default()
+            })
+            .id();
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                items: vec![caps],
+                ..Default::default()
+            }))
+            .id();
+
+        app.world
+            .get_mut::<Equipment>(player)
+            .unwrap()
+            .0
+            .insert(Slot::Head, worn);
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        for body in ["remove cap", "remove cap"] {
+            app.world
+                .resource_mut::<Events<ActionEvent>>()
+                .send(ActionEvent {
+                    id: player_client_id,
+                    body: body.into(),
+                });
+
+            app.update();
+        }
+
+        assert!(app.world.get::<Equipment>(player).unwrap().0.is_empty());
+        // Worn down, it's not like the others, so it doesn't join their stack.
+        assert_eq!(
+            app.world.get::<Backpack>(player).unwrap().0,
+            vec![caps, worn]
+        );
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let outputs = output_reader
+            .iter(output_events)
+            .map(|output| output.body.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            outputs,
+            vec!["You take off the Cap.", "You don't have that on."]
+        );
+    }
+}
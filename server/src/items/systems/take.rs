@@ -1,64 +1,164 @@
-use bevy::prelude::*;
-use lazy_static::lazy_static;
-use regex::Regex;
+use bevy::{prelude::*, utils::HashMap};
 
 use crate::{
-    items::components::{backpack::Backpack, can_take::CanTake},
-    network::events::{NetworkInput, NetworkOutput},
-    player::components::{client::NetworkClient, online::Online},
-    spatial::components::position::Position,
+    items::{
+        components::{
+            backpack::Backpack, can_take::CanTake, capacity::Capacity, container::Container,
+            equipment::Equipment, stack::Stack,
+        },
+        utils::{
+            load::Loads,
+            stacks::{describe, move_stack, quantity, stacks_by, Stacking, Target},
+        },
+    },
+    network::events::NetworkOutput,
+    player::{
+        components::{client::NetworkClient, online::Online},
+        events::action_event::ActionEvent,
+        utils::{actions::Action, players::Players},
+    },
+    spatial::components::{position::Position, zone::Zone},
     visual::components::details::Details,
+    world::{components::from_level::FromLevel, utils::levels::LeaveLevel},
 };
 
+/// Take items from the ground, like `take apple`, `take 5 apple`, `take 2.apple`
+/// or `take all`. Anything taken joins a matching stack in the [`Backpack`] if
+/// there is one, and only as much as fits within the player's [`Capacity`] is taken.
 pub fn take(
     mut commands: Commands,
-    mut input: EventReader<NetworkInput>,
+    mut actions: EventReader<ActionEvent>,
     mut output: EventWriter<NetworkOutput>,
-    mut players: Query<(&NetworkClient, &Position, &mut Backpack), With<Online>>,
-    entities: Query<(Entity, &Position, &Details), With<CanTake>>,
+    mut players: Players<
+        (
+            &NetworkClient,
+            &Position,
+            &Zone,
+            &mut Backpack,
+            Option<&Capacity>,
+            Option<&Equipment>,
+        ),
+        With<Online>,
+    >,
+    entities: Query<(Entity, &Position, &Zone, &Details, Option<&Stack>), With<CanTake>>,
+    stacking: Query<(Option<&Stack>, Option<&Container>, Stacking), With<CanTake>>,
+    loads: Loads,
 ) {
-    lazy_static! {
-        static ref CMD: Regex = Regex::new("^(take)(( +)(.+))?$").unwrap();
-    }
-
-    for message in input.iter() {
-        if let Some((client, position, mut backpack)) =
-            players.iter_mut().find(|(c, _, _)| c.id == message.id)
+    for message in actions.iter() {
+        if let Some((client, position, zone, mut backpack, capacity, equipment)) =
+            players.sender_mut(&message.id)
         {
-            if let Some(captures) = CMD.captures(&message.body.to_lowercase()) {
-                if backpack.0.len() >= 50 {
-                    output.send(NetworkOutput {
-                        id: client.id,
-                        body: "You can't carry anything else!".into(),
-                    });
-
-                    break;
-                }
-
+            if let Some(captures) = Action::Take.captures(&message.body.to_lowercase()) {
                 match captures.get(4) {
-                    Some(name_or_id) => {
-                        match entities.iter().find(|(e, p, d)| {
-                            p.0 == position.0
-                                && (d.name.to_lowercase()
-                                    == name_or_id.as_str().to_lowercase().trim()
-                                    || e.id().to_string() == name_or_id.as_str())
-                        }) {
-                            Some((entity, _, details)) => {
-                                commands.entity(entity).remove::<Position>();
-                                backpack.0.push(entity);
-
-                                output.send(NetworkOutput {
-                                    id: client.id,
-                                    body: format!("You take the {}.", details.name),
-                                });
+                    // Taking things out of containers is handled by `get`.
+                    Some(target) if target.as_str().contains(" from ") => {}
+                    Some(target) => {
+                        let picked = Target::parse(target.as_str()).pick(
+                            entities
+                                .iter()
+                                .filter(|(_, p, z, _, _)| p.0 == position.0 && z.0 == zone.0)
+                                .map(|(entity, _, _, details, stack)| {
+                                    (entity, details.name.as_str(), quantity(stack))
+                                }),
+                        );
+
+                        if picked.is_empty() {
+                            output.send(NetworkOutput {
+                                id: client.id,
+                                body: "You don't see that here.".into(),
+                            });
+
+                            continue;
+                        }
+
+                        // Stacks already in the backpack, and how many each holds.
+                        let mut stacks = backpack
+                            .0
+                            .iter()
+                            .filter_map(|entity| {
+                                stacking
+                                    .get(*entity)
+                                    .ok()
+                                    .and_then(|(stack, container, by)| {
+                                        stacks_by(by).filter(|_| container.is_none()).map(|key| {
+                                            (key.to_string(), (*entity, quantity(stack)))
+                                        })
+                                    })
+                            })
+                            .collect::<HashMap<_, _>>();
+
+                        let mut load = loads.of_player(&backpack.0, equipment);
+                        let mut taken = Vec::new();
+                        let mut refused = None;
+
+                        for (entity, wanted, total) in picked {
+                            let (name, key) =
+                                if let (Ok((_, _, _, details, _)), Ok((_, container, by))) =
+                                    (entities.get(entity), stacking.get(entity))
+                                {
+                                    (
+                                        details.name.clone(),
+                                        stacks_by(by).filter(|_| container.is_none()),
+                                    )
+                                } else {
+                                    continue;
+                                };
+
+                            let (weight, bulk) = loads.each(entity, total);
+
+                            let amount = capacity.map_or(wanted, |capacity| {
+                                load.fits(capacity, Some(&weight), Some(&bulk), wanted)
+                            });
+
+                            if amount < wanted {
+                                refused = Some(
+                                    if capacity.map_or(false, |capacity| {
+                                        load.fits(capacity, Some(&weight), None, 1) == 0
+                                    }) {
+                                        "That's too heavy for you to carry."
+                                    } else {
+                                        "You don't have room for that."
+                                    },
+                                );
+                            }
+
+                            if amount == 0 {
+                                continue;
+                            }
+
+                            load.add(Some(&weight), Some(&bulk), amount);
+
+                            // The whole pile is going, so it mustn't come back when
+                            // levels are reloaded.
+                            if amount == total {
+                                commands.add(LeaveLevel(entity));
                             }
-                            None => {
-                                output.send(NetworkOutput {
-                                    id: client.id,
-                                    body: "You don't see that here.".into(),
-                                });
+
+                            if let Some(moved) =
+                                move_stack(&mut commands, &mut stacks, key, entity, amount, total)
+                            {
+                                commands
+                                    .entity(moved)
+                                    .remove_bundle::<(Position, Zone, FromLevel)>();
+                                backpack.0.push(moved);
                             }
+
+                            taken.push((name, amount));
                         }
+
+                        output.send(NetworkOutput {
+                            id: client.id,
+                            body: match (taken.is_empty(), refused) {
+                                (true, refused) => {
+                                    refused.unwrap_or("You don't see that here.").to_string()
+                                }
+                                (false, Some(_)) => format!(
+                                    "You take {}, but can't carry any more.",
+                                    describe(&taken)
+                                ),
+                                (false, None) => format!("You take {}.", describe(&taken)),
+                            },
+                        });
                     }
                     None => {
                         output.send(NetworkOutput {
@@ -77,19 +177,31 @@ mod tests {
     use bevy::{ecs::event::Events, prelude::*};
 
     use crate::{
-        items::components::backpack::Backpack,
-        network::events::{NetworkInput, NetworkOutput},
-        player::components::client::NetworkClient,
+        items::components::{backpack::Backpack, stack::Stack, weight::Weight},
+        network::events::NetworkOutput,
+        player::{
+            components::client::NetworkClient, events::action_event::ActionEvent,
+            resources::player_index::PlayerIndex, systems::index_players::index_players,
+        },
         spatial::components::position::Position,
-        test::bundles::utils::{item_bundle, player_bundle, ItemBundle, PlayerBundle},
+        test::{
+            bundles::utils::{
+                item_bundle, item_in_backpack_bundle, player_bundle, ItemBundle, PlayerBundle,
+            },
+            prototypes::utils::insert_prototypes,
+        },
+        visual::components::details::Details,
+        world::components::{overridden::Overridden, prototype::Prototype},
     };
 
     #[test]
     fn take() {
         let mut app = App::new();
 
-        app.add_event::<NetworkInput>();
+        app.add_event::<ActionEvent>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::take);
 
         let player = app
@@ -112,11 +224,10 @@ mod tests {
             .id();
 
         app.world
-            .resource_mut::<Events<NetworkInput>>()
-            .send(NetworkInput {
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
                 id: player_client_id,
                 body: format!("take apple"),
-                internal: false,
             });
 
         app.update();
@@ -135,12 +246,262 @@ mod tests {
         assert_eq!(output.body, "You take the Apple.");
     }
 
+    #[test]
+    fn split() {
+        let mut app = App::new();
+
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::take);
+        insert_prototypes(&mut app);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        let apples = app
+            .world
+            .spawn()
+            .insert_bundle(item_bundle(ItemBundle {
+                name: "Apple".into(),
+                quantity: 5,
+                ..Default::default()
+            }))
+            .id();
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: format!("take 3 apples"),
+            });
+
+        app.update();
+
+        let taken = *app.world.get::<Backpack>(player).unwrap().0.get(0).unwrap();
+
+        assert_eq!(app.world.get::<Stack>(apples).unwrap().0, 2);
+        assert_eq!(app.world.get::<Stack>(taken).unwrap().0, 3);
+        assert!(app.world.get::<Position>(taken).is_none());
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(output.body, "You take 3 Apples.");
+    }
+
+    #[test]
+    fn merge() {
+        let mut app = App::new();
+
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::take);
+
+        let carried = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Apple".into(),
+                quantity: 2,
+                ..Default::default()
+            }))
+            .id();
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                items: vec![carried],
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        let apple = app
+            .world
+            .spawn()
+            .insert_bundle(item_bundle(ItemBundle {
+                name: "Apple".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        app.world.spawn().insert_bundle(item_bundle(ItemBundle {
+            name: "Banana".into(),
+            ..Default::default()
+        }));
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: format!("take all"),
+            });
+
+        app.update();
+
+        assert!(app.world.get_entity(apple).is_none());
+        assert_eq!(app.world.get::<Stack>(carried).unwrap().0, 3);
+        assert_eq!(app.world.get::<Backpack>(player).unwrap().0.len(), 2);
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(output.body, "You take the Apple and the Banana.");
+    }
+
+    #[test]
+    fn overridden() {
+        let mut app = App::new();
+
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::take);
+
+        let carried = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Apple".into(),
+                quantity: 2,
+                ..Default::default()
+            }))
+            .id();
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                items: vec![carried],
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        // An Apple placed with a name of its own.
+        let golden = app
+            .world
+            .spawn()
+            .insert_bundle(item_bundle(ItemBundle {
+                name: "Golden Apple".into(),
+                ..Default::default()
+            }))
+            .insert_bundle((Prototype("Apple".into()), Overridden))
+            .id();
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: "take golden apple".into(),
+            });
+
+        app.update();
+
+        assert_eq!(app.world.get::<Stack>(carried).unwrap().0, 2);
+        assert_eq!(
+            app.world.get::<Backpack>(player).unwrap().0,
+            vec![carried, golden]
+        );
+        assert_eq!(
+            app.world.get::<Details>(golden).unwrap().name,
+            "Golden Apple"
+        );
+    }
+
+    #[test]
+    fn too_heavy() {
+        let mut app = App::new();
+
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::take);
+        insert_prototypes(&mut app);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        let logs = app
+            .world
+            .spawn()
+            .insert_bundle(item_bundle(ItemBundle {
+                name: "Log".into(),
+                quantity: 3,
+                ..Default::default()
+            }))
+            .insert(Weight(10.0))
+            .id();
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: format!("take all logs"),
+            });
+
+        app.update();
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: format!("take log"),
+            });
+
+        app.update();
+
+        assert_eq!(app.world.get::<Stack>(logs).unwrap().0, 1);
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let outputs = output_reader
+            .iter(output_events)
+            .map(|output| output.body.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            outputs,
+            vec![
+                "You take 2 Logs, but can't carry any more.",
+                "That's too heavy for you to carry."
+            ]
+        );
+    }
+
     #[test]
     fn entity_not_found() {
         let mut app = App::new();
 
-        app.add_event::<NetworkInput>();
+        app.add_event::<ActionEvent>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::take);
 
         let player = app
@@ -154,11 +515,10 @@ mod tests {
         let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
 
         app.world
-            .resource_mut::<Events<NetworkInput>>()
-            .send(NetworkInput {
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
                 id: player_client_id,
                 body: format!("take apple"),
-                internal: false,
             });
 
         app.update();
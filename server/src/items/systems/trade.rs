@@ -0,0 +1,795 @@
+use bevy::{prelude::*, utils::HashMap};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::{
+    items::{
+        components::{
+            backpack::Backpack, bulk::Bulk, can_take::CanTake, capacity::Capacity,
+            container::Container, equipment::Equipment, stack::Stack, trade::Trade,
+            trade_request::TradeRequest, weight::Weight,
+        },
+        utils::{
+            load::Loads,
+            stacks::{describe, move_stack, quantity, stacks_by, stow, Stacking, Target},
+        },
+    },
+    network::events::{NetworkInput, NetworkOutput},
+    player::{
+        components::{character::Character, client::NetworkClient, online::Online},
+        utils::players::Players,
+    },
+    spatial::components::{position::Position, zone::Zone},
+    visual::components::details::Details,
+};
+
+/// Trade items safely with another player on the same tile.
+///
+/// `trade <player>` invites them with a [`TradeRequest`], and once they send
+/// `trade <player>` back a [`Trade`] starts. Then each side offers items with
+/// `trade add <item>` and takes them back with `trade remove <item>`. Coins are
+/// items like any other, so `trade add 20 coins` works too. `trade` shows both
+/// offers, and once both sides `trade accept` the offers as they stand they're
+/// swapped in one go. Any change to either offer takes back both acceptances,
+/// and `trade cancel` returns everything.
+///
+/// Unlike `give`, this reads [`NetworkInput`] rather than queued actions. Every
+/// command here only changes what's on offer, and the swap itself is instant
+/// once both sides accept, so a delay would just leave each side waiting on
+/// the other's replies.
+pub fn trade(
+    mut commands: Commands,
+    mut input: EventReader<NetworkInput>,
+    mut output: EventWriter<NetworkOutput>,
+    mut players: Players<
+        (
+            Entity,
+            &NetworkClient,
+            &Character,
+            &Position,
+            &Zone,
+            &mut Backpack,
+            Option<&Capacity>,
+            Option<&Equipment>,
+            Option<&mut Trade>,
+            Option<&TradeRequest>,
+        ),
+        With<Online>,
+    >,
+    items: Query<
+        (&Details, Option<&Stack>, Option<&Container>, Stacking),
+        (With<CanTake>, Without<Position>),
+    >,
+    loads: Loads,
+) {
+    lazy_static! {
+        static ref CMD: Regex =
+            Regex::new("^trade( +(accept|cancel|add|remove)( +(.+))?| +(.+))?$").unwrap();
+    }
+
+    // How each item stacks, if it does, and how many it holds.
+    let lookup = |entity| {
+        items.get(entity).ok().map(|(_, stack, container, by)| {
+            (
+                stacks_by(by).filter(|_| container.is_none()),
+                quantity(stack),
+            )
+        })
+    };
+
+    let describe_offer = |offer: &[Entity]| {
+        let offered = offer
+            .iter()
+            .filter_map(|entity| {
+                items
+                    .get(*entity)
+                    .ok()
+                    .map(|(details, stack, _, _)| (details.name.clone(), quantity(stack)))
+            })
+            .collect::<Vec<_>>();
+
+        if offered.is_empty() {
+            "nothing".to_string()
+        } else {
+            describe(&offered)
+        }
+    };
+
+    for message in input.iter() {
+        let body = message.body.to_lowercase();
+
+        let captures = if let Some(captures) = CMD.captures(&body) {
+            captures
+        } else {
+            continue;
+        };
+
+        let (me, client, name, position, zone, trade, request) =
+            if let Some(player) = players.sender(&message.id) {
+                let (entity, client, character, position, zone, _, _, _, trade, request) = player;
+
+                (
+                    entity,
+                    client.id,
+                    character.name.clone(),
+                    position.0,
+                    zone.0.clone(),
+                    trade.map(|trade| (trade.partner, trade.offer.clone(), trade.accepted)),
+                    request.map(|request| request.0),
+                )
+            } else {
+                continue;
+            };
+
+        let partner = trade.as_ref().and_then(|(partner, _, _)| {
+            players.get(*partner).ok().and_then(
+                |(entity, client, character, _, _, _, _, _, trade, _)| {
+                    trade.map(|trade| {
+                        (
+                            entity,
+                            client.id,
+                            character.name.clone(),
+                            trade.offer.clone(),
+                            trade.accepted,
+                        )
+                    })
+                },
+            )
+        });
+
+        let subcommand = captures.get(2).map(|m| m.as_str());
+
+        if trade.is_none() {
+            let other = match captures.get(5) {
+                Some(other) if subcommand.is_none() => other.as_str().trim(),
+                _ if subcommand == Some("cancel") && request.is_some() => {
+                    commands.entity(me).remove::<TradeRequest>();
+
+                    output.send(NetworkOutput {
+                        id: client,
+                        body: "You withdraw your invitation to trade.".into(),
+                    });
+
+                    continue;
+                }
+                _ => {
+                    output.send(NetworkOutput {
+                        id: client,
+                        body: "You're not trading with anyone. Use `trade <player>` to start."
+                            .into(),
+                    });
+
+                    continue;
+                }
+            };
+
+            if name.to_lowercase() == other {
+                output.send(NetworkOutput {
+                    id: client,
+                    body: "You can't trade with yourself.".into(),
+                });
+
+                continue;
+            }
+
+            let (other, other_client, other_name, busy, invited_me) = if let Some(found) = players
+                .iter()
+                .find(|(e, _, c, p, z, _, _, _, _, _)| {
+                    *e != me && p.0 == position && z.0 == zone && c.name.to_lowercase() == other
+                })
+                .map(
+                    |(entity, client, character, _, _, _, _, _, trade, request)| {
+                        (
+                            entity,
+                            client.id,
+                            character.name.clone(),
+                            trade.is_some(),
+                            request.map_or(false, |request| request.0 == me),
+                        )
+                    },
+                ) {
+                found
+            } else {
+                output.send(NetworkOutput {
+                    id: client,
+                    body: "You don't see them here.".into(),
+                });
+
+                continue;
+            };
+
+            if busy {
+                output.send(NetworkOutput {
+                    id: client,
+                    body: format!("{other_name} is already trading with someone."),
+                });
+
+                continue;
+            }
+
+            // Nothing starts until the other side sends `trade <player>` back.
+            if !invited_me {
+                commands.entity(me).insert(TradeRequest(other));
+
+                output.send(NetworkOutput {
+                    id: client,
+                    body: format!("You invite {other_name} to trade."),
+                });
+
+                output.send(NetworkOutput {
+                    id: other_client,
+                    body: format!("{name} wants to trade with you. Use `trade {name}` to start."),
+                });
+
+                continue;
+            }
+
+            commands.entity(me).remove::<TradeRequest>();
+            commands.entity(other).remove::<TradeRequest>();
+            commands.entity(me).insert(Trade::with(other));
+            commands.entity(other).insert(Trade::with(me));
+
+            output.send(NetworkOutput {
+                id: client,
+                body: format!(
+                    "You start trading with {other_name}. Offer things with `trade add`."
+                ),
+            });
+
+            output.send(NetworkOutput {
+                id: other_client,
+                body: format!("{name} starts trading with you. Offer things with `trade add`."),
+            });
+
+            continue;
+        }
+
+        let (my_offer, accepted) = if let Some((_, offer, accepted)) = trade {
+            (offer, accepted)
+        } else {
+            continue;
+        };
+
+        // The other side has gone, so `cancel_trades` will sort it out.
+        let (partner, partner_client, partner_name, their_offer, their_accepted) =
+            if let Some(partner) = partner {
+                partner
+            } else {
+                continue;
+            };
+
+        match (subcommand, captures.get(4)) {
+            (None, _) if captures.get(5).is_some() => {
+                output.send(NetworkOutput {
+                    id: client,
+                    body: format!("You're already trading with {partner_name}."),
+                });
+            }
+            (None, _) => {
+                output.send(NetworkOutput {
+                    id: client,
+                    body: format!(
+                        "You're trading with {partner_name}.\r\nYou offer {}{}.\r\n{partner_name} offers {}{}.",
+                        describe_offer(&my_offer),
+                        if accepted { ", and have accepted" } else { "" },
+                        describe_offer(&their_offer),
+                        if their_accepted { ", and has accepted" } else { "" },
+                    ),
+                });
+            }
+            (Some("accept"), _) => {
+                if my_offer.is_empty() && their_offer.is_empty() {
+                    output.send(NetworkOutput {
+                        id: client,
+                        body: "There's nothing to trade yet.".into(),
+                    });
+
+                    continue;
+                }
+
+                if !their_accepted {
+                    if let Ok((_, _, _, _, _, _, _, _, Some(mut trade), _)) = players.get_mut(me) {
+                        trade.accepted = true;
+                    }
+
+                    output.send(NetworkOutput {
+                        id: client,
+                        body: format!("You accept the trade. Waiting on {partner_name}."),
+                    });
+
+                    output.send(NetworkOutput {
+                        id: partner_client,
+                        body: format!(
+                            "{name} accepts the trade. Use `trade accept` to complete it."
+                        ),
+                    });
+
+                    continue;
+                }
+
+                // Offered items aren't in either backpack, so see whether each
+                // side can carry what they'd end up with.
+                let fits = |player: Entity, offer: &[Entity]| {
+                    players.get(player).ok().map(
+                        |(_, _, _, _, _, backpack, capacity, equipment, _, _)| {
+                            let incoming = loads.of(offer);
+
+                            (
+                                backpack.0.clone(),
+                                capacity.map_or(true, |capacity| {
+                                    loads.of_player(&backpack.0, equipment).fits(
+                                        capacity,
+                                        Some(&Weight(incoming.weight)),
+                                        Some(&Bulk(incoming.bulk)),
+                                        1,
+                                    ) == 1
+                                }),
+                            )
+                        },
+                    )
+                };
+
+                let ((my_backpack, i_fit), (their_backpack, they_fit)) =
+                    match (fits(me, &their_offer), fits(partner, &my_offer)) {
+                        (Some(mine), Some(theirs)) => (mine, theirs),
+                        _ => continue,
+                    };
+
+                let refused = if !i_fit {
+                    Some(("You", name.as_str()))
+                } else if !they_fit {
+                    Some((partner_name.as_str(), "You"))
+                } else {
+                    None
+                };
+
+                if let Some((mine, theirs)) = refused {
+                    output.send(NetworkOutput {
+                        id: client,
+                        body: format!(
+                            "{mine} can't carry all of that, so the trade can't go ahead."
+                        ),
+                    });
+
+                    output.send(NetworkOutput {
+                        id: partner_client,
+                        body: format!(
+                            "{theirs} can't carry all of that, so the trade can't go ahead."
+                        ),
+                    });
+
+                    continue;
+                }
+
+                let mut mine = my_backpack;
+                let mut theirs = their_backpack;
+
+                stow(&mut commands, &mut mine, &their_offer, &lookup);
+                stow(&mut commands, &mut theirs, &my_offer, &lookup);
+
+                if let Ok((_, _, _, _, _, mut backpack, _, _, _, _)) = players.get_mut(me) {
+                    backpack.0 = mine;
+                }
+
+                if let Ok((_, _, _, _, _, mut backpack, _, _, _, _)) = players.get_mut(partner) {
+                    backpack.0 = theirs;
+                }
+
+                commands.entity(me).remove::<Trade>();
+                commands.entity(partner).remove::<Trade>();
+
+                output.send(NetworkOutput {
+                    id: client,
+                    body: format!(
+                        "You trade {} for {} with {partner_name}.",
+                        describe_offer(&my_offer),
+                        describe_offer(&their_offer)
+                    ),
+                });
+
+                output.send(NetworkOutput {
+                    id: partner_client,
+                    body: format!(
+                        "You trade {} for {} with {name}.",
+                        describe_offer(&their_offer),
+                        describe_offer(&my_offer)
+                    ),
+                });
+            }
+            (Some("cancel"), _) => {
+                for (entity, offer) in [(me, &my_offer), (partner, &their_offer)] {
+                    if let Ok((_, _, _, _, _, mut backpack, _, _, _, _)) = players.get_mut(entity) {
+                        stow(&mut commands, &mut backpack.0, offer, &lookup);
+                    }
+
+                    commands.entity(entity).remove::<Trade>();
+                }
+
+                output.send(NetworkOutput {
+                    id: client,
+                    body: "You cancel the trade, and your offer is returned to you.".into(),
+                });
+
+                output.send(NetworkOutput {
+                    id: partner_client,
+                    body: format!("{name} cancels the trade, and your offer is returned to you."),
+                });
+            }
+            (Some(subcommand), None) => {
+                output.send(NetworkOutput {
+                    id: client,
+                    body: if subcommand == "add" {
+                        "Offer what?"
+                    } else {
+                        "Take back what?"
+                    }
+                    .into(),
+                });
+            }
+            (Some(subcommand), Some(target)) => {
+                let adding = subcommand == "add";
+
+                let backpack = if let Ok((_, _, _, _, _, backpack, _, _, _, _)) = players.get(me) {
+                    backpack.0.clone()
+                } else {
+                    continue;
+                };
+
+                // Items move from the backpack to the offer, or back again.
+                let (mut from, mut into) = if adding {
+                    (backpack, my_offer)
+                } else {
+                    (my_offer, backpack)
+                };
+
+                let picked =
+                    Target::parse(target.as_str()).pick(from.iter().filter_map(|entity| {
+                        items.get(*entity).ok().map(|(details, stack, _, _)| {
+                            (*entity, details.name.as_str(), quantity(stack))
+                        })
+                    }));
+
+                if picked.is_empty() {
+                    output.send(NetworkOutput {
+                        id: client,
+                        body: if adding {
+                            "You don't have that."
+                        } else {
+                            "You haven't offered that."
+                        }
+                        .into(),
+                    });
+
+                    continue;
+                }
+
+                // Stacks already where the items are going, and how many each holds.
+                let mut stacks = into
+                    .iter()
+                    .filter_map(|entity| {
+                        lookup(*entity).and_then(|(key, quantity)| {
+                            key.map(|key| (key.to_string(), (*entity, quantity)))
+                        })
+                    })
+                    .collect::<HashMap<_, _>>();
+
+                let mut moved = Vec::new();
+
+                for (entity, amount, total) in picked {
+                    let (item, key) = if let (Ok((details, _, _, _)), Some((key, _))) =
+                        (items.get(entity), lookup(entity))
+                    {
+                        (details.name.clone(), key)
+                    } else {
+                        continue;
+                    };
+
+                    if amount == total {
+                        from.retain(|e| *e != entity);
+                    }
+
+                    if let Some(entity) =
+                        move_stack(&mut commands, &mut stacks, key, entity, amount, total)
+                    {
+                        into.push(entity);
+                    }
+
+                    moved.push((item, amount));
+                }
+
+                let (backpack, offer) = if adding { (from, into) } else { (into, from) };
+
+                if let Ok((_, _, _, _, _, mut mine, _, _, Some(mut trade), _)) = players.get_mut(me)
+                {
+                    mine.0 = backpack;
+                    trade.offer = offer;
+                    trade.accepted = false;
+                }
+
+                if let Ok((_, _, _, _, _, _, _, _, Some(mut trade), _)) = players.get_mut(partner) {
+                    trade.accepted = false;
+                }
+
+                let moved = describe(&moved);
+
+                output.send(NetworkOutput {
+                    id: client,
+                    body: if adding {
+                        format!("You offer {moved}.")
+                    } else {
+                        format!("You take back {moved}.")
+                    },
+                });
+
+                output.send(NetworkOutput {
+                    id: partner_client,
+                    body: if adding {
+                        format!("{name} offers {moved}.")
+                    } else {
+                        format!("{name} takes back {moved}.")
+                    },
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::{
+        items::components::{
+            backpack::Backpack, stack::Stack, trade::Trade, trade_request::TradeRequest,
+        },
+        network::events::{NetworkInput, NetworkOutput},
+        player::{
+            components::client::NetworkClient, resources::player_index::PlayerIndex,
+            systems::index_players::index_players,
+        },
+        test::{
+            bundles::utils::{item_in_backpack_bundle, player_bundle, ItemBundle, PlayerBundle},
+            prototypes::utils::insert_prototypes,
+        },
+    };
+
+    #[test]
+    fn trade() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkInput>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::trade);
+        insert_prototypes(&mut app);
+
+        let apple = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Apple".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        let coins = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Coin".into(),
+                quantity: 50,
+                ..Default::default()
+            }))
+            .id();
+
+        let alice = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                name: "Alice".into(),
+                items: vec![apple],
+                ..Default::default()
+            }))
+            .id();
+
+        let bob = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                name: "Bob".into(),
+                items: vec![coins],
+                ..Default::default()
+            }))
+            .id();
+
+        let alice_client_id = app.world.get::<NetworkClient>(alice).unwrap().id;
+        let bob_client_id = app.world.get::<NetworkClient>(bob).unwrap().id;
+
+        for (id, body) in [
+            (alice_client_id, "trade bob"),
+            (bob_client_id, "trade alice"),
+            (alice_client_id, "trade add apple"),
+            (bob_client_id, "trade add 20 coins"),
+            (alice_client_id, "trade accept"),
+            (bob_client_id, "trade accept"),
+        ] {
+            app.world
+                .resource_mut::<Events<NetworkInput>>()
+                .send(NetworkInput {
+                    id,
+                    body: body.into(),
+                    internal: false,
+                });
+
+            app.update();
+        }
+
+        assert!(app.world.get::<Trade>(alice).is_none());
+        assert!(app.world.get::<Trade>(bob).is_none());
+
+        let paid = app.world.get::<Backpack>(alice).unwrap().0.clone();
+
+        assert_eq!(paid.len(), 1);
+        assert_eq!(app.world.get::<Stack>(paid[0]).unwrap().0, 20);
+        assert_eq!(app.world.get::<Stack>(coins).unwrap().0, 30);
+        assert_eq!(
+            app.world.get::<Backpack>(bob).unwrap().0,
+            vec![coins, apple]
+        );
+    }
+
+    #[test]
+    fn invitation() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkInput>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::trade);
+
+        let alice = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                name: "Alice".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        let bob = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                name: "Bob".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        let alice_client_id = app.world.get::<NetworkClient>(alice).unwrap().id;
+        let bob_client_id = app.world.get::<NetworkClient>(bob).unwrap().id;
+
+        app.world
+            .resource_mut::<Events<NetworkInput>>()
+            .send(NetworkInput {
+                id: alice_client_id,
+                body: "trade bob".into(),
+                internal: false,
+            });
+
+        app.update();
+
+        assert!(app.world.get::<Trade>(alice).is_none());
+        assert!(app.world.get::<Trade>(bob).is_none());
+        assert_eq!(app.world.get::<TradeRequest>(alice).unwrap().0, bob);
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let outputs = output_reader
+            .iter(output_events)
+            .filter(|output| output.id == bob_client_id)
+            .map(|output| output.body.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            outputs,
+            vec!["Alice wants to trade with you. Use `trade Alice` to start."]
+        );
+
+        app.world
+            .resource_mut::<Events<NetworkInput>>()
+            .send(NetworkInput {
+                id: bob_client_id,
+                body: "trade alice".into(),
+                internal: false,
+            });
+
+        app.update();
+
+        assert!(app.world.get::<TradeRequest>(alice).is_none());
+        assert_eq!(app.world.get::<Trade>(alice).unwrap().partner, bob);
+        assert_eq!(app.world.get::<Trade>(bob).unwrap().partner, alice);
+    }
+
+    #[test]
+    fn changes_undo_accept() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkInput>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::trade);
+
+        let apple = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Apple".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        let alice = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                name: "Alice".into(),
+                items: vec![apple],
+                ..Default::default()
+            }))
+            .id();
+
+        let bob = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                name: "Bob".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        let alice_client_id = app.world.get::<NetworkClient>(alice).unwrap().id;
+        let bob_client_id = app.world.get::<NetworkClient>(bob).unwrap().id;
+
+        for (id, body) in [
+            (alice_client_id, "trade bob"),
+            (bob_client_id, "trade alice"),
+            (alice_client_id, "trade add apple"),
+            (bob_client_id, "trade accept"),
+            (alice_client_id, "trade remove apple"),
+            (alice_client_id, "trade cancel"),
+        ] {
+            app.world
+                .resource_mut::<Events<NetworkInput>>()
+                .send(NetworkInput {
+                    id,
+                    body: body.into(),
+                    internal: false,
+                });
+
+            app.update();
+        }
+
+        assert!(app.world.get::<Trade>(alice).is_none());
+        assert!(app.world.get::<Trade>(bob).is_none());
+        assert_eq!(app.world.get::<Backpack>(alice).unwrap().0, vec![apple]);
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let outputs = output_reader
+            .iter(output_events)
+            .filter(|output| output.id == bob_client_id)
+            .map(|output| output.body.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            outputs,
+            vec![
+                "Alice takes back the Apple.",
+                "Alice cancels the trade, and your offer is returned to you.",
+            ]
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{
+    items::{
+        components::{
+            backpack::Backpack, can_take::CanTake, container::Container, equipment::Equipment,
+            equippable::Equippable, stack::Stack,
+        },
+        utils::stacks::{move_stack, quantity, stacks_by, Stacking, Target},
+    },
+    network::events::NetworkOutput,
+    player::{
+        components::{client::NetworkClient, online::Online},
+        events::action_event::ActionEvent,
+        utils::{actions::Action, players::Players},
+    },
+    spatial::components::position::Position,
+    visual::components::details::Details,
+};
+
+/// Put on something [`Equippable`] from the [`Backpack`], like `wear cap`, or
+/// take a tool in hand, like `wield hatchet`. Only one item fits in each slot,
+/// so whatever's there has to be removed first.
+pub fn wear(
+    mut commands: Commands,
+    mut actions: EventReader<ActionEvent>,
+    mut output: EventWriter<NetworkOutput>,
+    mut players: Players<(&NetworkClient, &mut Backpack, &mut Equipment), With<Online>>,
+    items: Query<
+        (
+            &Details,
+            Option<&Stack>,
+            Option<&Equippable>,
+            Option<&Container>,
+            Stacking,
+        ),
+        (With<CanTake>, Without<Position>),
+    >,
+) {
+    for message in actions.iter() {
+        let body = message.body.to_lowercase();
+
+        let captures = if let Some(captures) = Action::Wear.captures(&body) {
+            captures
+        } else {
+            continue;
+        };
+
+        let (client, mut backpack, mut equipment) =
+            if let Some(player) = players.sender_mut(&message.id) {
+                player
+            } else {
+                continue;
+            };
+
+        let wielding = &captures[1] == "wield";
+
+        let target = if let Some(target) = captures.get(3) {
+            target
+        } else {
+            output.send(NetworkOutput {
+                id: client.id,
+                body: format!("{} what?", if wielding { "Wield" } else { "Wear" }),
+            });
+
+            continue;
+        };
+
+        let picked = Target::parse(target.as_str()).pick(backpack.0.iter().filter_map(|entity| {
+            items
+                .get(*entity)
+                .ok()
+                .map(|(details, stack, _, _, _)| (*entity, details.name.as_str(), quantity(stack)))
+        }));
+
+        let (entity, details, equippable, key, total) = if let Some(found) =
+            picked.first().and_then(|(entity, _, total)| {
+                items
+                    .get(*entity)
+                    .ok()
+                    .map(|(details, _, equippable, container, by)| {
+                        (
+                            *entity,
+                            details,
+                            equippable,
+                            stacks_by(by).filter(|_| container.is_none()),
+                            *total,
+                        )
+                    })
+            }) {
+            found
+        } else {
+            output.send(NetworkOutput {
+                id: client.id,
+                body: "You don't have that.".into(),
+            });
+
+            continue;
+        };
+
+        let slot = match equippable {
+            Some(equippable) if equippable.slot.wielded() == wielding => equippable.slot,
+            Some(_) if wielding => {
+                output.send(NetworkOutput {
+                    id: client.id,
+                    body: "That's for wearing, not wielding.".into(),
+                });
+
+                continue;
+            }
+            Some(_) => {
+                output.send(NetworkOutput {
+                    id: client.id,
+                    body: "That's for wielding, not wearing.".into(),
+                });
+
+                continue;
+            }
+            None => {
+                output.send(NetworkOutput {
+                    id: client.id,
+                    body: format!("You can't {} that.", &captures[1]),
+                });
+
+                continue;
+            }
+        };
+
+        if let Some(current) = equipment.0.get(&slot).and_then(|e| items.get(*e).ok()) {
+            output.send(NetworkOutput {
+                id: client.id,
+                body: format!(
+                    "You're already {} the {}. Remove it first.",
+                    if wielding { "wielding" } else { "wearing" },
+                    current.0.name
+                ),
+            });
+
+            continue;
+        }
+
+        // Only one of a stack goes on, and the rest stays in the backpack.
+        if total == 1 {
+            backpack.0.retain(|e| *e != entity);
+        }
+
+        if let Some(worn) = move_stack(
+            &mut commands,
+            &mut HashMap::default(),
+            key,
+            entity,
+            1,
+            total,
+        ) {
+            equipment.0.insert(slot, worn);
+        }
+
+        output.send(NetworkOutput {
+            id: client.id,
+            body: format!("You {} the {}.", &captures[1], details.name),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::{
+        items::components::{
+            backpack::Backpack,
+            equipment::Equipment,
+            equippable::{Equippable, Slot},
+            stack::Stack,
+        },
+        network::events::NetworkOutput,
+        player::{
+            components::client::NetworkClient, events::action_event::ActionEvent,
+            resources::player_index::PlayerIndex, systems::index_players::index_players,
+        },
+        test::{
+            bundles::utils::{item_in_backpack_bundle, player_bundle, ItemBundle, PlayerBundle},
+            prototypes::utils::insert_prototypes,
+        },
+    };
+
+    fn setup() -> App {
+        let mut app = App::new();
+
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::wear);
+        insert_prototypes(&mut app);
+
+        app
+    }
+
+    #[test]
+    fn wear() {
+        let mut app = setup();
+
+        let caps = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Leather Cap".into(),
+                quantity: 2,
+                ..Default::default()
+            }))
+            .insert(Equippable { slot: Slot::Head })
+            .id();
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                items: vec![caps],
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        for body in ["wear leather cap", "wear leather cap"] {
+            app.world
+                .resource_mut::<Events<ActionEvent>>()
+                .send(ActionEvent {
+                    id: player_client_id,
+                    body: body.into(),
+                });
+
+            app.update();
+        }
+
+        let worn = *app
+            .world
+            .get::<Equipment>(player)
+            .unwrap()
+            .0
+            .get(&Slot::Head)
+            .unwrap();
+
+        assert_ne!(worn, caps);
+        assert_eq!(app.world.get::<Stack>(worn).unwrap().0, 1);
+        assert_eq!(app.world.get::<Stack>(caps).unwrap().0, 1);
+        assert_eq!(app.world.get::<Backpack>(player).unwrap().0, vec![caps]);
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let outputs = output_reader
+            .iter(output_events)
+            .map(|output| output.body.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            outputs,
+            vec![
+                "You wear the Leather Cap.",
+                "You're already wearing the Leather Cap. Remove it first."
+            ]
+        );
+    }
+
+    #[test]
+    fn wrong_verb() {
+        let mut app = setup();
+
+        let hatchet = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Hatchet".into(),
+                ..Default::default()
+            }))
+            .insert(Equippable { slot: Slot::Tool })
+            .id();
+
+        let apple = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Apple".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                items: vec![hatchet, apple],
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        for body in ["wear hatchet", "wield apple"] {
+            app.world
+                .resource_mut::<Events<ActionEvent>>()
+                .send(ActionEvent {
+                    id: player_client_id,
+                    body: body.into(),
+                });
+        }
+
+        app.update();
+
+        assert!(app.world.get::<Equipment>(player).unwrap().0.is_empty());
+        assert_eq!(
+            app.world.get::<Backpack>(player).unwrap().0,
+            vec![hatchet, apple]
+        );
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let outputs = output_reader
+            .iter(output_events)
+            .map(|output| output.body.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            outputs,
+            vec!["That's for wielding, not wearing.", "You can't wield that."]
+        );
+    }
+}
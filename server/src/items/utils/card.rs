@@ -0,0 +1,146 @@
+use inflector::Inflector;
+
+use crate::{
+    items::components::{bulk::Bulk, item_details::ItemDetails, weight::Weight},
+    visual::components::sprite::{Sprite, SpritePaint},
+};
+
+/// Everything worth knowing about `count` items called `name` when they're
+/// looked at, laid out the same way wherever they are: what they are, then
+/// what they weigh and take up in all, then any [`ItemDetails`].
+pub fn card(
+    sprite: &Sprite,
+    name: &str,
+    description: &str,
+    count: u32,
+    weight: Option<&Weight>,
+    bulk: Option<&Bulk>,
+    extra: Option<&ItemDetails>,
+) -> String {
+    let mut lines = vec![
+        if count > 1 {
+            format!("{} {} {}", sprite.paint(), count, name.to_plural())
+        } else {
+            format!("{} {}", sprite.paint(), name)
+        },
+        description.to_string(),
+    ];
+
+    if let Some(flavour) = extra.and_then(|extra| extra.flavour.as_ref()) {
+        lines.push(format!("\"{flavour}\""));
+    }
+
+    // Totals for the whole stack, with what each one comes to if there's more.
+    let total = |each: f32, unit: &str| {
+        if count > 1 {
+            format!("{:.1}{unit} ({:.1}{unit} each)", each * count as f32, each)
+        } else {
+            format!("{:.1}{unit}", each)
+        }
+    };
+
+    if let Some(weight) = weight {
+        lines.push(format!("Weight: {}", total(weight.0, " kg")));
+    }
+
+    if let Some(bulk) = bulk {
+        lines.push(format!("Bulk: {}", total(bulk.0, "")));
+    }
+
+    if let Some(extra) = extra {
+        if let Some(quality) = extra.quality {
+            lines.push(format!("Quality: {}", quality.name()));
+        }
+
+        if let Some(durability) = extra.durability {
+            lines.push(format!(
+                "Durability: {}/{}",
+                durability.current, durability.max
+            ));
+        }
+
+        if let Some(crafted_by) = &extra.crafted_by {
+            lines.push(format!("Crafted by: {crafted_by}"));
+        }
+    }
+
+    lines.join("\r\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use yansi::Paint;
+
+    use crate::{
+        items::components::{
+            bulk::Bulk,
+            item_details::{Durability, ItemDetails, Quality},
+            weight::Weight,
+        },
+        visual::components::sprite::Sprite,
+    };
+
+    use super::card;
+
+    #[test]
+    fn card_format() {
+        Paint::disable();
+
+        let sprite = Sprite {
+            character: "/".into(),
+            color: "94A3B8".into(),
+            background: None,
+        };
+
+        // Items with details of their own never stack, so there's only ever one.
+        assert_eq!(
+            card(
+                &sprite,
+                "Hatchet",
+                "A short-handled axe.",
+                1,
+                Some(&Weight(1.5)),
+                Some(&Bulk(3.0)),
+                Some(&ItemDetails {
+                    quality: Some(Quality::Fine),
+                    crafted_by: Some("Alice".into()),
+                    durability: Some(Durability {
+                        current: 40,
+                        max: 50,
+                    }),
+                    flavour: Some("Still smells of pine.".into()),
+                }),
+            ),
+            "/ Hatchet\r\nA short-handled axe.\r\n\"Still smells of pine.\"\r\n\
+             Weight: 1.5 kg\r\nBulk: 3.0\r\n\
+             Quality: fine\r\nDurability: 40/50\r\nCrafted by: Alice"
+        );
+
+        assert_eq!(
+            card(
+                &sprite,
+                "Log",
+                "A length of split pine.",
+                2,
+                Some(&Weight(8.0)),
+                Some(&Bulk(10.0)),
+                None
+            ),
+            "/ 2 Logs\r\nA length of split pine.\r\n\
+             Weight: 16.0 kg (8.0 kg each)\r\nBulk: 20.0 (10.0 each)"
+        );
+
+        assert_eq!(
+            card(
+                &sprite,
+                "Hatchet",
+                "A short-handled axe.",
+                1,
+                None,
+                None,
+                None
+            ),
+            "/ Hatchet\r\nA short-handled axe."
+        );
+    }
+}
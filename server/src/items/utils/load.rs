@@ -0,0 +1,212 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::items::{
+    components::{
+        bulk::Bulk, can_take::CanTake, capacity::Capacity, container::Container,
+        equipment::Equipment, stack::Stack, weight::Weight,
+    },
+    utils::stacks::quantity,
+};
+
+/// Total weight and bulk of what's being carried.
+#[derive(Default, Debug)]
+pub struct Load {
+    pub weight: f32,
+    pub bulk: f32,
+}
+
+impl Load {
+    /// Everything in `items`, where `lookup` gives each one's weight, bulk, how
+    /// many there are and what's inside it, usually by way of [`Loads`].
+    /// Whatever's inside a container adds to its weight, but not its bulk.
+    pub fn of<'a, F>(items: &[Entity], lookup: &F) -> Load
+    where
+        F: Fn(Entity) -> Option<(Option<&'a Weight>, Option<&'a Bulk>, u32, &'a [Entity])>,
+    {
+        let mut load = Load::default();
+
+        for item in items {
+            if let Some((weight, bulk, quantity, contents)) = lookup(*item) {
+                load.add(weight, bulk, quantity);
+                load.weight += Load::of(contents, lookup).weight;
+            }
+        }
+
+        load
+    }
+
+    /// Everything a player has on them: what's in their `backpack`, plus the
+    /// weight, but not the bulk, of whatever they've got equipped.
+    pub fn of_player<'a, F>(backpack: &[Entity], equipment: Option<&Equipment>, lookup: &F) -> Load
+    where
+        F: Fn(Entity) -> Option<(Option<&'a Weight>, Option<&'a Bulk>, u32, &'a [Entity])>,
+    {
+        let mut load = Load::of(backpack, lookup);
+
+        if let Some(equipment) = equipment {
+            let equipped = equipment.0.values().copied().collect::<Vec<_>>();
+
+            load.weight += Load::of(&equipped, lookup).weight;
+        }
+
+        load
+    }
+
+    /// What each one of the `total` items stacked on `entity` weighs and takes
+    /// up, with anything inside it.
+    pub fn each<'a, F>(entity: Entity, total: u32, lookup: &F) -> (Weight, Bulk)
+    where
+        F: Fn(Entity) -> Option<(Option<&'a Weight>, Option<&'a Bulk>, u32, &'a [Entity])>,
+    {
+        let load = Load::of(&[entity], lookup);
+
+        (
+            Weight(load.weight / total as f32),
+            Bulk(load.bulk / total as f32),
+        )
+    }
+
+    /// Add `amount` items, each with `weight` and `bulk`.
+    pub fn add(&mut self, weight: Option<&Weight>, bulk: Option<&Bulk>, amount: u32) {
+        self.weight += weight.map_or(0.0, |weight| weight.0) * amount as f32;
+        self.bulk += bulk.map_or(0.0, |bulk| bulk.0) * amount as f32;
+    }
+
+    /// How many of `amount` items, each with `weight` and `bulk`, still fit
+    /// within `capacity`.
+    pub fn fits(
+        &self,
+        capacity: &Capacity,
+        weight: Option<&Weight>,
+        bulk: Option<&Bulk>,
+        amount: u32,
+    ) -> u32 {
+        let room = |used: f32, limit: f32, each: f32| {
+            if each > 0.0 {
+                // A little leeway, so rounding doesn't leave out the last item.
+                ((limit - used) / each + 0.001).floor().max(0.0) as u32
+            } else {
+                u32::MAX
+            }
+        };
+
+        amount
+            .min(room(
+                self.weight,
+                capacity.weight,
+                weight.map_or(0.0, |weight| weight.0),
+            ))
+            .min(room(
+                self.bulk,
+                capacity.bulk,
+                bulk.map_or(0.0, |bulk| bulk.0),
+            ))
+    }
+}
+
+/// Looks up what items weigh and take up, to work out a [`Load`] from.
+///
+/// Systems that change a [`Container`] can't have this as well, so they build
+/// their lookup from their own query instead.
+#[derive(SystemParam)]
+pub struct Loads<'w, 's> {
+    items: Query<
+        'w,
+        's,
+        (
+            Option<&'static Weight>,
+            Option<&'static Bulk>,
+            Option<&'static Stack>,
+            Option<&'static Container>,
+        ),
+        With<CanTake>,
+    >,
+}
+
+impl<'w, 's> Loads<'w, 's> {
+    /// What [`Load::of`] needs to know about `entity`.
+    pub fn lookup(
+        &self,
+        entity: Entity,
+    ) -> Option<(Option<&Weight>, Option<&Bulk>, u32, &[Entity])> {
+        self.items.get(entity).ok().map(carried)
+    }
+
+    /// Everything in `items`, with anything inside them.
+    pub fn of(&self, items: &[Entity]) -> Load {
+        Load::of(items, &|entity| self.lookup(entity))
+    }
+
+    /// Everything a player has on them, as in [`Load::of_player`].
+    pub fn of_player(&self, backpack: &[Entity], equipment: Option<&Equipment>) -> Load {
+        Load::of_player(backpack, equipment, &|entity| self.lookup(entity))
+    }
+
+    /// What each one of the `total` items stacked on `entity` weighs and takes up.
+    pub fn each(&self, entity: Entity, total: u32) -> (Weight, Bulk) {
+        Load::each(entity, total, &|entity| self.lookup(entity))
+    }
+}
+
+/// What [`Load::of`] needs to know about an item, from its components.
+#[allow(clippy::type_complexity)]
+fn carried<'a>(
+    (weight, bulk, stack, container): (
+        Option<&'a Weight>,
+        Option<&'a Bulk>,
+        Option<&'a Stack>,
+        Option<&'a Container>,
+    ),
+) -> (Option<&'a Weight>, Option<&'a Bulk>, u32, &'a [Entity]) {
+    (
+        weight,
+        bulk,
+        quantity(stack),
+        container.map_or(&[][..], |container| container.items.as_slice()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::items::components::{bulk::Bulk, capacity::Capacity, weight::Weight};
+
+    use super::Load;
+
+    #[test]
+    fn fits() {
+        let capacity = Capacity {
+            weight: 10.0,
+            bulk: 10.0,
+        };
+
+        let mut load = Load::default();
+
+        load.add(Some(&Weight(0.2)), Some(&Bulk(1.0)), 4);
+
+        assert_eq!(
+            load.fits(&capacity, Some(&Weight(0.2)), Some(&Bulk(1.0)), 10),
+            6
+        );
+        assert_eq!(load.fits(&capacity, Some(&Weight(9.5)), None, 1), 0);
+        assert_eq!(load.fits(&capacity, None, None, 100), 100);
+    }
+
+    #[test]
+    fn of() {
+        let (pouch, apples) = (Entity::from_raw(1), Entity::from_raw(2));
+        let contents = [apples];
+
+        let lookup = |entity: Entity| match entity.id() {
+            1 => Some((Some(&Weight(0.5)), Some(&Bulk(2.0)), 1, &contents[..])),
+            2 => Some((Some(&Weight(0.2)), Some(&Bulk(1.0)), 5, &[][..])),
+            _ => None,
+        };
+
+        let load = Load::of(&[pouch], &lookup);
+
+        assert!((load.weight - 1.5).abs() < 0.001);
+        assert!((load.bulk - 2.0).abs() < 0.001);
+    }
+}
@@ -0,0 +1,4 @@
+pub mod card;
+pub mod load;
+pub mod nesting;
+pub mod stacks;
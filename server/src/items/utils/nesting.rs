@@ -0,0 +1,194 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{
+    items::{components::container::Container, utils::stacks::Target},
+    spatial::components::{position::Position, zone::Zone},
+};
+
+/// How many containers deep items can go, so a pouch can sit in a chest but
+/// nothing more can be tucked inside another container in that pouch.
+pub const MAX_NESTING: usize = 2;
+
+/// Which container holds each contained entity.
+pub fn parents<'a>(
+    containers: impl IntoIterator<Item = (Entity, &'a Container)>,
+) -> HashMap<Entity, Entity> {
+    containers
+        .into_iter()
+        .flat_map(|(entity, container)| container.items.iter().map(move |item| (*item, entity)))
+        .collect()
+}
+
+/// Every container holding `entity`, from the innermost out.
+pub fn enclosing(entity: Entity, parents: &HashMap<Entity, Entity>) -> Vec<Entity> {
+    let mut enclosing = Vec::new();
+    let mut current = entity;
+
+    while let Some(parent) = parents.get(&current) {
+        // Shouldn't happen, but a loop would never end.
+        if enclosing.contains(parent) {
+            break;
+        }
+
+        enclosing.push(*parent);
+        current = *parent;
+    }
+
+    enclosing
+}
+
+/// How many levels of containers `entity` is made of, where `contents` gives
+/// what's inside a container. Anything that isn't a container is 0.
+pub fn height<'a, F>(entity: Entity, contents: &F) -> usize
+where
+    F: Fn(Entity) -> Option<&'a [Entity]>,
+{
+    match contents(entity) {
+        Some(items) => {
+            1 + items
+                .iter()
+                .map(|item| height(*item, contents))
+                .max()
+                .unwrap_or(0)
+        }
+        None => 0,
+    }
+}
+
+/// Whether `item` can go inside `container` without nesting containers deeper
+/// than [`MAX_NESTING`], or ending up inside itself.
+pub fn can_nest<'a, F>(
+    item: Entity,
+    container: Entity,
+    parents: &HashMap<Entity, Entity>,
+    contents: &F,
+) -> Result<(), &'static str>
+where
+    F: Fn(Entity) -> Option<&'a [Entity]>,
+{
+    let enclosing = enclosing(container, parents);
+
+    if item == container || enclosing.contains(&item) {
+        return Err("You can't put something inside itself.");
+    }
+
+    if enclosing.len() + 1 + height(item, contents) > MAX_NESTING {
+        return Err("You can't fit containers that far inside each other.");
+    }
+
+    Ok(())
+}
+
+/// The container `text` points at, like `chest` or `2.pouch`, out of
+/// `containers` along with their names and where they are. Containers in the
+/// `backpack` come before those on the ground at `position` in `zone`.
+pub fn find_container<'a>(
+    text: &str,
+    backpack: &[Entity],
+    position: &Position,
+    zone: &Zone,
+    containers: impl IntoIterator<Item = (Entity, &'a str, Option<&'a Position>, Option<&'a Zone>)>,
+) -> Option<Entity> {
+    let containers = containers.into_iter().collect::<Vec<_>>();
+
+    let carried = backpack
+        .iter()
+        .filter_map(|entity| containers.iter().find(|(e, _, _, _)| e == entity));
+    let here = containers.iter().filter(|(_, _, p, z)| {
+        p.map_or(false, |p| p.0 == position.0) && z.map_or(false, |z| z.0 == zone.0)
+    });
+
+    Target::parse(text)
+        .pick(
+            carried
+                .chain(here)
+                .map(|(entity, name, _, _)| (*entity, *name, 1)),
+        )
+        .first()
+        .map(|(entity, _, _)| *entity)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{
+        items::components::container::Container,
+        spatial::components::{position::Position, zone::Zone},
+    };
+
+    use super::{can_nest, find_container, parents};
+
+    #[test]
+    fn find() {
+        let (chest, pouch, other) = (
+            Entity::from_raw(1),
+            Entity::from_raw(2),
+            Entity::from_raw(3),
+        );
+        let (here, there) = (Position(IVec2::new(1, 1)), Position(IVec2::new(2, 2)));
+        let zone = Zone("Town".into());
+
+        let containers = [
+            (chest, "Chest", Some(&here), Some(&zone)),
+            (other, "Pouch", Some(&there), Some(&zone)),
+            (pouch, "Pouch", None, None),
+        ];
+
+        let find = |text| find_container(text, &[pouch], &here, &zone, containers);
+
+        assert_eq!(find("chest"), Some(chest));
+        assert_eq!(find("pouch"), Some(pouch));
+        assert_eq!(find("2.pouch"), None);
+        assert_eq!(find("sack"), None);
+    }
+
+    #[test]
+    fn nesting() {
+        let (chest, pouch, bag, apple) = (
+            Entity::from_raw(1),
+            Entity::from_raw(2),
+            Entity::from_raw(3),
+            Entity::from_raw(4),
+        );
+
+        let containers = [
+            (
+                chest,
+                Container {
+                    bulk: 100.0,
+                    items: vec![pouch],
+                },
+            ),
+            (
+                pouch,
+                Container {
+                    bulk: 10.0,
+                    items: vec![apple],
+                },
+            ),
+            (
+                bag,
+                Container {
+                    bulk: 10.0,
+                    items: vec![],
+                },
+            ),
+        ];
+
+        let parents = parents(containers.iter().map(|(e, c)| (*e, c)));
+        let contents = |entity| {
+            containers
+                .iter()
+                .find(|(e, _)| *e == entity)
+                .map(|(_, c)| c.items.as_slice())
+        };
+
+        assert_eq!(can_nest(apple, chest, &parents, &contents), Ok(()));
+        assert_eq!(can_nest(apple, pouch, &parents, &contents), Ok(()));
+        assert_eq!(can_nest(bag, chest, &parents, &contents), Ok(()));
+        assert!(can_nest(bag, pouch, &parents, &contents).is_err());
+        assert!(can_nest(chest, pouch, &parents, &contents).is_err());
+        assert!(can_nest(pouch, pouch, &parents, &contents).is_err());
+    }
+}
@@ -0,0 +1,487 @@
+use bevy::{
+    ecs::system::{Command, SystemState},
+    prelude::*,
+    utils::HashMap,
+};
+use bevy_proto::prelude::ProtoData;
+use inflector::Inflector;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::{
+    items::components::{item_details::ItemDetails, stack::Stack},
+    visual::components::sprite::{Sprite, SpritePaint},
+    world::components::{overridden::Overridden, prototype::Prototype},
+};
+
+/// How many of something a command is after.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Amount {
+    One,
+    Count(u32),
+    All,
+}
+
+/// What a command like `take 5 apple`, `drop all banana` or `take 2.apple` points at.
+#[derive(PartialEq, Debug)]
+pub struct Target {
+    pub amount: Amount,
+    /// Which of several matching stacks to use, counting from 1.
+    pub ordinal: Option<usize>,
+    /// The name or id to match, or nothing for `all`.
+    pub name: Option<String>,
+}
+
+impl Target {
+    pub fn parse(text: &str) -> Target {
+        lazy_static! {
+            static ref TARGET: Regex = Regex::new(r"^(?:(all|\d+) +)?(?:(\d+)\.)?(.+)$").unwrap();
+        }
+
+        let text = text.trim().to_lowercase();
+
+        if text == "all" {
+            return Target {
+                amount: Amount::All,
+                ordinal: None,
+                name: None,
+            };
+        }
+
+        match TARGET.captures(&text) {
+            Some(captures) => Target {
+                amount: match captures.get(1).map(|m| m.as_str()) {
+                    Some("all") => Amount::All,
+                    Some(count) => Amount::Count(count.parse().unwrap_or(u32::MAX)),
+                    None => Amount::One,
+                },
+                ordinal: captures.get(2).and_then(|m| m.as_str().parse().ok()),
+                name: captures.get(3).map(|m| m.as_str().trim().to_string()),
+            },
+            None => Target {
+                amount: Amount::One,
+                ordinal: None,
+                name: Some(text),
+            },
+        }
+    }
+
+    /// Whether `entity`, going by `name`, is what's being pointed at. Plurals
+    /// count too, so `drop all bananas` works.
+    pub fn matches(&self, entity: Entity, name: &str) -> bool {
+        match &self.name {
+            Some(target) => {
+                let name = name.to_lowercase();
+
+                *target == name || *target == name.to_plural() || *target == entity.id().to_string()
+            }
+            None => true,
+        }
+    }
+
+    /// Work out how many to move from each stack, given as the entity, its name
+    /// and how many it holds. Comes back with the entity and how many to move
+    /// out of how many it holds.
+    pub fn pick<'a>(
+        &self,
+        stacks: impl IntoIterator<Item = (Entity, &'a str, u32)>,
+    ) -> Vec<(Entity, u32, u32)> {
+        let mut matched = stacks
+            .into_iter()
+            .filter(|(entity, name, _)| self.matches(*entity, name))
+            .collect::<Vec<_>>();
+
+        if let Some(ordinal) = self.ordinal {
+            matched = matched
+                .into_iter()
+                .skip(ordinal.saturating_sub(1))
+                .take(1)
+                .collect();
+        }
+
+        let mut wanted = match self.amount {
+            Amount::One => 1,
+            Amount::Count(count) => count,
+            Amount::All => u32::MAX,
+        };
+
+        let mut picked = Vec::new();
+
+        for (entity, _, quantity) in matched {
+            if wanted == 0 {
+                break;
+            }
+
+            let moved = quantity.min(wanted);
+
+            wanted -= moved;
+            picked.push((entity, moved, quantity));
+        }
+
+        picked
+    }
+}
+
+/// How many items an entity stands for.
+pub fn quantity(stack: Option<&Stack>) -> u32 {
+    stack.map_or(1, |stack| stack.0)
+}
+
+/// Describe items moved by name and count, like `the Apple` or `5 Apples`,
+/// listing them off if there's more than one kind.
+pub fn describe(moved: &[(String, u32)]) -> String {
+    let mut counted: Vec<(&str, u32)> = Vec::new();
+
+    for (name, count) in moved {
+        match counted.iter_mut().find(|(n, _)| *n == name.as_str()) {
+            Some((_, total)) => *total += *count,
+            None => counted.push((name.as_str(), *count)),
+        }
+    }
+
+    let mut items = counted
+        .into_iter()
+        .map(|(name, count)| {
+            if count == 1 {
+                format!("the {name}")
+            } else {
+                format!("{count} {}", name.to_plural())
+            }
+        })
+        .collect::<Vec<_>>();
+
+    match items.pop() {
+        Some(last) if !items.is_empty() => format!("{} and {last}", items.join(", ")),
+        Some(last) => last,
+        None => String::new(),
+    }
+}
+
+/// A line listing `count` items called `name`, like `o 3 Apples`, along with
+/// how many things are inside if it's a container holding `contents`.
+pub fn listing(sprite: &Sprite, name: &str, count: u32, contents: Option<usize>) -> String {
+    format!(
+        "{} {} {}{}",
+        sprite.paint(),
+        count,
+        if count > 1 {
+            name.to_plural()
+        } else {
+            name.to_string()
+        },
+        match contents {
+            Some(0) => " (empty)".to_string(),
+            Some(held) => format!(" (holding {held})"),
+            None => String::new(),
+        }
+    )
+}
+
+/// What decides how an item stacks, to add to a query and hand to [`stacks_by`].
+pub type Stacking = (
+    Option<&'static Prototype>,
+    Option<&'static Overridden>,
+    Option<&'static ItemDetails>,
+);
+
+/// What an item stacks by: the [`Prototype`] it was spawned from. Anything
+/// without one, [`Overridden`] where it was placed, or with [`ItemDetails`] of
+/// its own like wear or a maker's mark, is one of a kind and never stacks.
+/// Neither do containers, so check for those as well.
+pub fn stacks_by<'a>(
+    (prototype, overridden, details): (
+        Option<&'a Prototype>,
+        Option<&'a Overridden>,
+        Option<&'a ItemDetails>,
+    ),
+) -> Option<&'a str> {
+    match (prototype, overridden, details) {
+        (Some(prototype), None, None) => Some(prototype.0.as_str()),
+        _ => None,
+    }
+}
+
+/// Move `amount` of the `total` items stacked on `entity` somewhere else, where
+/// `stacks` are the stacks already there by what they stack by, and how many
+/// each holds.
+///
+/// They join the stack with the same `key` if there is one, despawning `entity`
+/// if that empties it, or are split off into an entity of their own otherwise.
+/// Things that never stack, like containers, have no `key` and move whole.
+/// Comes back with whatever needs putting in the new place, if anything.
+pub fn move_stack(
+    commands: &mut Commands,
+    stacks: &mut HashMap<String, (Entity, u32)>,
+    key: Option<&str>,
+    entity: Entity,
+    amount: u32,
+    total: u32,
+) -> Option<Entity> {
+    let key = if let Some(key) = key {
+        key
+    } else {
+        return Some(entity);
+    };
+
+    if let Some((stack, held)) = stacks.get_mut(key) {
+        *held += amount;
+
+        commands.add(MergeStack {
+            from: entity,
+            into: *stack,
+            amount,
+        });
+
+        return None;
+    }
+
+    let moved = if amount == total {
+        entity
+    } else {
+        let into = commands.spawn().id();
+
+        commands.add(SplitStack {
+            from: entity,
+            into,
+            amount,
+        });
+
+        into
+    };
+
+    stacks.insert(key.to_string(), (moved, amount));
+
+    Some(moved)
+}
+
+/// Move whole stacks of `entities` into `into`, joining matching stacks already
+/// there. `lookup` gives what each stacks by, if it stacks at all, and how many
+/// it holds.
+pub fn stow<'a, F>(commands: &mut Commands, into: &mut Vec<Entity>, entities: &[Entity], lookup: &F)
+where
+    F: Fn(Entity) -> Option<(Option<&'a str>, u32)>,
+{
+    let mut stacks = into
+        .iter()
+        .filter_map(|entity| {
+            lookup(*entity)
+                .and_then(|(key, quantity)| key.map(|key| (key.to_string(), (*entity, quantity))))
+        })
+        .collect::<HashMap<_, _>>();
+
+    for entity in entities {
+        if let Some((key, quantity)) = lookup(*entity) {
+            if let Some(moved) = move_stack(commands, &mut stacks, key, *entity, quantity, quantity)
+            {
+                into.push(moved);
+            }
+        }
+    }
+}
+
+/// Move `amount` items out of the stack on `from` onto the stack on `into`,
+/// despawning `from` if that empties it. Both are counted when this is
+/// applied, so several merges into the same stack in one frame all add up.
+pub struct MergeStack {
+    pub from: Entity,
+    pub into: Entity,
+    pub amount: u32,
+}
+
+impl Command for MergeStack {
+    fn write(self, world: &mut World) {
+        let held = quantity(world.get::<Stack>(self.into));
+
+        if let Some(mut into) = world.get_entity_mut(self.into) {
+            into.insert(Stack(held + self.amount));
+        }
+
+        let left = quantity(world.get::<Stack>(self.from)).saturating_sub(self.amount);
+
+        if left == 0 {
+            world.despawn(self.from);
+        } else if let Some(mut from) = world.get_entity_mut(self.from) {
+            from.insert(Stack(left));
+        }
+    }
+}
+
+/// Move `amount` items out of the stack on `from` into the new entity `into`,
+/// built afresh from the same [`Prototype`] so it's the same item in every
+/// way. Only prototyped items ever stack, but if the prototype has gone since,
+/// the stack is left whole rather than split into something less. `into` is
+/// left wherever it's put, so give it a position or add it to a backpack.
+pub struct SplitStack {
+    pub from: Entity,
+    pub into: Entity,
+    pub amount: u32,
+}
+
+impl Command for SplitStack {
+    fn write(self, world: &mut World) {
+        let prototype = world.get::<Prototype>(self.from).cloned();
+
+        let mut state =
+            SystemState::<(Commands, Option<Res<ProtoData>>, Option<Res<AssetServer>>)>::new(world);
+
+        let built = {
+            let (mut commands, prototypes, asset_server) = state.get_mut(world);
+
+            match (prototype, prototypes, asset_server) {
+                (Some(prototype), Some(prototypes), Some(asset_server)) => prototypes
+                    .get_prototype(&prototype.0)
+                    .map(|template| {
+                        template
+                            .insert(commands.entity(self.into), &prototypes, &asset_server)
+                            .insert_bundle((prototype.clone(), Stack(self.amount)));
+                    })
+                    .is_some(),
+                _ => false,
+            }
+        };
+
+        state.apply(world);
+
+        if !built {
+            warn!("Could not split {:?}, as its prototype is gone", self.from);
+
+            world.despawn(self.into);
+
+            return;
+        }
+
+        if let Some(mut stack) = world.get_mut::<Stack>(self.from) {
+            stack.0 = stack.0.saturating_sub(self.amount);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::Command, prelude::*};
+
+    use crate::{
+        items::components::{consumable::Consumable, item_details::ItemDetails, stack::Stack},
+        test::{
+            bundles::utils::{item_in_backpack_bundle, ItemBundle},
+            prototypes::utils::insert_prototypes,
+        },
+        world::components::{overridden::Overridden, prototype::Prototype},
+    };
+
+    use super::{describe, stacks_by, Amount, SplitStack, Target};
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            Target::parse("5 apple"),
+            Target {
+                amount: Amount::Count(5),
+                ordinal: None,
+                name: Some("apple".into()),
+            }
+        );
+        assert_eq!(
+            Target::parse("all Banana"),
+            Target {
+                amount: Amount::All,
+                ordinal: None,
+                name: Some("banana".into()),
+            }
+        );
+        assert_eq!(
+            Target::parse("2.apple"),
+            Target {
+                amount: Amount::One,
+                ordinal: Some(2),
+                name: Some("apple".into()),
+            }
+        );
+        assert_eq!(
+            Target::parse("all"),
+            Target {
+                amount: Amount::All,
+                ordinal: None,
+                name: None,
+            }
+        );
+    }
+
+    #[test]
+    fn pick() {
+        let (first, second, banana) = (
+            Entity::from_raw(1),
+            Entity::from_raw(2),
+            Entity::from_raw(3),
+        );
+        let stacks = [
+            (first, "Apple", 3),
+            (banana, "Banana", 1),
+            (second, "Apple", 4),
+        ];
+
+        assert_eq!(
+            Target::parse("5 apples").pick(stacks),
+            vec![(first, 3, 3), (second, 2, 4)]
+        );
+        assert_eq!(Target::parse("2.apple").pick(stacks), vec![(second, 1, 4)]);
+        assert_eq!(Target::parse("all").pick(stacks).len(), 3);
+        assert_eq!(Target::parse("pear").pick(stacks), vec![]);
+    }
+
+    #[test]
+    fn describe_moved() {
+        assert_eq!(describe(&[("Apple".into(), 1)]), "the Apple");
+        assert_eq!(
+            describe(&[
+                ("Apple".into(), 2),
+                ("Banana".into(), 1),
+                ("Apple".into(), 1)
+            ]),
+            "3 Apples and the Banana"
+        );
+    }
+
+    #[test]
+    fn stacks_by_prototype() {
+        let prototype = Prototype("cap".into());
+        let details = ItemDetails::default();
+
+        assert_eq!(stacks_by((Some(&prototype), None, None)), Some("cap"));
+        assert_eq!(stacks_by((None, None, None)), None);
+        assert_eq!(stacks_by((Some(&prototype), Some(&Overridden), None)), None);
+        assert_eq!(stacks_by((Some(&prototype), None, Some(&details))), None);
+    }
+
+    #[test]
+    fn split_from_prototype() {
+        let mut app = App::new();
+
+        insert_prototypes(&mut app);
+
+        // The bundle leaves out most of what an apple is, like being edible.
+        let from = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Apple".into(),
+                quantity: 3,
+                ..Default::default()
+            }))
+            .id();
+
+        let into = app.world.spawn().id();
+
+        SplitStack {
+            from,
+            into,
+            amount: 2,
+        }
+        .write(&mut app.world);
+
+        assert_eq!(app.world.get::<Stack>(from).unwrap().0, 1);
+        assert_eq!(app.world.get::<Stack>(into).unwrap().0, 2);
+        assert_eq!(app.world.get::<Prototype>(into).unwrap().0, "Apple");
+        assert!(app.world.get::<Consumable>(into).is_some());
+    }
+}
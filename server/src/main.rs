@@ -8,6 +8,8 @@ mod test;
 mod visual;
 mod world;
 
+use std::{env, process};
+
 use bevy::{
     app::ScheduleRunnerSettings, asset::AssetPlugin, log::LogPlugin, prelude::*, utils::Duration,
 };
@@ -21,11 +23,33 @@ use player::PlayerPlugin;
 use social::SocialPlugin;
 use spatial::SpatialPlugin;
 use visual::VisualPlugin;
-use world::WorldPlugin;
+use world::{utils::validation::validate_assets, WorldPlugin};
+
+/// Where bevy_proto finds prototypes, for the server and for `server validate`.
+fn proto_options() -> ProtoDataOptions {
+    ProtoDataOptions {
+        directories: vec![String::from("server/assets/prototypes")],
+        recursive_loading: true,
+        ..Default::default()
+    }
+}
 
 fn main() {
     dotenv().ok();
 
+    // `server validate` checks the levels and prototypes without starting the server.
+    if env::args().nth(1).as_deref() == Some("validate") {
+        let problems = validate_assets(proto_options());
+
+        for problem in &problems {
+            println!("{problem}");
+        }
+
+        println!("Found {} problem(s)", problems.len());
+
+        process::exit(if problems.is_empty() { 0 } else { 1 });
+    }
+
     App::new()
         .insert_resource(ScheduleRunnerSettings::run_loop(Duration::from_secs_f64(
             1.0 / 60.0,
@@ -33,11 +57,7 @@ fn main() {
         .add_plugins(MinimalPlugins)
         .add_plugin(AssetPlugin)
         .add_plugin(ProtoPlugin {
-            options: Some(ProtoDataOptions {
-                directories: vec![String::from("server/assets/prototypes")],
-                recursive_loading: true,
-                ..Default::default()
-            }),
+            options: Some(proto_options()),
         })
         .add_plugin(LogPlugin)
         .add_plugin(WorldPlugin)
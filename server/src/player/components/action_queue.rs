@@ -0,0 +1,18 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+/// Actions a player has asked to perform, done one at a time with
+/// `cooldown` running between them.
+#[derive(Component, Default, Debug)]
+pub struct ActionQueue {
+    pub actions: VecDeque<String>,
+    pub cooldown: Timer,
+}
+
+impl ActionQueue {
+    /// Whether the last action has finished and the next can be performed.
+    pub fn is_ready(&self) -> bool {
+        self.cooldown.elapsed() >= self.cooldown.duration()
+    }
+}
@@ -0,0 +1,70 @@
+use bevy::{prelude::*, utils::Duration};
+
+/// A temporary effect on a character, like being well fed, that wears off
+/// when its timer runs out.
+#[derive(Debug)]
+pub struct Buff {
+    /// How the character is while it lasts, as in "You are well fed."
+    pub name: String,
+    pub timer: Timer,
+}
+
+/// Every [`Buff`] a character has on them.
+#[derive(Component, Default, Debug)]
+pub struct Buffs(pub Vec<Buff>);
+
+impl Buffs {
+    /// Add a buff lasting `seconds`, or start the one by that name over.
+    pub fn apply(&mut self, name: &str, seconds: f32) {
+        let timer = Timer::from_seconds(seconds, false);
+
+        match self.0.iter_mut().find(|buff| buff.name == name) {
+            Some(buff) => buff.timer = timer,
+            None => self.0.push(Buff {
+                name: name.to_string(),
+                timer,
+            }),
+        }
+    }
+
+    /// Run every timer forward by `delta`, removing and coming back with the
+    /// names of any buffs that wore off.
+    pub fn tick(&mut self, delta: Duration) -> Vec<String> {
+        let mut expired = Vec::new();
+
+        self.0.retain_mut(|buff| {
+            if buff.timer.tick(delta).finished() {
+                expired.push(buff.name.clone());
+
+                false
+            } else {
+                true
+            }
+        });
+
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::utils::Duration;
+
+    use super::Buffs;
+
+    #[test]
+    fn tick() {
+        let mut buffs = Buffs::default();
+
+        buffs.apply("well fed", 10.0);
+        buffs.apply("refreshed", 5.0);
+
+        assert!(buffs.tick(Duration::from_secs(4)).is_empty());
+
+        buffs.apply("refreshed", 8.0);
+
+        assert!(buffs.tick(Duration::from_secs(4)).is_empty());
+        assert_eq!(buffs.tick(Duration::from_secs(2)), vec!["well fed"]);
+        assert_eq!(buffs.0.len(), 1);
+    }
+}
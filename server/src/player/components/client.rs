@@ -2,8 +2,18 @@ use bevy::prelude::*;
 
 use crate::network::server::ConnectionId;
 
+/// How many rows tall the map is drawn.
+pub const MAP_HEIGHT: i32 = 16;
+
 #[derive(Component)]
 pub struct NetworkClient {
     pub id: ConnectionId,
     pub width: i32,
 }
+
+impl NetworkClient {
+    /// How far the player can see, out to the edges of their map.
+    pub fn sight_radius(&self) -> i32 {
+        (self.width / 2).max(MAP_HEIGHT / 2)
+    }
+}
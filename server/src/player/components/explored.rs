@@ -0,0 +1,107 @@
+use api::explored::handlers::ExploredZone;
+use bevy::{prelude::*, utils::HashMap};
+
+/// Every cell a character has seen, kept as a bitset per zone with
+/// one bit per grid cell, row by row.
+#[derive(Component, Default)]
+pub struct Explored(pub HashMap<String, Cells>);
+
+/// The bits for one zone, and how wide the zone was when they were set.
+#[derive(Default)]
+pub struct Cells {
+    pub width: i32,
+    pub bits: Vec<u8>,
+}
+
+impl Explored {
+    /// Remember a position in a zone of the given size.
+    pub fn insert(&mut self, zone: &str, size: IVec2, position: IVec2) {
+        if let Some(index) = bit_index(size, position) {
+            let cells = self.0.entry(zone.to_string()).or_default();
+            let length = ((size.x * size.y + 7) / 8) as usize;
+
+            // A different width shifts every row, so what was there can't be
+            // trusted any more.
+            if cells.width != size.x {
+                cells.width = size.x;
+                cells.bits.clear();
+            }
+
+            // The zone may have grown taller since the bitset was last saved.
+            if cells.bits.len() < length {
+                cells.bits.resize(length, 0);
+            }
+
+            cells.bits[index / 8] |= 1 << (index % 8);
+        }
+    }
+
+    /// Whether a position in a zone of the given size has been seen before.
+    pub fn contains(&self, zone: &str, size: IVec2, position: IVec2) -> bool {
+        bit_index(size, position)
+            .and_then(|index| {
+                self.0
+                    .get(zone)
+                    .filter(|cells| cells.width == size.x)?
+                    .bits
+                    .get(index / 8)
+                    .map(|byte| byte & (1 << (index % 8)) != 0)
+            })
+            .unwrap_or(false)
+    }
+
+    /// Every zone's cells, ready to save.
+    pub fn zones(&self) -> Vec<ExploredZone> {
+        self.0
+            .iter()
+            .map(|(zone, cells)| ExploredZone {
+                zone: zone.clone(),
+                width: cells.width,
+                cells: cells.bits.clone(),
+            })
+            .collect()
+    }
+}
+
+fn bit_index(size: IVec2, position: IVec2) -> Option<usize> {
+    if position.x < 0 || position.y < 0 || position.x >= size.x || position.y >= size.y {
+        None
+    } else {
+        Some((position.y * size.x + position.x) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::Explored;
+
+    #[test]
+    fn remembers() {
+        let mut explored = Explored::default();
+        let size = IVec2::new(10, 4);
+
+        explored.insert("Test", size, IVec2::new(3, 2));
+
+        assert!(explored.contains("Test", size, IVec2::new(3, 2)));
+        assert!(!explored.contains("Test", size, IVec2::new(2, 3)));
+        assert!(!explored.contains("Other", size, IVec2::new(3, 2)));
+        assert_eq!(explored.0.get("Test").unwrap().bits.len(), 5);
+    }
+
+    #[test]
+    fn resized() {
+        let mut explored = Explored::default();
+
+        explored.insert("Test", IVec2::new(10, 4), IVec2::new(3, 2));
+
+        assert!(!explored.contains("Test", IVec2::new(12, 4), IVec2::new(3, 2)));
+        assert!(explored.contains("Test", IVec2::new(10, 6), IVec2::new(3, 2)));
+
+        explored.insert("Test", IVec2::new(12, 4), IVec2::new(0, 0));
+
+        assert!(!explored.contains("Test", IVec2::new(12, 4), IVec2::new(3, 2)));
+        assert!(explored.contains("Test", IVec2::new(12, 4), IVec2::new(0, 0)));
+    }
+}
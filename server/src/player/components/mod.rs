@@ -1,3 +1,7 @@
+pub mod action_queue;
+pub mod buffs;
 pub mod character;
 pub mod client;
+pub mod explored;
 pub mod online;
+pub mod vitals;
@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// One of a character's [`Vitals`].
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum Stat {
+    Health,
+    Stamina,
+}
+
+impl Stat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Stat::Health => "health",
+            Stat::Stamina => "stamina",
+        }
+    }
+}
+
+/// How healthy and rested a character is, out of their maximum for each.
+#[derive(Component, Clone, Copy, PartialEq, Debug)]
+pub struct Vitals {
+    pub health: u32,
+    pub max_health: u32,
+    pub stamina: u32,
+    pub max_stamina: u32,
+}
+
+impl Default for Vitals {
+    fn default() -> Self {
+        Self {
+            health: 100,
+            max_health: 100,
+            stamina: 100,
+            max_stamina: 100,
+        }
+    }
+}
+
+impl Vitals {
+    /// Where `stat` is at, out of its maximum.
+    pub fn get(&self, stat: Stat) -> (u32, u32) {
+        match stat {
+            Stat::Health => (self.health, self.max_health),
+            Stat::Stamina => (self.stamina, self.max_stamina),
+        }
+    }
+
+    /// Raise `stat` by up to `amount` without going over its maximum, coming
+    /// back with how much it actually went up.
+    pub fn restore(&mut self, stat: Stat, amount: u32) -> u32 {
+        let (current, max) = match stat {
+            Stat::Health => (&mut self.health, self.max_health),
+            Stat::Stamina => (&mut self.stamina, self.max_stamina),
+        };
+
+        let restored = amount.min(max.saturating_sub(*current));
+
+        *current += restored;
+
+        restored
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Stat, Vitals};
+
+    #[test]
+    fn restore() {
+        let mut vitals = Vitals {
+            health: 90,
+            ..Default::default()
+        };
+
+        assert_eq!(vitals.restore(Stat::Health, 5), 5);
+        assert_eq!(vitals.restore(Stat::Health, 20), 5);
+        assert_eq!(vitals.restore(Stat::Stamina, 20), 0);
+        assert_eq!(vitals.get(Stat::Health), (100, 100));
+    }
+}
@@ -0,0 +1,9 @@
+use crate::network::server::ConnectionId;
+
+/// Sent by the [`process_actions`] system when a queued action is due,
+/// and read by the systems that perform each action.
+#[derive(Debug)]
+pub struct ActionEvent {
+    pub id: ConnectionId,
+    pub body: String,
+}
@@ -1 +1,2 @@
+pub mod action_event;
 pub mod prompt_event;
@@ -1,26 +1,55 @@
 pub mod components;
 pub mod events;
+pub mod resources;
 pub mod systems;
+pub mod utils;
 
-use bevy::prelude::*;
+use bevy::{core::FixedTimestep, prelude::*};
 
 use self::{
-    events::prompt_event::PromptEvent,
-    systems::{emit_prompt_on_input::*, handle_network_events::*, send_prompt::*},
+    events::{action_event::ActionEvent, prompt_event::PromptEvent},
+    resources::{action_settings::ActionSettings, admins::Admins, player_index::PlayerIndex},
+    systems::{
+        emit_prompt_on_input::*, expire_buffs::*, handle_network_events::*, index_players::*,
+        process_actions::*, queue_actions::*, save_explored::*, send_prompt::*, status::*,
+    },
 };
 
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<PlayerIndex>();
+        app.init_resource::<ActionSettings>();
+        app.init_resource::<Admins>();
+
         app.add_event::<PromptEvent>();
+        app.add_event::<ActionEvent>();
+
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
 
         app.add_system_set(
             SystemSet::new()
                 .label("player")
                 .with_system(handle_network_events)
                 .with_system(emit_prompt_on_input)
-                .with_system(send_prompt),
+                .with_system(send_prompt)
+                .with_system(queue_actions)
+                .with_system(expire_buffs)
+                .with_system(status),
+        );
+
+        app.add_system_set(
+            SystemSet::new()
+                .label("actions")
+                .with_run_criteria(FixedTimestep::step(ACTION_TICK))
+                .with_system(process_actions),
+        );
+
+        app.add_system_set(
+            SystemSet::new()
+                .with_run_criteria(FixedTimestep::step(EXPLORED_SAVE_INTERVAL))
+                .with_system(save_explored),
         );
     }
 }
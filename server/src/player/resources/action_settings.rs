@@ -0,0 +1,18 @@
+use std::env;
+
+/// How many actions a player can have queued up at once. Defaults to 10,
+/// or `MAX_QUEUED_ACTIONS` from env.
+pub struct ActionSettings {
+    pub max_queued: usize,
+}
+
+impl Default for ActionSettings {
+    fn default() -> Self {
+        Self {
+            max_queued: env::var("MAX_QUEUED_ACTIONS")
+                .ok()
+                .and_then(|max| max.parse().ok())
+                .unwrap_or(10),
+        }
+    }
+}
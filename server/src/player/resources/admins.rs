@@ -0,0 +1,21 @@
+use std::env;
+
+/// Names of the characters allowed to use admin commands, from a comma
+/// separated `ADMINS` in env.
+pub struct Admins(pub Vec<String>);
+
+impl Default for Admins {
+    fn default() -> Self {
+        Self(
+            env::var("ADMINS")
+                .map(|admins| {
+                    admins
+                        .split(',')
+                        .map(|name| name.trim().to_string())
+                        .filter(|name| !name.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        )
+    }
+}
@@ -0,0 +1,3 @@
+pub mod action_settings;
+pub mod admins;
+pub mod player_index;
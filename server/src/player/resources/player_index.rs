@@ -0,0 +1,37 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::network::server::ConnectionId;
+
+/// Maps connections and characters to their player entity so systems
+/// can resolve who sent a message without scanning every player.
+#[derive(Default)]
+pub struct PlayerIndex {
+    clients: HashMap<ConnectionId, Entity>,
+    characters: HashMap<i32, Entity>,
+}
+
+impl PlayerIndex {
+    /// The entity for a given connection, if it's still connected.
+    pub fn get(&self, id: &ConnectionId) -> Option<Entity> {
+        self.clients.get(id).copied()
+    }
+
+    /// The entity for a given character, if they're logged in.
+    pub fn get_character(&self, id: i32) -> Option<Entity> {
+        self.characters.get(&id).copied()
+    }
+
+    pub fn insert_client(&mut self, id: ConnectionId, entity: Entity) {
+        self.clients.insert(id, entity);
+    }
+
+    pub fn insert_character(&mut self, id: i32, entity: Entity) {
+        self.characters.insert(id, entity);
+    }
+
+    /// Forget everything about an entity.
+    pub fn remove(&mut self, entity: Entity) {
+        self.clients.retain(|_, e| *e != entity);
+        self.characters.retain(|_, e| *e != entity);
+    }
+}
@@ -5,6 +5,7 @@ use crate::{
     player::{
         components::{client::NetworkClient, online::Online},
         events::prompt_event::PromptEvent,
+        utils::players::Players,
     },
 };
 
@@ -13,11 +14,11 @@ use crate::{
 pub fn emit_prompt_on_input(
     mut input: EventReader<NetworkInput>,
     mut prompts: EventWriter<PromptEvent>,
-    players: Query<&NetworkClient, With<Online>>,
+    players: Players<&NetworkClient, With<Online>>,
 ) {
     for message in input.iter() {
         if !message.internal {
-            if let Some(client) = players.iter().find(|c| c.id == message.id) {
+            if let Some(client) = players.sender(&message.id) {
                 prompts.send(PromptEvent(client.id));
             }
         }
@@ -30,7 +31,10 @@ mod test {
 
     use crate::{
         network::events::NetworkInput,
-        player::{components::client::NetworkClient, events::prompt_event::PromptEvent},
+        player::{
+            components::client::NetworkClient, events::prompt_event::PromptEvent,
+            resources::player_index::PlayerIndex, systems::index_players::index_players,
+        },
         test::bundles::utils::{player_bundle, PlayerBundle},
     };
 
@@ -40,6 +44,8 @@ mod test {
 
         app.add_event::<NetworkInput>();
         app.add_event::<PromptEvent>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::emit_prompt_on_input);
 
         let player = app
@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+
+use crate::{
+    network::events::NetworkOutput,
+    player::components::{buffs::Buffs, client::NetworkClient},
+};
+
+/// Run down every [`Buffs`] timer, letting players know as theirs wear off.
+pub fn expire_buffs(
+    time: Res<Time>,
+    mut output: EventWriter<NetworkOutput>,
+    mut players: Query<(&NetworkClient, &mut Buffs)>,
+) {
+    for (client, mut buffs) in players.iter_mut() {
+        if buffs.0.is_empty() {
+            continue;
+        }
+
+        for name in buffs.tick(time.delta()) {
+            output.send(NetworkOutput {
+                id: client.id,
+                body: format!("You are no longer {name}."),
+            });
+        }
+    }
+}
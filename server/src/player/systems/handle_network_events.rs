@@ -2,18 +2,48 @@ use bevy::prelude::*;
 
 use crate::{
     auth::components::authenticating::Authenticating,
+    items::{
+        components::{
+            backpack::Backpack, can_take::CanTake, container::Container,
+            refuses_gifts::RefusesGifts, stack::Stack, trade::Trade,
+        },
+        utils::stacks::{quantity, stacks_by, stow, Stacking},
+    },
     network::events::{NetworkEvent, NetworkOutput},
-    player::components::client::NetworkClient,
+    player::{
+        components::{character::Character, client::NetworkClient, explored::Explored},
+        resources::player_index::PlayerIndex,
+        utils::api::{save_explored, save_settings},
+    },
+    spatial::components::position::Position,
 };
 
 /// Spawn a new entity with a [`Player`] component when a new connection
-/// comes in, an despawn it when the connection is lost.
+/// comes in, an despawn it when the connection is lost, saving what
+/// they've [`Explored`] and whether they've turned off gifts on the way out.
+/// Anything they had on offer in a [`Trade`] goes back in their [`Backpack`]
+/// first, and `cancel_trades` hands the other side theirs.
 pub fn handle_network_events(
     mut commands: Commands,
     mut events: EventReader<NetworkEvent>,
     mut output: EventWriter<NetworkOutput>,
-    players: Query<(Entity, &NetworkClient)>,
+    mut index: ResMut<PlayerIndex>,
+    players: Query<(&Character, &Explored, Option<&RefusesGifts>)>,
+    mut traders: Query<(&mut Backpack, &Trade)>,
+    items: Query<
+        (Option<&Stack>, Option<&Container>, Stacking),
+        (With<CanTake>, Without<Position>),
+    >,
 ) {
+    let lookup = |entity| {
+        items.get(entity).ok().map(|(stack, container, by)| {
+            (
+                stacks_by(by).filter(|_| container.is_none()),
+                quantity(stack),
+            )
+        })
+    };
+
     for event in events.iter() {
         match event {
             NetworkEvent::Connected(id) => {
@@ -30,8 +60,27 @@ pub fn handle_network_events(
                 info!("Player spawned for {id:?}");
             }
             NetworkEvent::Disconnected(id) => {
-                if let Some((entity, _)) = players.iter().find(|(_, c)| c.id == *id) {
+                if let Some(entity) = index.get(id) {
+                    if let Ok((mut backpack, trade)) = traders.get_mut(entity) {
+                        stow(&mut commands, &mut backpack.0, &trade.offer, &lookup);
+                    }
+
+                    if let Ok((character, explored, refuses_gifts)) = players.get(entity) {
+                        if !save_explored(character.id, explored.zones())
+                            .map_or(false, |response| response.status().is_success())
+                        {
+                            warn!("Could not save explored map for {}", character.name);
+                        }
+
+                        if !save_settings(character.id, refuses_gifts.is_some())
+                            .map_or(false, |response| response.status().is_success())
+                        {
+                            warn!("Could not save settings for {}", character.name);
+                        }
+                    }
+
                     commands.entity(entity).despawn();
+                    index.remove(entity);
 
                     info!("Player despawned {id:?}");
                 }
@@ -42,3 +91,111 @@ pub fn handle_network_events(
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::{
+        items::components::{stack::Stack, trade::Trade},
+        network::events::{NetworkEvent, NetworkOutput},
+        player::{
+            components::client::NetworkClient, resources::player_index::PlayerIndex,
+            systems::index_players::index_players,
+        },
+        test::bundles::utils::{item_in_backpack_bundle, player_bundle, ItemBundle, PlayerBundle},
+    };
+
+    #[test]
+    fn disconnected() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::handle_network_events);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        app.world
+            .resource_mut::<Events<NetworkEvent>>()
+            .send(NetworkEvent::Disconnected(player_client_id));
+
+        app.update();
+
+        assert!(app.world.get_entity(player).is_none());
+        assert!(app
+            .world
+            .resource::<PlayerIndex>()
+            .get(&player_client_id)
+            .is_none());
+    }
+
+    #[test]
+    fn trading() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::handle_network_events);
+
+        let apples = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Apple".into(),
+                quantity: 2,
+                ..Default::default()
+            }))
+            .id();
+
+        let offered = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Apple".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        let partner = app.world.spawn().id();
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                items: vec![apples],
+                ..Default::default()
+            }))
+            .insert(Trade {
+                partner,
+                offer: vec![offered],
+                accepted: false,
+            })
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        app.world
+            .resource_mut::<Events<NetworkEvent>>()
+            .send(NetworkEvent::Disconnected(player_client_id));
+
+        app.update();
+
+        // The offer went back into their apples before they were despawned.
+        assert!(app.world.get_entity(player).is_none());
+        assert!(app.world.get_entity(offered).is_none());
+        assert_eq!(app.world.get::<Stack>(apples).unwrap().0, 3);
+    }
+}
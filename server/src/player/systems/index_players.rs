@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+
+use crate::player::{
+    components::{character::Character, client::NetworkClient},
+    resources::player_index::PlayerIndex,
+};
+
+/// Keep the [`PlayerIndex`] up to date as clients connect and
+/// characters log in.
+pub fn index_players(
+    mut index: ResMut<PlayerIndex>,
+    clients: Query<(Entity, &NetworkClient), Added<NetworkClient>>,
+    characters: Query<(Entity, &Character), Added<Character>>,
+) {
+    for (entity, client) in clients.iter() {
+        index.insert_client(client.id, entity);
+    }
+
+    for (entity, character) in characters.iter() {
+        index.insert_character(character.id, entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{
+        player::{
+            components::{character::Character, client::NetworkClient},
+            resources::player_index::PlayerIndex,
+        },
+        test::bundles::utils::{player_bundle, PlayerBundle},
+    };
+
+    #[test]
+    fn indexes() {
+        let mut app = App::new();
+
+        app.init_resource::<PlayerIndex>();
+        app.add_system(super::index_players);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+        let player_character_id = app.world.get::<Character>(player).unwrap().id;
+
+        app.update();
+
+        let index = app.world.resource::<PlayerIndex>();
+
+        assert_eq!(index.get(&player_client_id), Some(player));
+        assert_eq!(index.get_character(player_character_id), Some(player));
+    }
+}
@@ -1,3 +1,9 @@
 pub mod emit_prompt_on_input;
+pub mod expire_buffs;
 pub mod handle_network_events;
+pub mod index_players;
+pub mod process_actions;
+pub mod queue_actions;
+pub mod save_explored;
 pub mod send_prompt;
+pub mod status;
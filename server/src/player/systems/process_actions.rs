@@ -0,0 +1,80 @@
+use bevy::{prelude::*, utils::Duration};
+
+use crate::player::{
+    components::{action_queue::ActionQueue, client::NetworkClient},
+    events::action_event::ActionEvent,
+    utils::actions::action_duration,
+};
+
+/// How often, in seconds, queued actions are processed.
+pub const ACTION_TICK: f64 = 0.1;
+
+/// Perform the next action in each player's [`ActionQueue`] once their
+/// last one has finished. Runs every [`ACTION_TICK`].
+pub fn process_actions(
+    mut actions: EventWriter<ActionEvent>,
+    mut players: Query<(&NetworkClient, &mut ActionQueue)>,
+) {
+    for (client, mut queue) in players.iter_mut() {
+        queue.cooldown.tick(Duration::from_secs_f64(ACTION_TICK));
+
+        if !queue.is_ready() {
+            continue;
+        }
+
+        if let Some(body) = queue.actions.pop_front() {
+            queue.cooldown = Timer::from_seconds(action_duration(&body).unwrap_or_default(), false);
+
+            actions.send(ActionEvent {
+                id: client.id,
+                body,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::{
+        player::{components::action_queue::ActionQueue, events::action_event::ActionEvent},
+        test::bundles::utils::{player_bundle, PlayerBundle},
+    };
+
+    #[test]
+    fn one_at_a_time() {
+        let mut app = App::new();
+
+        app.add_event::<ActionEvent>();
+        app.add_system(super::process_actions);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        app.world
+            .get_mut::<ActionQueue>(player)
+            .unwrap()
+            .actions
+            .extend(["n".to_string(), "s".to_string()]);
+
+        app.update();
+        app.update();
+
+        let action_events = app.world.resource::<Events<ActionEvent>>();
+        let mut action_reader = action_events.get_reader();
+        let actions = action_reader.iter(action_events).collect::<Vec<_>>();
+
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].body, "n");
+        assert_eq!(
+            app.world.get::<ActionQueue>(player).unwrap().actions,
+            vec!["s"]
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use bevy::prelude::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::{
+    network::events::{NetworkInput, NetworkOutput},
+    player::{
+        components::{action_queue::ActionQueue, client::NetworkClient, online::Online},
+        resources::action_settings::ActionSettings,
+        utils::{actions::action_duration, players::Players},
+    },
+};
+
+/// Add any actions players send to their [`ActionQueue`] so they're performed
+/// one at a time, and handle `stop` and `clear` to empty it.
+pub fn queue_actions(
+    mut input: EventReader<NetworkInput>,
+    mut output: EventWriter<NetworkOutput>,
+    settings: Res<ActionSettings>,
+    mut players: Players<(&NetworkClient, &mut ActionQueue), With<Online>>,
+) {
+    lazy_static! {
+        static ref CMD: Regex = Regex::new("^(stop|clear)$").unwrap();
+    }
+
+    for message in input.iter() {
+        if let Some((client, mut queue)) = players.sender_mut(&message.id) {
+            if let Some(captures) = CMD.captures(&message.body.to_lowercase()) {
+                let count = queue.actions.len();
+
+                queue.actions.clear();
+
+                output.send(NetworkOutput {
+                    id: client.id,
+                    body: match captures.get(0).unwrap().as_str() {
+                        "stop" => "You stop what you're doing.".to_string(),
+                        _ => format!(
+                            "You clear {count} queued action{}.",
+                            if count == 1 { "" } else { "s" }
+                        ),
+                    },
+                });
+            } else if action_duration(&message.body).is_some() {
+                if queue.actions.len() >= settings.max_queued {
+                    output.send(NetworkOutput {
+                        id: client.id,
+                        body: "You can't queue up any more actions.".to_string(),
+                    });
+                } else {
+                    queue.actions.push_back(message.body.clone());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::{
+        network::events::{NetworkInput, NetworkOutput},
+        player::{
+            components::{action_queue::ActionQueue, client::NetworkClient},
+            resources::{action_settings::ActionSettings, player_index::PlayerIndex},
+            systems::index_players::index_players,
+        },
+        test::bundles::utils::{player_bundle, PlayerBundle},
+    };
+
+    #[test]
+    fn queues() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkInput>();
+        app.add_event::<NetworkOutput>();
+        app.insert_resource(ActionSettings { max_queued: 2 });
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::queue_actions);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        for body in ["n", "look", "take apple", "e"] {
+            app.world
+                .resource_mut::<Events<NetworkInput>>()
+                .send(NetworkInput {
+                    id: player_client_id,
+                    body: body.into(),
+                    internal: false,
+                });
+        }
+
+        app.update();
+
+        assert_eq!(
+            app.world.get::<ActionQueue>(player).unwrap().actions,
+            vec!["n", "take apple"]
+        );
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(output.body, "You can't queue up any more actions.");
+    }
+
+    #[test]
+    fn clear() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkInput>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<ActionSettings>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::queue_actions);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        app.world
+            .get_mut::<ActionQueue>(player)
+            .unwrap()
+            .actions
+            .extend(["n".to_string(), "s".to_string()]);
+
+        app.world
+            .resource_mut::<Events<NetworkInput>>()
+            .send(NetworkInput {
+                id: player_client_id,
+                body: "clear".into(),
+                internal: false,
+            });
+
+        app.update();
+
+        assert!(app
+            .world
+            .get::<ActionQueue>(player)
+            .unwrap()
+            .actions
+            .is_empty());
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(output.body, "You clear 2 queued actions.");
+    }
+}
@@ -0,0 +1,28 @@
+use bevy::{prelude::*, tasks::IoTaskPool};
+
+use crate::player::{
+    components::{character::Character, explored::Explored},
+    utils::api::save_explored as save,
+};
+
+/// How often, in seconds, what players have explored is saved while they're online.
+pub const EXPLORED_SAVE_INTERVAL: f64 = 60.0;
+
+/// Save what each player has [`Explored`] since the last time this ran, so a crash
+/// loses at most [`EXPLORED_SAVE_INTERVAL`] of it. Saving happens on the
+/// [`IoTaskPool`], so the world doesn't wait on the API.
+pub fn save_explored(
+    pool: Res<IoTaskPool>,
+    players: Query<(&Character, &Explored), Changed<Explored>>,
+) {
+    for (character, explored) in players.iter() {
+        let (id, name, zones) = (character.id, character.name.clone(), explored.zones());
+
+        pool.spawn(async move {
+            if !save(id, zones).map_or(false, |response| response.status().is_success()) {
+                warn!("Could not save explored map for {name}");
+            }
+        })
+        .detach();
+    }
+}
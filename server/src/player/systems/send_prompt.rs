@@ -5,6 +5,7 @@ use crate::{
     player::{
         components::{character::Character, client::NetworkClient, online::Online},
         events::prompt_event::PromptEvent,
+        utils::players::Players,
     },
     visual::palette::Palette,
     world::resources::world_time::{WorldTime, WorldTimeTag},
@@ -16,11 +17,11 @@ pub fn send_prompt(
     palette: Res<Palette>,
     mut prompts: EventReader<PromptEvent>,
     mut output: EventWriter<NetworkOutput>,
-    players: Query<(&NetworkClient, &Character), With<Online>>,
+    players: Players<(&NetworkClient, &Character), With<Online>>,
 ) {
     for event in prompts.iter() {
-        if let Some((client, character)) = players.iter().find(|(c, _)| c.id == event.0) {
-            let time = world_time.time.format("%-l:%M%P").to_string();
+        if let Some((client, character)) = players.sender(&event.0) {
+            let time = world_time.clock();
 
             let prompt = format!(
                 "{} [{}] >",
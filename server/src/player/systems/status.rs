@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::{
+    network::events::{NetworkInput, NetworkOutput},
+    player::{
+        components::{
+            buffs::Buffs,
+            client::NetworkClient,
+            online::Online,
+            vitals::{Stat, Vitals},
+        },
+        utils::players::Players,
+    },
+};
+
+/// Show a player's [`Vitals`] and whatever [`Buffs`] they have on them, with
+/// how long each has left.
+pub fn status(
+    mut input: EventReader<NetworkInput>,
+    mut output: EventWriter<NetworkOutput>,
+    players: Players<(&NetworkClient, &Vitals, &Buffs), With<Online>>,
+) {
+    lazy_static! {
+        static ref CMD: Regex = Regex::new("^(status|st)$").unwrap();
+    }
+
+    for message in input.iter() {
+        if let Some((client, vitals, buffs)) = players.sender(&message.id) {
+            if CMD.is_match(&message.body.to_lowercase()) {
+                let mut lines = [Stat::Health, Stat::Stamina]
+                    .iter()
+                    .map(|stat| {
+                        let (current, max) = vitals.get(*stat);
+
+                        format!("{}: {current}/{max}", stat.name())
+                    })
+                    .collect::<Vec<_>>();
+
+                for buff in &buffs.0 {
+                    let left = buff.timer.duration().saturating_sub(buff.timer.elapsed());
+
+                    lines.push(format!(
+                        "You are {} for another {}s.",
+                        buff.name,
+                        left.as_secs_f32().ceil()
+                    ));
+                }
+
+                output.send(NetworkOutput {
+                    id: client.id,
+                    body: lines.join("\r\n"),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::{
+        network::events::{NetworkInput, NetworkOutput},
+        player::{
+            components::{buffs::Buffs, client::NetworkClient, vitals::Vitals},
+            resources::player_index::PlayerIndex,
+            systems::index_players::index_players,
+        },
+        test::bundles::utils::{player_bundle, PlayerBundle},
+    };
+
+    #[test]
+    fn status() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkInput>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::status);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        app.world.get_mut::<Vitals>(player).unwrap().health = 80;
+        app.world
+            .get_mut::<Buffs>(player)
+            .unwrap()
+            .apply("well fed", 60.0);
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        app.world
+            .resource_mut::<Events<NetworkInput>>()
+            .send(NetworkInput {
+                id: player_client_id,
+                body: "status".into(),
+                internal: false,
+            });
+
+        app.update();
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(
+            output.body,
+            "health: 80/100\r\nstamina: 100/100\r\nYou are well fed for another 60s."
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+/// Every command that's queued and performed as an action, rather than handled
+/// straight away. Each handler matches its input with [`Action::captures`], so
+/// what gets queued is exactly what gets handled.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+    Move,
+    ToggleDoor,
+    ToggleLock,
+    Take,
+    Get,
+    Drop,
+    Put,
+    Give,
+    Wear,
+    Remove,
+    Consume,
+    Fish,
+}
+
+impl Action {
+    pub const ALL: [Action; 12] = [
+        Action::Move,
+        Action::ToggleDoor,
+        Action::ToggleLock,
+        Action::Take,
+        Action::Get,
+        Action::Drop,
+        Action::Put,
+        Action::Give,
+        Action::Wear,
+        Action::Remove,
+        Action::Consume,
+        Action::Fish,
+    ];
+
+    fn pattern(self) -> &'static str {
+        match self {
+            Action::Move => {
+                "^(north|n|northeast|ne|east|e|southeast|se|south|s|southwest|sw|west|w|northwest|nw|up|u|down|d)$"
+            }
+            Action::ToggleDoor => {
+                "^(open|close)( +door)?( +(?P<direction>north|n|northeast|ne|east|e|southeast|se|south|s|southwest|sw|west|w|northwest|nw))?$"
+            }
+            Action::ToggleLock => {
+                "^(lock|unlock|pick|pick lock)( +door)?( +(?P<direction>north|n|northeast|ne|east|e|southeast|se|south|s|southwest|sw|west|w|northwest|nw))?$"
+            }
+            Action::Take => "^(take)(( +)(.+))?$",
+            Action::Get => "^(get|take)( +(.+?)( +from +(.+))?)?$",
+            Action::Drop => "^(drop)(( +)(.+))?$",
+            Action::Put => "^put( +(.+?)( +in(to)? +(.+))?)?$",
+            Action::Give => "^give( +(.+?)( +to +(.+))?)?$",
+            Action::Wear => "^(wear|wield)( +(.+))?$",
+            Action::Remove => "^remove( +(.+))?$",
+            Action::Consume => "^(eat|drink|use)( +(.+))?$",
+            Action::Fish => "^fish$",
+        }
+    }
+
+    fn regex(self) -> &'static Regex {
+        lazy_static! {
+            static ref REGEXES: Vec<Regex> = Action::ALL
+                .iter()
+                .map(|action| Regex::new(action.pattern()).unwrap())
+                .collect();
+        }
+
+        &REGEXES[self as usize]
+    }
+
+    /// Match lowercased input against this action's command.
+    pub fn captures(self, body: &str) -> Option<Captures> {
+        self.regex().captures(body)
+    }
+
+    /// How many seconds it takes to perform `body`.
+    fn seconds(self, body: &str) -> f32 {
+        match self {
+            Action::Move => 0.5,
+            Action::ToggleLock if body.starts_with("pick") => 3.0,
+            Action::Fish => 3.0,
+            _ => 1.0,
+        }
+    }
+}
+
+/// How many seconds an action takes, or `None` if the input isn't an
+/// action and can be handled straight away.
+pub fn action_duration(body: &str) -> Option<f32> {
+    let body = body.to_lowercase();
+
+    Action::ALL
+        .iter()
+        .find(|action| action.regex().is_match(&body))
+        .map(|action| action.seconds(&body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::action_duration;
+
+    #[test]
+    fn durations() {
+        assert_eq!(action_duration("NE"), Some(0.5));
+        assert_eq!(action_duration("open door north"), Some(1.0));
+        assert_eq!(action_duration("pick lock w"), Some(3.0));
+        assert_eq!(action_duration("give apple to Ada"), Some(1.0));
+        assert_eq!(action_duration("fish"), Some(3.0));
+        assert_eq!(action_duration("look"), None);
+    }
+}
@@ -0,0 +1,53 @@
+use std::env;
+
+use api::{
+    explored::handlers::{ExploredZone, GetExploredRequest, SaveExploredRequest},
+    settings::handlers::{GetSettingsRequest, SaveSettingsRequest},
+};
+use reqwest::blocking::Response;
+
+pub fn get_explored(character_id: i32) -> reqwest::Result<Response> {
+    let api_url = env::var("API_URL").expect("Could not read API_URL from env");
+    let client = reqwest::blocking::Client::new();
+
+    client
+        .post(format!("http://{api_url}/explored"))
+        .json(&GetExploredRequest { character_id })
+        .send()
+}
+
+pub fn save_explored(character_id: i32, zones: Vec<ExploredZone>) -> reqwest::Result<Response> {
+    let api_url = env::var("API_URL").expect("Could not read API_URL from env");
+    let client = reqwest::blocking::Client::new();
+
+    client
+        .post(format!("http://{api_url}/save_explored"))
+        .json(&SaveExploredRequest {
+            character_id,
+            zones,
+        })
+        .send()
+}
+
+pub fn get_settings(character_id: i32) -> reqwest::Result<Response> {
+    let api_url = env::var("API_URL").expect("Could not read API_URL from env");
+    let client = reqwest::blocking::Client::new();
+
+    client
+        .post(format!("http://{api_url}/settings"))
+        .json(&GetSettingsRequest { character_id })
+        .send()
+}
+
+pub fn save_settings(character_id: i32, refuses_gifts: bool) -> reqwest::Result<Response> {
+    let api_url = env::var("API_URL").expect("Could not read API_URL from env");
+    let client = reqwest::blocking::Client::new();
+
+    client
+        .post(format!("http://{api_url}/save_settings"))
+        .json(&SaveSettingsRequest {
+            character_id,
+            refuses_gifts,
+        })
+        .send()
+}
@@ -0,0 +1,63 @@
+use std::ops::{Deref, DerefMut};
+
+use bevy::{
+    ecs::{
+        query::{Fetch, FilterFetch, WorldQuery},
+        system::SystemParam,
+    },
+    prelude::*,
+};
+
+use crate::{network::server::ConnectionId, player::resources::player_index::PlayerIndex};
+
+/// A query over players that can also find whoever sent a message through the
+/// [`PlayerIndex`], without scanning every player. Everything else a [`Query`]
+/// can do is still there.
+#[derive(SystemParam)]
+pub struct Players<'w, 's, Q: WorldQuery + 'static, F: WorldQuery + 'static>
+where
+    F::Fetch: FilterFetch,
+{
+    index: Res<'w, PlayerIndex>,
+    query: Query<'w, 's, Q, F>,
+}
+
+impl<'w, 's, Q: WorldQuery, F: WorldQuery> Players<'w, 's, Q, F>
+where
+    F::Fetch: FilterFetch,
+{
+    /// The player on the connection `id`, if they match the query.
+    pub fn sender(&self, id: &ConnectionId) -> Option<<Q::ReadOnlyFetch as Fetch<'_, 's>>::Item> {
+        self.index
+            .get(id)
+            .and_then(|entity| self.query.get(entity).ok())
+    }
+
+    /// The player on the connection `id`, if they match the query, to change.
+    pub fn sender_mut(&mut self, id: &ConnectionId) -> Option<<Q::Fetch as Fetch>::Item> {
+        match self.index.get(id) {
+            Some(entity) => self.query.get_mut(entity).ok(),
+            None => None,
+        }
+    }
+}
+
+impl<'w, 's, Q: WorldQuery, F: WorldQuery> Deref for Players<'w, 's, Q, F>
+where
+    F::Fetch: FilterFetch,
+{
+    type Target = Query<'w, 's, Q, F>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.query
+    }
+}
+
+impl<'w, 's, Q: WorldQuery, F: WorldQuery> DerefMut for Players<'w, 's, Q, F>
+where
+    F::Fetch: FilterFetch,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.query
+    }
+}
@@ -4,8 +4,11 @@ use regex::Regex;
 
 use crate::{
     network::events::{NetworkInput, NetworkOutput},
-    player::components::{character::Character, client::NetworkClient, online::Online},
-    spatial::components::position::Position,
+    player::{
+        components::{character::Character, client::NetworkClient, online::Online},
+        utils::players::Players,
+    },
+    spatial::components::{position::Position, zone::Zone},
     visual::palette::Palette,
 };
 
@@ -14,7 +17,7 @@ pub fn say(
     palette: Res<Palette>,
     mut input: EventReader<NetworkInput>,
     mut output: EventWriter<NetworkOutput>,
-    players: Query<(&NetworkClient, &Position, &Character), With<Online>>,
+    players: Players<(&NetworkClient, &Position, &Zone, &Character), With<Online>>,
 ) {
     lazy_static! {
         static ref CMD: Regex = Regex::new("^(say|')(( +)(.+))?$").unwrap();
@@ -22,9 +25,7 @@ pub fn say(
 
     for message in input.iter() {
         if let Some(captures) = CMD.captures(&message.body) {
-            if let Some((client, position, character)) =
-                players.iter().find(|(c, _, _)| c.id == message.id)
-            {
+            if let Some((client, position, zone, character)) = players.sender(&message.id) {
                 if let Some(phrase) = captures.get(4) {
                     output.send(NetworkOutput {
                         id: client.id,
@@ -36,8 +37,10 @@ pub fn say(
 
                     players
                         .iter()
-                        .filter(|(c, p, _)| p.0 == position.0 && c.id != client.id)
-                        .for_each(|(c, _, _)| {
+                        .filter(|(c, p, z, _)| {
+                            p.0 == position.0 && z.0 == zone.0 && c.id != client.id
+                        })
+                        .for_each(|(c, _, _, _)| {
                             output.send(NetworkOutput {
                                 id: c.id,
                                 body: format!(
@@ -65,7 +68,11 @@ mod tests {
 
     use crate::{
         network::events::{NetworkInput, NetworkOutput},
-        player::components::{character::Character, client::NetworkClient},
+        player::{
+            components::{character::Character, client::NetworkClient},
+            resources::player_index::PlayerIndex,
+            systems::index_players::index_players,
+        },
         test::bundles::utils::{player_bundle, PlayerBundle},
         visual::palette::Palette,
     };
@@ -79,6 +86,8 @@ mod tests {
         app.insert_resource(Palette::default());
         app.add_event::<NetworkInput>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::say);
 
         let sender = app
@@ -142,6 +151,8 @@ mod tests {
         app.insert_resource(Palette::default());
         app.add_event::<NetworkInput>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::say);
 
         let player = app
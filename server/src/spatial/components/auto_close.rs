@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+use bevy_proto::ProtoComponent;
+use serde::{Deserialize, Serialize};
+
+/// A [`Door`](super::door::Door) that swings shut by itself once it's been
+/// left open for `after` seconds.
+#[derive(Clone, Serialize, Deserialize, ProtoComponent, Component, Debug)]
+pub struct AutoClose {
+    pub after: f32,
+    /// Seconds the door has been open for.
+    #[serde(skip)]
+    pub elapsed: f32,
+}
@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+use bevy_proto::ProtoComponent;
+use serde::{Deserialize, Serialize};
+
+/// Moves anyone who steps on it to `position` in `zone`.
+#[derive(Clone, Serialize, Deserialize, ProtoComponent, Component, Debug)]
+pub struct Exit {
+    pub zone: String,
+    pub position: IVec2,
+}
@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+use bevy_proto::ProtoComponent;
+use serde::{Deserialize, Serialize};
+
+use crate::world::resources::world_time::WorldTimeTag;
+
+/// A [`Door`](super::door::Door) that opens when the [`WorldTimeTag`] changes to
+/// one of `open`, and closes when it changes to anything else. Players can still
+/// open and close it themselves in between.
+#[derive(Clone, Serialize, Deserialize, ProtoComponent, Component, Debug)]
+pub struct GateSchedule {
+    pub open: Vec<WorldTimeTag>,
+    /// The part of the day the gate was last opened or closed for.
+    #[serde(skip)]
+    pub applied: Option<WorldTimeTag>,
+}
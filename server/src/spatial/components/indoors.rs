@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+use bevy_proto::ProtoComponent;
+use serde::{Deserialize, Serialize};
+
+/// A [`Tile`](super::tile::Tile) under a roof, out of the sky and weather.
+#[derive(Clone, Serialize, Deserialize, ProtoComponent, Component, Debug)]
+pub struct Indoors;
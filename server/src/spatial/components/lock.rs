@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+use bevy_proto::ProtoComponent;
+use serde::{Deserialize, Serialize};
+
+/// A lock on a [`Door`](super::door::Door), opened by carrying the item
+/// spawned from the `key` prototype.
+#[derive(Clone, Serialize, Deserialize, ProtoComponent, Component, Debug)]
+pub struct Lock {
+    pub key: String,
+    pub locked: bool,
+    /// Chance, from 0 to 1, of picking the lock. It can't be picked without one.
+    #[serde(default)]
+    pub pick_chance: Option<f32>,
+}
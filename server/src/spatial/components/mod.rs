@@ -1,4 +1,14 @@
+pub mod auto_close;
 pub mod collider;
 pub mod door;
+pub mod exit;
+pub mod gate_schedule;
+pub mod indoors;
+pub mod landmark;
+pub mod lock;
+pub mod movement_cost;
 pub mod position;
+pub mod stairs;
 pub mod tile;
+pub mod travelling;
+pub mod zone;
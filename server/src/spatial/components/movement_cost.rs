@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+use bevy_proto::ProtoComponent;
+use serde::{Deserialize, Serialize};
+
+/// How much longer than usual it takes to step onto a tile,
+/// as a multiplier of a normal step.
+#[derive(Clone, Serialize, Deserialize, ProtoComponent, Component, Debug)]
+pub struct MovementCost(pub f32);
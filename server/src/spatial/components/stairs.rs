@@ -0,0 +1,11 @@
+use bevy::prelude::*;
+use bevy_proto::ProtoComponent;
+use serde::{Deserialize, Serialize};
+
+/// Stairs, ladders and anything else that lets a player move to the
+/// floor above or below.
+#[derive(Clone, Serialize, Deserialize, ProtoComponent, Component, Debug)]
+pub struct Stairs {
+    pub up: bool,
+    pub down: bool,
+}
@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// The rest of the path a player is walking along after using `travel`.
+#[derive(Component, Debug)]
+pub struct Travelling {
+    pub path: VecDeque<IVec2>,
+}
@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+use bevy_proto::ProtoComponent;
+use serde::{Deserialize, Serialize};
+
+/// The LDtk level an entity with a [`Position`] lives in. Positions are only
+/// meaningful within the same zone.
+#[derive(Clone, Serialize, Deserialize, ProtoComponent, Component, Debug, PartialEq, Eq)]
+pub struct Zone(pub String);
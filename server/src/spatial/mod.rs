@@ -1,9 +1,13 @@
 pub mod components;
 mod systems;
+pub mod utils;
 
 use bevy::prelude::*;
 
-use self::systems::{look::*, map::*, movement::*, peer::*, toggle_door::*};
+use self::systems::{
+    explore::*, look::*, map::*, movement::*, peer::*, toggle_door::*, toggle_lock::*, travel::*,
+    walk::*,
+};
 
 pub struct SpatialPlugin;
 
@@ -16,7 +20,11 @@ impl Plugin for SpatialPlugin {
                 .with_system(peer)
                 .with_system(map)
                 .with_system(movement)
-                .with_system(toggle_door),
+                .with_system(toggle_door)
+                .with_system(toggle_lock)
+                .with_system(explore)
+                .with_system(travel)
+                .with_system(walk),
         );
     }
 }
@@ -0,0 +1,85 @@
+use bevy::prelude::*;
+
+use crate::{
+    player::components::{client::NetworkClient, explored::Explored},
+    spatial::{
+        components::{position::Position, zone::Zone},
+        utils::visibility::Sight,
+    },
+    world::resources::zones::Zones,
+};
+
+/// Remember everything a player can see whenever they move.
+pub fn explore(
+    zones: Res<Zones>,
+    sight: Sight,
+    mut players: Query<
+        (&NetworkClient, &Position, &Zone, &mut Explored),
+        Or<(Changed<Position>, Changed<Zone>)>,
+    >,
+) {
+    for (client, position, zone, mut explored) in players.iter_mut() {
+        let layout = if let Some(layout) = zones.0.get(&zone.0) {
+            layout
+        } else {
+            continue;
+        };
+
+        for cell in sight.visible_from(&zone.0, position.0, client.sight_radius()) {
+            explored.insert(&zone.0, layout.size, cell);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{
+        player::components::explored::Explored,
+        test::bundles::utils::{player_bundle, PlayerBundle},
+        world::resources::{
+            weather::Weather,
+            world_time::WorldTime,
+            zones::{ZoneLayout, Zones},
+        },
+    };
+
+    #[test]
+    fn explore() {
+        let mut app = App::new();
+
+        app.insert_resource(WorldTime::default());
+        app.init_resource::<Weather>();
+        app.init_resource::<Zones>();
+        app.add_system(super::explore);
+
+        app.world.resource_mut::<Zones>().0.insert(
+            "Test".into(),
+            ZoneLayout {
+                depth: 0,
+                offset: IVec2::ZERO,
+                size: IVec2::new(20, 20),
+            },
+        );
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                x: 5,
+                y: 5,
+                ..Default::default()
+            }))
+            .insert(Explored::default())
+            .id();
+
+        app.update();
+
+        let explored = app.world.get::<Explored>(player).unwrap();
+
+        assert!(explored.contains("Test", IVec2::new(20, 20), IVec2::new(5, 5)));
+        assert!(explored.contains("Test", IVec2::new(20, 20), IVec2::new(6, 5)));
+        assert!(!explored.contains("Test", IVec2::new(20, 20), IVec2::new(19, 19)));
+    }
+}
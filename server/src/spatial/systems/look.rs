@@ -3,64 +3,191 @@ use lazy_static::lazy_static;
 use regex::Regex;
 
 use crate::{
+    items::{
+        components::{
+            backpack::Backpack, bulk::Bulk, can_take::CanTake, equipment::Equipment,
+            item_details::ItemDetails, stack::Stack, weight::Weight,
+        },
+        utils::{
+            card::card,
+            stacks::{quantity, Target},
+        },
+    },
     network::events::{NetworkInput, NetworkOutput},
-    player::components::{client::NetworkClient, online::Online},
-    spatial::components::{position::Position, tile::Tile},
+    player::{
+        components::{character::Character, client::NetworkClient, online::Online},
+        utils::players::Players,
+    },
+    spatial::components::{indoors::Indoors, position::Position, tile::Tile, zone::Zone},
     visual::components::{
         details::Details,
         sprite::{Sprite, SpritePaint},
     },
+    world::resources::world_time::WorldTime,
 };
 
 /// Send a description of the tile the player is currently on or
-/// an entity if they target one.
+/// an entity if they target one, as it looks at this time of day. Other
+/// players are described by what they're wearing and wielding, and items,
+/// whether on the ground or carried, get a full card.
 pub fn look(
+    world_time: Res<WorldTime>,
     mut input: EventReader<NetworkInput>,
     mut output: EventWriter<NetworkOutput>,
-    players: Query<(&NetworkClient, &Position), With<Online>>,
-    entities: Query<(Entity, &Position, &Details, &Sprite), Without<Tile>>,
-    tiles: Query<(&Position, &Details, &Sprite), With<Tile>>,
+    players: Players<
+        (
+            &NetworkClient,
+            &Position,
+            &Zone,
+            &Character,
+            &Sprite,
+            Option<&Backpack>,
+            Option<&Equipment>,
+        ),
+        With<Online>,
+    >,
+    entities: Query<(Entity, &Position, &Zone, &Details, &Sprite), Without<Tile>>,
+    tiles: Query<(&Position, &Zone, &Details, &Sprite, Option<&Indoors>), With<Tile>>,
+    items: Query<
+        (
+            &Details,
+            &Sprite,
+            Option<&Stack>,
+            Option<&Weight>,
+            Option<&Bulk>,
+            Option<&ItemDetails>,
+        ),
+        With<CanTake>,
+    >,
 ) {
     lazy_static! {
-        static ref CMD: Regex = Regex::new("^(look|l)(( +)(.+))?$").unwrap();
+        static ref CMD: Regex = Regex::new("^(look|l|examine|ex)(( +)(.+))?$").unwrap();
     }
 
     for message in input.iter() {
         if let Some(captures) = CMD.captures(&message.body.to_lowercase()) {
-            if let Some((client, position)) = players.iter().find(|(c, _)| c.id == message.id) {
+            if let Some((client, position, zone, _, _, backpack, equipment)) =
+                players.sender(&message.id)
+            {
+                let indoors = tiles
+                    .iter()
+                    .any(|(p, z, _, _, i)| p.0 == position.0 && z.0 == zone.0 && i.is_some());
+
                 match captures.get(4) {
+                    // Looking inside containers is handled by `look_in`.
+                    Some(target) if target.as_str().starts_with("in ") => {}
                     // Look at a specific entity by name or ID in the same tile
                     // as the player.
                     Some(name_or_id) => {
-                        match entities.iter().find(|(e, p, d, _)| {
-                            p.0 == position.0
-                                && (d.name.to_lowercase()
-                                    == name_or_id.as_str().to_lowercase().trim()
-                                    || e.id().to_string() == name_or_id.as_str())
-                        }) {
-                            Some((_, _, details, sprite)) => {
-                                output.send(NetworkOutput {
-                                    id: client.id,
-                                    body: format!(
-                                        "{} {}\r\n{}",
-                                        sprite.paint(),
-                                        details.name,
-                                        details.description
-                                    ),
-                                });
-                            }
-                            None => {
-                                output.send(NetworkOutput {
-                                    id: client.id,
-                                    body: "You don't see that here.".into(),
-                                });
-                            }
-                        }
+                        let name = name_or_id.as_str().to_lowercase();
+
+                        let card_of = |entity| {
+                            items.get(entity).ok().map(
+                                |(details, sprite, stack, weight, bulk, extra)| {
+                                    card(
+                                        sprite,
+                                        &details.name,
+                                        details.describe(world_time.part, indoors),
+                                        quantity(stack),
+                                        weight,
+                                        bulk,
+                                        extra,
+                                    )
+                                },
+                            )
+                        };
+
+                        // What's on the ground here, then what the player has
+                        // on them, so `look 2.apple` counts through both.
+                        let here = entities
+                            .iter()
+                            .filter(|(_, p, z, _, _)| p.0 == position.0 && z.0 == zone.0)
+                            .map(|(entity, _, _, details, _)| {
+                                let stack = items
+                                    .get(entity)
+                                    .ok()
+                                    .and_then(|(_, _, stack, _, _, _)| stack);
+
+                                (entity, details.name.as_str(), quantity(stack))
+                            });
+
+                        let carried = backpack
+                            .map_or(&[][..], |backpack| backpack.0.as_slice())
+                            .iter()
+                            .copied()
+                            .chain(equipment.map_or(Vec::new(), |equipment| {
+                                equipment.items().into_iter().map(|(_, e)| e).collect()
+                            }))
+                            .filter_map(|entity| {
+                                items.get(entity).ok().map(|(details, _, stack, _, _, _)| {
+                                    (entity, details.name.as_str(), quantity(stack))
+                                })
+                            })
+                            .collect::<Vec<_>>();
+
+                        let body = Target::parse(&name)
+                            .pick(here.chain(carried))
+                            .first()
+                            .and_then(|(entity, _, _)| {
+                                card_of(*entity).or_else(|| {
+                                    entities.get(*entity).ok().map(|(_, _, _, details, sprite)| {
+                                        format!(
+                                            "{} {}\r\n{}",
+                                            sprite.paint(),
+                                            details.name,
+                                            details.describe(world_time.part, indoors)
+                                        )
+                                    })
+                                })
+                            })
+                            .or_else(|| {
+                                players
+                                    .iter()
+                                    .find(|(_, p, z, c, _, _, _)| {
+                                        p.0 == position.0
+                                            && z.0 == zone.0
+                                            && c.name.to_lowercase() == name.trim()
+                                    })
+                                    .map(|(_, _, _, character, sprite, _, equipment)| {
+                                        let lines = equipment.map_or(Vec::new(), |equipment| {
+                                            equipment.describe(|entity| {
+                                                items
+                                                    .get(entity)
+                                                    .ok()
+                                                    .map(|(details, sprite, _, _, _, _)| {
+                                                        (details, sprite)
+                                                    })
+                                            })
+                                        });
+
+                                        if lines.is_empty() {
+                                            format!(
+                                                "{} {}\r\nThey aren't wearing or wielding anything.",
+                                                sprite.paint(),
+                                                character.name
+                                            )
+                                        } else {
+                                            format!(
+                                                "{} {}\r\nThey have on:\r\n{}",
+                                                sprite.paint(),
+                                                character.name,
+                                                lines.join("\r\n")
+                                            )
+                                        }
+                                    })
+                            })
+                            .unwrap_or_else(|| "You don't see that here.".into());
+
+                        output.send(NetworkOutput {
+                            id: client.id,
+                            body,
+                        });
                     }
                     // If none provided, look at tile.
                     None => {
-                        if let Some((_, details, sprite)) =
-                            tiles.iter().find(|(p, _, _)| p.0 == position.0)
+                        if let Some((_, _, details, sprite, _)) = tiles
+                            .iter()
+                            .find(|(p, z, _, _, _)| p.0 == position.0 && z.0 == zone.0)
                         {
                             output.send(NetworkOutput {
                                 id: client.id,
@@ -68,7 +195,7 @@ pub fn look(
                                     "{} {}\r\n{}",
                                     sprite.paint(),
                                     details.name,
-                                    details.description
+                                    details.describe(world_time.part, indoors)
                                 ),
                             });
                         }
@@ -85,13 +212,24 @@ mod tests {
     use yansi::Paint;
 
     use crate::{
+        items::components::{
+            equipment::Equipment,
+            equippable::Slot,
+            item_details::{ItemDetails, Quality},
+            weight::Weight,
+        },
         network::events::{NetworkInput, NetworkOutput},
-        player::components::client::NetworkClient,
+        player::{
+            components::client::NetworkClient, resources::player_index::PlayerIndex,
+            systems::index_players::index_players,
+        },
+        spatial::components::indoors::Indoors,
         test::bundles::utils::{
-            closed_door_bundle, open_door_bundle, player_bundle, tile_bundle, DoorBundle,
-            PlayerBundle, TileBundle,
+            closed_door_bundle, item_bundle, item_in_backpack_bundle, open_door_bundle,
+            player_bundle, tile_bundle, DoorBundle, ItemBundle, PlayerBundle, TileBundle,
         },
         visual::components::{details::Details, sprite::Sprite},
+        world::resources::world_time::{WorldTime, WorldTimeTag},
     };
 
     #[test]
@@ -100,8 +238,11 @@ mod tests {
 
         let mut app = App::new();
 
+        app.insert_resource(WorldTime::default());
         app.add_event::<NetworkInput>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::look);
 
         let player = app
@@ -144,8 +285,11 @@ mod tests {
 
         let mut app = App::new();
 
+        app.insert_resource(WorldTime::default());
         app.add_event::<NetworkInput>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::look);
 
         let player = app
@@ -204,8 +348,11 @@ mod tests {
 
         let mut app = App::new();
 
+        app.insert_resource(WorldTime::default());
         app.add_event::<NetworkInput>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::look);
 
         let player = app
@@ -257,12 +404,210 @@ mod tests {
         );
     }
 
+    #[test]
+    fn at_player() {
+        Paint::disable();
+
+        let mut app = App::new();
+
+        app.insert_resource(WorldTime::default());
+        app.add_event::<NetworkInput>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::look);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        let cap = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Cap".into(),
+                character: "^".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        let other = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                name: "Bob".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        app.world
+            .get_mut::<Equipment>(other)
+            .unwrap()
+            .0
+            .insert(Slot::Head, cap);
+
+        app.world.spawn().insert_bundle(tile_bundle(TileBundle {
+            ..Default::default()
+        }));
+
+        app.world
+            .resource_mut::<Events<NetworkInput>>()
+            .send(NetworkInput {
+                id: player_client_id,
+                body: "look bob".into(),
+                internal: false,
+            });
+
+        app.update();
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(output.id, player_client_id);
+        assert_eq!(output.body, "@ Bob\r\nThey have on:\r\nhead: ^ Cap");
+    }
+
+    #[test]
+    fn at_ground_item_by_ordinal() {
+        Paint::disable();
+
+        let mut app = App::new();
+
+        app.insert_resource(WorldTime::default());
+        app.add_event::<NetworkInput>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::look);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        app.world.spawn().insert_bundle(tile_bundle(TileBundle {
+            ..Default::default()
+        }));
+
+        for description in ["A green one.", "A red one."] {
+            app.world.spawn().insert_bundle(item_bundle(ItemBundle {
+                name: "Apple".into(),
+                description: description.into(),
+                ..Default::default()
+            }));
+        }
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        for body in ["look 2.apple", "look apples"] {
+            app.world
+                .resource_mut::<Events<NetworkInput>>()
+                .send(NetworkInput {
+                    id: player_client_id,
+                    body: body.into(),
+                    internal: false,
+                });
+
+            app.update();
+        }
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let outputs = output_reader
+            .iter(output_events)
+            .map(|output| output.body.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(outputs.len(), 2);
+        assert!(outputs[0].contains("A red one."));
+        assert!(outputs[1].contains("A green one."));
+    }
+
+    #[test]
+    fn at_carried_item() {
+        Paint::disable();
+
+        let mut app = App::new();
+
+        app.insert_resource(WorldTime::default());
+        app.add_event::<NetworkInput>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::look);
+
+        let apples = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Apple".into(),
+                description: "A delicious fruit.".into(),
+                character: "o".into(),
+                quantity: 3,
+                ..Default::default()
+            }))
+            .insert(Weight(0.2))
+            .insert(ItemDetails {
+                quality: Some(Quality::Fine),
+                ..Default::default()
+            })
+            .id();
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                items: vec![apples],
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        app.world.spawn().insert_bundle(tile_bundle(TileBundle {
+            ..Default::default()
+        }));
+
+        app.world
+            .resource_mut::<Events<NetworkInput>>()
+            .send(NetworkInput {
+                id: player_client_id,
+                body: "examine apples".into(),
+                internal: false,
+            });
+
+        app.update();
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(output.id, player_client_id);
+        assert_eq!(
+            output.body,
+            "o 3 Apples\r\nA delicious fruit.\r\nWeight: 0.6 kg (0.2 kg each)\r\nQuality: fine"
+        );
+    }
+
     #[test]
     fn entity_not_found() {
         let mut app = App::new();
 
+        app.insert_resource(WorldTime::default());
         app.add_event::<NetworkInput>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::look);
 
         let player = app
@@ -296,4 +641,86 @@ mod tests {
         assert_eq!(output.id, player_client_id);
         assert_eq!(output.body, "You don't see that here.".to_string());
     }
+
+    #[test]
+    fn time_of_day() {
+        Paint::disable();
+
+        let mut app = App::new();
+
+        app.insert_resource(WorldTime {
+            part: WorldTimeTag::Night,
+            ..Default::default()
+        });
+        app.add_event::<NetworkInput>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::look);
+
+        let outside = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let outside_client_id = app.world.get::<NetworkClient>(outside).unwrap().id;
+
+        let inside = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                x: 1,
+                ..Default::default()
+            }))
+            .id();
+
+        let inside_client_id = app.world.get::<NetworkClient>(inside).unwrap().id;
+
+        for (x, indoors) in [(0, false), (1, true)] {
+            let mut tile = app.world.spawn();
+
+            tile.insert_bundle(tile_bundle(TileBundle {
+                name: "Garden".into(),
+                description: "Flowers sway in the breeze.".into(),
+                x,
+                ..Default::default()
+            }));
+
+            tile.get_mut::<Details>().unwrap().times.insert(
+                WorldTimeTag::Night,
+                "Fireflies drift between the flowers.".into(),
+            );
+
+            if indoors {
+                tile.insert(Indoors);
+            }
+        }
+
+        for id in [outside_client_id, inside_client_id] {
+            app.world
+                .resource_mut::<Events<NetworkInput>>()
+                .send(NetworkInput {
+                    id,
+                    body: "look".into(),
+                    internal: false,
+                });
+        }
+
+        app.update();
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let outputs = output_reader.iter(output_events).collect::<Vec<_>>();
+
+        assert_eq!(outputs[0].id, outside_client_id);
+        assert_eq!(
+            outputs[0].body,
+            ". Garden\r\nFireflies drift between the flowers."
+        );
+        assert_eq!(outputs[1].id, inside_client_id);
+        assert_eq!(outputs[1].body, ". Garden\r\nFlowers sway in the breeze.");
+    }
 }
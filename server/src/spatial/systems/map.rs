@@ -1,25 +1,57 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashSet};
 use lazy_static::lazy_static;
 use regex::Regex;
 use yansi::Paint;
 
 use crate::{
     network::events::{NetworkInput, NetworkOutput},
-    player::components::{client::NetworkClient, online::Online},
-    spatial::components::position::Position,
+    player::{
+        components::{
+            client::{NetworkClient, MAP_HEIGHT},
+            explored::Explored,
+            online::Online,
+        },
+        utils::players::Players,
+    },
+    spatial::{
+        components::{indoors::Indoors, position::Position, stairs::Stairs, zone::Zone},
+        utils::visibility::Sight,
+    },
     visual::{
-        components::sprite::{Sprite, SpritePaint},
-        palette::{hex_to_rgb, rgb_to_color, Palette},
+        components::{
+            luminous::Luminous,
+            sprite::{Sprite, SpritePaint},
+        },
+        palette::{blend, hex_to_rgb, rgb_to_color, Palette},
     },
+    world::resources::zones::Zones,
 };
 
-/// Handles the `map` command.
+/// Handles the `map` command, showing the current floor and where any
+/// [`Stairs`] in view lead.
+///
+/// Only what the player has in [`Sight`] is drawn as it is. Cells the player has
+/// [`Explored`] before are drawn muted, and everything else is left blank.
+/// Outdoors, colors shift with the time of day, except on anything [`Luminous`],
+/// and the background is tinted by the weather.
 pub fn map(
     palette: Res<Palette>,
+    zones: Res<Zones>,
+    sight: Sight,
     mut input: EventReader<NetworkInput>,
     mut output: EventWriter<NetworkOutput>,
-    players: Query<(&NetworkClient, &Position, &Sprite), With<Online>>,
-    sprites: Query<(&Position, &Sprite), Without<NetworkClient>>,
+    players: Players<(&NetworkClient, &Position, &Zone, &Sprite, Option<&Explored>), With<Online>>,
+    sprites: Query<
+        (
+            &Position,
+            &Zone,
+            &Sprite,
+            Option<&Luminous>,
+            Option<&Stairs>,
+            Option<&Indoors>,
+        ),
+        Without<NetworkClient>,
+    >,
 ) {
     lazy_static! {
         static ref CMD: Regex = Regex::new("^(map|m)$").unwrap();
@@ -27,11 +59,11 @@ pub fn map(
 
     for message in input.iter() {
         if CMD.is_match(&message.body.to_lowercase()) {
-            if let Some((client, position, player_sprite)) =
-                players.iter().find(|(c, _, _)| c.id == message.id)
+            if let Some((client, position, zone, player_sprite, explored)) =
+                players.sender(&message.id)
             {
                 let map_width = client.width;
-                let map_height = 16;
+                let map_height = MAP_HEIGHT;
 
                 let mut map = vec![
                     vec![Paint::new(" ").bg(palette.slate[9]); map_width as usize];
@@ -43,29 +75,96 @@ pub fn map(
                 let start_y = position.0.y - (map_height as i32 / 2);
                 let end_y = position.0.y + (map_height as i32 / 2);
 
+                let in_zone = sprites
+                    .iter()
+                    .filter(|(_, z, _, _, _, _)| z.0 == zone.0)
+                    .collect::<Vec<_>>();
+
+                let stairs = in_zone
+                    .iter()
+                    .filter_map(|&(p, _, sprite, _, s, _)| s.map(|s| (p, s, sprite)))
+                    .collect::<Vec<_>>();
+
+                let indoors = in_zone
+                    .iter()
+                    .filter(|(_, _, _, _, _, i)| i.is_some())
+                    .map(|(p, _, _, _, _, _)| p.0)
+                    .collect::<HashSet<_>>();
+
+                let sprites = in_zone
+                    .iter()
+                    .map(|&(p, _, s, l, _, _)| (p, s, l.is_some()))
+                    .collect::<Vec<_>>();
+
+                let visible = sight.visible_from(&zone.0, position.0, client.sight_radius());
+
+                let layout = zones.0.get(&zone.0);
+
+                let tint = sight.weather.get(&zone.0).tint(&palette);
+
                 for x in start_x..=end_x {
                     for y in start_y..=end_y {
+                        if !visible.contains(&IVec2::new(x, y)) {
+                            let remembered =
+                                explored.zip(layout).map_or(false, |(explored, layout)| {
+                                    explored.contains(&zone.0, layout.size, IVec2::new(x, y))
+                                });
+
+                            if let Some(sprite) = sprites
+                                .iter()
+                                .filter(|s| remembered && s.0 .0 == IVec2::new(x, y))
+                                .last()
+                            {
+                                map[(y - start_y - 1).clamp(0, map_height) as usize]
+                                    [(x - start_x - 1).clamp(0, map_width) as usize] =
+                                    Paint::new(sprite.1.character.as_str())
+                                        .fg(palette.slate[7])
+                                        .bg(palette.slate[9]);
+                            }
+
+                            continue;
+                        }
+
                         // We'll use the background color of the first entity on this
                         // space (usually the tile) in case the one below doesn't have one.
                         let first = sprites.iter().find(|s| s.0 .0 == IVec2::new(x, y));
 
+                        let outdoors = !indoors.contains(&IVec2::new(x, y));
+
                         // Since we're creating the entities layer-by-layer,
                         // the last one at a given position is which should be rendered.
                         if let Some(sprite) =
                             sprites.iter().filter(|s| s.0 .0 == IVec2::new(x, y)).last()
                         {
+                            let shift = |rgb| {
+                                if outdoors {
+                                    sight.world_time.part.shift(rgb)
+                                } else {
+                                    rgb
+                                }
+                            };
+
                             let mut sprite = if sprite.0 .0 == position.0 {
-                                player_sprite.paint()
-                            } else {
+                                player_sprite.paint_with(shift)
+                            } else if sprite.2 {
                                 sprite.1.paint()
+                            } else {
+                                sprite.1.paint_with(shift)
                             };
 
-                            if let Some(background) = &player_sprite.background {
-                                sprite = sprite.bg(rgb_to_color(hex_to_rgb(background)));
-                            } else if let Some((_, s)) = first {
-                                if let Some(background) = &s.background {
-                                    sprite = sprite.bg(rgb_to_color(hex_to_rgb(background)));
+                            let background = player_sprite
+                                .background
+                                .as_ref()
+                                .or_else(|| first.and_then(|(_, s, _)| s.background.as_ref()));
+
+                            if let Some(background) = background {
+                                let mut color = rgb_to_color(shift(hex_to_rgb(background)));
+
+                                if let Some((tint, amount)) = tint.filter(|_| outdoors) {
+                                    color = blend(color, tint, amount);
                                 }
+
+                                sprite = sprite.bg(color);
                             }
 
                             map[(y - start_y - 1).clamp(0, map_height) as usize]
@@ -85,9 +184,36 @@ pub fn map(
                     .collect::<Vec<String>>()
                     .join("\n");
 
+                let floor = layout.map_or(0, |layout| layout.depth);
+
+                let mut legend = Vec::new();
+
+                for (p, s, sprite) in stairs.iter().filter(|(p, _, _)| visible.contains(&p.0)) {
+                    for (allowed, direction, floors) in [(s.up, "up", 1), (s.down, "down", -1)] {
+                        if !allowed {
+                            continue;
+                        }
+
+                        let destination = zones
+                            .stacked(&zone.0, p.0, floors)
+                            .map_or("nowhere".to_string(), |(name, _)| name);
+
+                        legend.push(format!(
+                            "{} leads {direction} to {destination}",
+                            sprite.paint()
+                        ));
+                    }
+                }
+
+                let mut body = format!("{} (floor {floor})\r\n{display}\r\n", zone.0);
+
+                if !legend.is_empty() {
+                    body.push_str(&format!("{}\r\n", legend.join("\r\n")));
+                }
+
                 output.send(NetworkOutput {
                     id: client.id,
-                    body: format!("{}\r\n", display),
+                    body,
                 });
             }
         }
@@ -101,9 +227,19 @@ mod tests {
 
     use crate::{
         network::events::{NetworkInput, NetworkOutput},
-        player::components::client::NetworkClient,
+        player::components::explored::Explored,
+        player::{
+            components::client::NetworkClient, resources::player_index::PlayerIndex,
+            systems::index_players::index_players,
+        },
+        spatial::components::collider::Collider,
         test::bundles::utils::{player_bundle, tile_bundle, PlayerBundle, TileBundle},
         visual::palette::Palette,
+        world::resources::{
+            weather::Weather,
+            world_time::WorldTime,
+            zones::{ZoneLayout, Zones},
+        },
     };
 
     #[test]
@@ -113,8 +249,13 @@ mod tests {
         let mut app = App::new();
 
         app.insert_resource(Palette::default());
+        app.insert_resource(WorldTime::default());
+        app.init_resource::<Weather>();
+        app.init_resource::<Zones>();
         app.add_event::<NetworkInput>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::map);
 
         let player = app
@@ -159,4 +300,148 @@ mod tests {
         assert_eq!(output.body.matches(".").count(), 2);
         assert_eq!(output.body.matches("@").count(), 1);
     }
+
+    #[test]
+    fn walls_block_sight() {
+        Paint::disable();
+
+        let mut app = App::new();
+
+        app.insert_resource(Palette::default());
+        app.insert_resource(WorldTime::default());
+        app.init_resource::<Weather>();
+        app.init_resource::<Zones>();
+        app.add_event::<NetworkInput>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::map);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        app.world.spawn().insert_bundle(tile_bundle(TileBundle {
+            ..Default::default()
+        }));
+
+        app.world
+            .spawn()
+            .insert_bundle(tile_bundle(TileBundle {
+                character: "#".into(),
+                x: 1,
+                ..Default::default()
+            }))
+            .insert(Collider);
+
+        app.world.spawn().insert_bundle(tile_bundle(TileBundle {
+            x: 2,
+            ..Default::default()
+        }));
+
+        app.world
+            .resource_mut::<Events<NetworkInput>>()
+            .send(NetworkInput {
+                id: player_client_id,
+                body: "map".into(),
+                internal: false,
+            });
+
+        app.update();
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(output.body.matches("#").count(), 1);
+        assert_eq!(output.body.matches(".").count(), 0);
+    }
+
+    #[test]
+    fn remembered() {
+        Paint::disable();
+
+        let mut app = App::new();
+
+        app.insert_resource(Palette::default());
+        app.insert_resource(WorldTime::default());
+        app.init_resource::<Weather>();
+        app.init_resource::<Zones>();
+        app.add_event::<NetworkInput>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::map);
+
+        let size = IVec2::new(4, 4);
+
+        app.world.resource_mut::<Zones>().0.insert(
+            "Test".into(),
+            ZoneLayout {
+                depth: 0,
+                offset: IVec2::ZERO,
+                size,
+            },
+        );
+
+        let mut explored = Explored::default();
+
+        explored.insert("Test", size, IVec2::new(2, 0));
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .insert(explored)
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        app.world.spawn().insert_bundle(tile_bundle(TileBundle {
+            ..Default::default()
+        }));
+
+        app.world
+            .spawn()
+            .insert_bundle(tile_bundle(TileBundle {
+                character: "#".into(),
+                x: 1,
+                ..Default::default()
+            }))
+            .insert(Collider);
+
+        app.world.spawn().insert_bundle(tile_bundle(TileBundle {
+            x: 2,
+            ..Default::default()
+        }));
+
+        app.world.spawn().insert_bundle(tile_bundle(TileBundle {
+            x: 3,
+            ..Default::default()
+        }));
+
+        app.world
+            .resource_mut::<Events<NetworkInput>>()
+            .send(NetworkInput {
+                id: player_client_id,
+                body: "map".into(),
+                internal: false,
+            });
+
+        app.update();
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(output.body.matches(".").count(), 1);
+    }
 }
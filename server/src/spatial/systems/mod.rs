@@ -1,5 +1,9 @@
+pub mod explore;
 pub mod look;
 pub mod map;
 pub mod movement;
 pub mod peer;
 pub mod toggle_door;
+pub mod toggle_lock;
+pub mod travel;
+pub mod walk;
@@ -1,65 +1,103 @@
 use bevy::prelude::*;
-use lazy_static::lazy_static;
-use regex::Regex;
 
 use crate::{
+    items::components::encumbrance::Encumbrance,
     network::{
         events::{NetworkInput, NetworkOutput},
         server::ConnectionId,
     },
-    player::components::{client::NetworkClient, online::Online},
-    spatial::components::{collider::Collider, position::Position, tile::Tile},
+    player::{
+        components::{action_queue::ActionQueue, client::NetworkClient, online::Online},
+        events::action_event::ActionEvent,
+        utils::{actions::Action, players::Players},
+    },
+    spatial::{
+        components::{position::Position, zone::Zone},
+        utils::{direction::direction_offset, terrain::Terrain},
+    },
+    world::resources::zones::Zones,
 };
 
-/// Handles movement commands.
+/// Handles movement commands, sending players through any exit they step on
+/// and up or down any stairs they're standing on, as laid out by the [`Terrain`].
+/// Stepping onto a tile with a movement cost makes the player wait that much longer
+/// before their next action, as does their [`Encumbrance`].
 pub fn movement(
-    mut input: ParamSet<(EventReader<NetworkInput>, EventWriter<NetworkInput>)>,
+    mut actions: EventReader<ActionEvent>,
+    mut input: EventWriter<NetworkInput>,
     mut output: EventWriter<NetworkOutput>,
-    mut players: Query<(&NetworkClient, &mut Position), With<Online>>,
-    tiles: Query<&Position, (With<Tile>, Without<NetworkClient>)>,
-    colliders: Query<&Position, (With<Collider>, Without<NetworkClient>)>,
+    zones: Res<Zones>,
+    mut players: Players<
+        (
+            &NetworkClient,
+            &mut Position,
+            &mut Zone,
+            Option<&mut ActionQueue>,
+            Option<&Encumbrance>,
+        ),
+        With<Online>,
+    >,
+    terrain: Terrain,
 ) {
-    lazy_static! {
-        static ref CMD: Regex = Regex::new(
-            "^(north|n|northeast|ne|east|e|southeast|se|south|s|southwest|sw|west|w|northwest|nw)$"
-        )
-        .unwrap();
-    }
-
     let mut moved: Vec<ConnectionId> = Vec::new();
 
-    for message in input.p0().iter() {
-        if let Some(captures) = CMD.captures(&message.body.to_lowercase()) {
-            if let Some((client, mut position)) = players.iter_mut().find(|p| p.0.id == message.id)
+    for message in actions.iter() {
+        if let Some(captures) = Action::Move.captures(&message.body.to_lowercase()) {
+            if let Some((client, mut position, mut zone, queue, encumbrance)) =
+                players.sender_mut(&message.id)
             {
-                let wanted_tile = match captures.get(0).unwrap().as_str() {
-                    "north" | "n" => tiles.iter().find(|p| p.0 == position.0 + IVec2::new(0, -1)),
-                    "northeast" | "ne" => {
-                        tiles.iter().find(|p| p.0 == position.0 + IVec2::new(1, -1))
-                    }
-                    "east" | "e" => tiles.iter().find(|p| p.0 == position.0 + IVec2::new(1, 0)),
-                    "southeast" | "se" => {
-                        tiles.iter().find(|p| p.0 == position.0 + IVec2::new(1, 1))
-                    }
-                    "south" | "s" => tiles.iter().find(|p| p.0 == position.0 + IVec2::new(0, 1)),
-                    "southwest" | "sw" => {
-                        tiles.iter().find(|p| p.0 == position.0 + IVec2::new(-1, 1))
+                let command = captures.get(0).unwrap().as_str();
+
+                // Work out which zone and position the player is trying to get to.
+                let wanted = match command {
+                    "up" | "u" | "down" | "d" => {
+                        let up = matches!(command, "up" | "u");
+
+                        if !terrain.stairs(&zone.0, position.0, up) {
+                            output.send(NetworkOutput {
+                                id: client.id,
+                                body: format!(
+                                    "There's no way {} here.",
+                                    if up { "up" } else { "down" }
+                                ),
+                            });
+
+                            continue;
+                        }
+
+                        zones.stacked(&zone.0, position.0, if up { 1 } else { -1 })
                     }
-                    "west" | "w" => tiles.iter().find(|p| p.0 == position.0 + IVec2::new(-1, 0)),
-                    "northwest" | "nw" => tiles
-                        .iter()
-                        .find(|p| p.0 == position.0 + IVec2::new(-1, -1)),
-                    _ => None,
+                    _ => direction_offset(command)
+                        .map(|direction| (zone.0.clone(), position.0 + direction)),
                 };
 
-                if let Some(tile) = wanted_tile {
-                    if colliders.iter().any(|c| c.0 == tile.0) {
+                let wanted = wanted.and_then(|(wanted_zone, wanted_position)| {
+                    terrain
+                        .movement_cost(&wanted_zone, wanted_position)
+                        .map(|cost| (wanted_zone, wanted_position, cost))
+                });
+
+                if let Some((wanted_zone, wanted_position, cost)) = wanted {
+                    if terrain.blocked(&wanted_zone, wanted_position) {
                         output.send(NetworkOutput {
                             id: client.id,
                             body: "Something blocks your way.".to_string(),
                         });
                     } else {
-                        position.0 = tile.0;
+                        position.0 = wanted_position;
+                        zone.0 = wanted_zone;
+
+                        if let Some(mut queue) = queue {
+                            let slowdown = cost * encumbrance.map_or(1.0, Encumbrance::slowdown);
+                            let duration = queue.cooldown.duration().mul_f32(slowdown);
+
+                            queue.cooldown.set_duration(duration);
+                        }
+
+                        if let Some(exit) = terrain.exit(&zone.0, position.0) {
+                            position.0 = exit.position;
+                            zone.0 = exit.zone.clone();
+                        }
 
                         moved.push(client.id);
                     }
@@ -74,7 +112,7 @@ pub fn movement(
     }
 
     for id in moved {
-        input.p1().send({
+        input.send({
             NetworkInput {
                 id,
                 body: "look".to_string(),
@@ -86,13 +124,22 @@ pub fn movement(
 
 #[cfg(test)]
 mod tests {
-    use bevy::{ecs::event::Events, prelude::*};
+    use bevy::{ecs::event::Events, prelude::*, utils::Duration};
 
     use crate::{
+        items::components::encumbrance::Encumbrance,
         network::events::{NetworkInput, NetworkOutput},
-        player::components::client::NetworkClient,
-        spatial::components::{collider::Collider, position::Position},
+        player::{
+            components::{action_queue::ActionQueue, client::NetworkClient},
+            events::action_event::ActionEvent,
+            resources::player_index::PlayerIndex,
+            systems::index_players::index_players,
+        },
+        spatial::components::{
+            collider::Collider, exit::Exit, position::Position, stairs::Stairs, zone::Zone,
+        },
         test::bundles::utils::{player_bundle, tile_bundle, PlayerBundle, TileBundle},
+        world::resources::zones::{ZoneLayout, Zones},
     };
 
     #[test]
@@ -100,7 +147,11 @@ mod tests {
         let mut app = App::new();
 
         app.add_event::<NetworkInput>();
+        app.add_event::<ActionEvent>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.init_resource::<Zones>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::movement);
 
         let player = app
@@ -123,11 +174,10 @@ mod tests {
         }));
 
         app.world
-            .resource_mut::<Events<NetworkInput>>()
-            .send(NetworkInput {
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
                 id: player_client_id,
                 body: "south".into(),
-                internal: false,
             });
 
         app.update();
@@ -138,12 +188,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn encumbered() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkInput>();
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.init_resource::<Zones>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::movement);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .insert(Encumbrance::Strained)
+            .id();
+
+        app.world.get_mut::<ActionQueue>(player).unwrap().cooldown =
+            Timer::from_seconds(1.0, false);
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        app.world.spawn().insert_bundle(tile_bundle(TileBundle {
+            ..Default::default()
+        }));
+
+        app.world.spawn().insert_bundle(tile_bundle(TileBundle {
+            y: 1,
+            ..Default::default()
+        }));
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: "south".into(),
+            });
+
+        app.update();
+
+        assert_eq!(
+            app.world
+                .get::<ActionQueue>(player)
+                .unwrap()
+                .cooldown
+                .duration(),
+            Duration::from_secs(2)
+        );
+    }
+
     #[test]
     fn blocked() {
         let mut app = App::new();
 
         app.add_event::<NetworkInput>();
+        app.add_event::<ActionEvent>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.init_resource::<Zones>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::movement);
 
         let player = app
@@ -169,11 +277,10 @@ mod tests {
             .insert(Collider);
 
         app.world
-            .resource_mut::<Events<NetworkInput>>()
-            .send(NetworkInput {
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
                 id: player_client_id,
                 body: "south".into(),
-                internal: false,
             });
 
         app.update();
@@ -196,7 +303,11 @@ mod tests {
         let mut app = App::new();
 
         app.add_event::<NetworkInput>();
+        app.add_event::<ActionEvent>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.init_resource::<Zones>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::movement);
 
         let player = app
@@ -214,11 +325,10 @@ mod tests {
         }));
 
         app.world
-            .resource_mut::<Events<NetworkInput>>()
-            .send(NetworkInput {
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
                 id: player_client_id,
                 body: "south".into(),
-                internal: false,
             });
 
         app.update();
@@ -236,12 +346,193 @@ mod tests {
         assert_eq!(output.body, "You can't go that direction.");
     }
 
+    #[test]
+    fn through_exit() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkInput>();
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.init_resource::<Zones>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::movement);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        app.world.spawn().insert_bundle(tile_bundle(TileBundle {
+            ..Default::default()
+        }));
+
+        app.world.spawn().insert_bundle(tile_bundle(TileBundle {
+            y: 1,
+            ..Default::default()
+        }));
+
+        app.world.spawn().insert_bundle((
+            Exit {
+                zone: "Cellar".into(),
+                position: IVec2::new(4, 2),
+            },
+            Position(IVec2::new(0, 1)),
+            Zone("Test".into()),
+        ));
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: "south".into(),
+            });
+
+        app.update();
+
+        assert_eq!(
+            app.world.get::<Position>(player).unwrap().0,
+            IVec2::new(4, 2)
+        );
+        assert_eq!(app.world.get::<Zone>(player).unwrap().0, "Cellar");
+    }
+
+    #[test]
+    fn up_stairs() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkInput>();
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.init_resource::<Zones>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::movement);
+
+        let mut zones = app.world.resource_mut::<Zones>();
+
+        zones.0.insert(
+            "Test".into(),
+            ZoneLayout {
+                depth: 0,
+                offset: IVec2::new(0, 0),
+                size: IVec2::new(4, 4),
+            },
+        );
+
+        zones.0.insert(
+            "Attic".into(),
+            ZoneLayout {
+                depth: 1,
+                offset: IVec2::new(0, 0),
+                size: IVec2::new(4, 4),
+            },
+        );
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        app.world.spawn().insert_bundle(tile_bundle(TileBundle {
+            ..Default::default()
+        }));
+
+        app.world.spawn().insert_bundle(tile_bundle(TileBundle {
+            zone: "Attic".into(),
+            ..Default::default()
+        }));
+
+        app.world.spawn().insert_bundle((
+            Stairs {
+                up: true,
+                down: false,
+            },
+            Position(IVec2::new(0, 0)),
+            Zone("Test".into()),
+        ));
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: "up".into(),
+            });
+
+        app.update();
+
+        assert_eq!(
+            app.world.get::<Position>(player).unwrap().0,
+            IVec2::new(0, 0)
+        );
+        assert_eq!(app.world.get::<Zone>(player).unwrap().0, "Attic");
+    }
+
+    #[test]
+    fn no_stairs() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkInput>();
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.init_resource::<Zones>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::movement);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        app.world.spawn().insert_bundle(tile_bundle(TileBundle {
+            ..Default::default()
+        }));
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: "d".into(),
+            });
+
+        app.update();
+
+        assert_eq!(app.world.get::<Zone>(player).unwrap().0, "Test");
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(output.id, player_client_id);
+        assert_eq!(output.body, "There's no way down here.");
+    }
+
     #[test]
     fn look_sent_after() {
         let mut app = App::new();
 
         app.add_event::<NetworkInput>();
+        app.add_event::<ActionEvent>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.init_resource::<Zones>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::movement);
 
         let player = app
@@ -264,11 +555,10 @@ mod tests {
         }));
 
         app.world
-            .resource_mut::<Events<NetworkInput>>()
-            .send(NetworkInput {
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
                 id: player_client_id,
                 body: "south".into(),
-                internal: false,
             });
 
         app.update();
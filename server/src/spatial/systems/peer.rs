@@ -4,8 +4,11 @@ use regex::Regex;
 
 use crate::{
     network::events::{NetworkInput, NetworkOutput},
-    player::components::{character::Character, client::NetworkClient, online::Online},
-    spatial::components::{position::Position, tile::Tile},
+    player::{
+        components::{character::Character, client::NetworkClient, online::Online},
+        utils::players::Players,
+    },
+    spatial::components::{position::Position, tile::Tile, zone::Zone},
     visual::{components::details::Details, palette::Palette},
 };
 
@@ -14,8 +17,8 @@ pub fn peer(
     palette: Res<Palette>,
     mut input: EventReader<NetworkInput>,
     mut output: EventWriter<NetworkOutput>,
-    players: Query<(&NetworkClient, &Position, &Character), With<Online>>,
-    entities: Query<(Entity, &Position, &Details), Without<Tile>>,
+    players: Players<(&NetworkClient, &Position, &Zone, &Character), With<Online>>,
+    entities: Query<(Entity, &Position, &Zone, &Details), Without<Tile>>,
 ) {
     lazy_static! {
         static ref CMD: Regex = Regex::new("^(peer|p)$").unwrap();
@@ -23,12 +26,11 @@ pub fn peer(
 
     for message in input.iter() {
         if CMD.is_match(&message.body.to_lowercase()) {
-            if let Some((client, position, _)) = players.iter().find(|(c, _, _)| c.id == message.id)
-            {
+            if let Some((client, position, zone, _)) = players.sender(&message.id) {
                 let ids = entities
                     .iter()
-                    .filter(|(_, p, _)| p.0 == position.0)
-                    .map(|(e, _, d)| {
+                    .filter(|(_, p, z, _)| p.0 == position.0 && z.0 == zone.0)
+                    .map(|(e, _, _, d)| {
                         format!("{} {}", d.name, palette.slate[9].paint(e.id()).bold())
                     })
                     .collect::<Vec<_>>();
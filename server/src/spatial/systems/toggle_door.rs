@@ -1,80 +1,137 @@
 use bevy::prelude::*;
-use lazy_static::lazy_static;
-use regex::Regex;
 
 use crate::{
-    network::events::{NetworkInput, NetworkOutput},
-    player::components::{client::NetworkClient, online::Online},
-    spatial::components::{collider::Collider, door::Door, position::Position},
+    network::events::NetworkOutput,
+    player::{
+        components::{character::Character, client::NetworkClient, online::Online},
+        events::action_event::ActionEvent,
+        utils::{actions::Action, players::Players},
+    },
+    spatial::{
+        components::{collider::Collider, door::Door, lock::Lock, position::Position, zone::Zone},
+        utils::{direction::adjacent, visibility::Sight},
+    },
     visual::components::sprite::Sprite,
 };
 
-/// Handles opening and closing doors
+/// Handles opening and closing doors, as long as they aren't locked.
+///
+/// A direction can be given (`open north`, `close door e`) to pick a door when
+/// there's more than one nearby. Anyone else who can see the door is told about it.
 pub fn toggle_door(
     mut commands: Commands,
-    mut input: EventReader<NetworkInput>,
+    mut actions: EventReader<ActionEvent>,
     mut output: EventWriter<NetworkOutput>,
-    players: Query<(&NetworkClient, &mut Position), With<Online>>,
+    sight: Sight,
+    players: Players<(&NetworkClient, &Character, &Position, &Zone), With<Online>>,
     mut doors: Query<
-        (Entity, &Door, &Position, &mut Sprite, Option<&Collider>),
+        (
+            Entity,
+            &Door,
+            &Position,
+            &Zone,
+            &mut Sprite,
+            Option<&Collider>,
+            Option<&Lock>,
+        ),
         Without<NetworkClient>,
     >,
 ) {
-    lazy_static! {
-        static ref CMD: Regex = Regex::new("^(open|close)$").unwrap();
-    }
-
-    for message in input.iter() {
-        if let Some(captures) = CMD.captures(&message.body.to_lowercase()) {
-            if let Some((client, position)) = players.iter().find(|p| p.0.id == message.id) {
-                if let Some((entity, door, _, mut sprite, collider)) =
-                    doors.iter_mut().find(|(_, _, p, _, _)| {
-                        p.0 == position.0 + IVec2::new(0, 1)
-                            || p.0 == position.0 + IVec2::new(0, -1)
-                            || p.0 == position.0 + IVec2::new(1, 0)
-                            || p.0 == position.0 + IVec2::new(-1, 0)
-                    })
-                {
-                    match captures.get(0).unwrap().as_str() {
-                        "open" => {
-                            match collider {
-                                Some(_) => {
-                                    sprite.character = door.opened_character.clone();
-                                    commands.entity(entity).remove::<Collider>();
-
-                                    output.send(NetworkOutput {
-                                        id: client.id,
-                                        body: "The door opens.".to_string(),
-                                    });
-                                }
-                                None => output.send(NetworkOutput {
-                                    id: client.id,
-                                    body: "It's already open!".to_string(),
-                                }),
-                            };
-                        }
-                        "close" => match collider {
-                            None => {
-                                sprite.character = door.closed_character.clone();
-                                commands.entity(entity).insert(Collider);
-
-                                output.send(NetworkOutput {
-                                    id: client.id,
-                                    body: "The door closes.".to_string(),
-                                });
-                            }
-                            Some(_) => output.send(NetworkOutput {
-                                id: client.id,
-                                body: "It's already closed!".to_string(),
-                            }),
-                        },
-                        _ => {}
-                    }
-                } else {
+    for message in actions.iter() {
+        if let Some(captures) = Action::ToggleDoor.captures(&message.body.to_lowercase()) {
+            if let Some((client, character, position, zone)) = players.sender(&message.id) {
+                let direction = captures.name("direction").map(|d| d.as_str());
+                let targets = adjacent(position.0, direction);
+
+                let mut found = doors
+                    .iter_mut()
+                    .filter(|(_, _, p, z, _, _, _)| z.0 == zone.0 && targets.contains(&p.0))
+                    .collect::<Vec<_>>();
+
+                if found.len() > 1 {
                     output.send(NetworkOutput {
                         id: client.id,
-                        body: "There's no doors here!".to_string(),
+                        body: "Which door? Try `open north`, for example.".to_string(),
                     });
+
+                    continue;
+                }
+
+                let (entity, door, door_position, _, mut sprite, collider, lock) =
+                    if let Some(found) = found.pop() {
+                        found
+                    } else {
+                        output.send(NetworkOutput {
+                            id: client.id,
+                            body: if direction.is_some() {
+                                "There's no door that way!".to_string()
+                            } else {
+                                "There's no doors here!".to_string()
+                            },
+                        });
+
+                        continue;
+                    };
+
+                let verb = match (captures.get(1).unwrap().as_str(), collider) {
+                    ("open", Some(_)) if lock.map_or(false, |lock| lock.locked) => {
+                        output.send(NetworkOutput {
+                            id: client.id,
+                            body: "It's locked.".to_string(),
+                        });
+
+                        continue;
+                    }
+                    ("open", Some(_)) => {
+                        sprite.character = door.opened_character.clone();
+                        commands.entity(entity).remove::<Collider>();
+
+                        "opens"
+                    }
+                    ("open", None) => {
+                        output.send(NetworkOutput {
+                            id: client.id,
+                            body: "It's already open!".to_string(),
+                        });
+
+                        continue;
+                    }
+                    (_, None) => {
+                        sprite.character = door.closed_character.clone();
+                        commands.entity(entity).insert(Collider);
+
+                        "closes"
+                    }
+                    (_, Some(_)) => {
+                        output.send(NetworkOutput {
+                            id: client.id,
+                            body: "It's already closed!".to_string(),
+                        });
+
+                        continue;
+                    }
+                };
+
+                output.send(NetworkOutput {
+                    id: client.id,
+                    body: format!("The door {verb}."),
+                });
+
+                let door_position = door_position.0;
+
+                for (other_client, _, other_position, _) in players
+                    .iter()
+                    .filter(|(c, _, _, z)| c.id != client.id && z.0 == zone.0)
+                {
+                    let visible =
+                        sight.visible_from(&zone.0, other_position.0, other_client.sight_radius());
+
+                    if visible.contains(&door_position) {
+                        output.send(NetworkOutput {
+                            id: other_client.id,
+                            body: format!("{} {verb} the door.", character.name),
+                        });
+                    }
                 }
             }
         }
@@ -86,20 +143,28 @@ mod tests {
     use bevy::{ecs::event::Events, prelude::*};
 
     use crate::{
-        network::events::{NetworkInput, NetworkOutput},
-        player::components::client::NetworkClient,
-        spatial::components::collider::Collider,
+        network::events::NetworkOutput,
+        player::{
+            components::client::NetworkClient, events::action_event::ActionEvent,
+            resources::player_index::PlayerIndex, systems::index_players::index_players,
+        },
+        spatial::components::{collider::Collider, lock::Lock},
         test::bundles::utils::{
             closed_door_bundle, open_door_bundle, player_bundle, DoorBundle, PlayerBundle,
         },
+        world::resources::{weather::Weather, world_time::WorldTime},
     };
 
     #[test]
     fn open() {
         let mut app = App::new();
 
-        app.add_event::<NetworkInput>();
+        app.insert_resource(WorldTime::default());
+        app.init_resource::<Weather>();
+        app.add_event::<ActionEvent>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::toggle_door);
 
         let player = app
@@ -122,11 +187,10 @@ mod tests {
             .id();
 
         app.world
-            .resource_mut::<Events<NetworkInput>>()
-            .send(NetworkInput {
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
                 id: player_client_id,
                 body: "open".into(),
-                internal: false,
             });
 
         app.update();
@@ -141,12 +205,71 @@ mod tests {
         assert_eq!(output.body, "The door opens.");
     }
 
+    #[test]
+    fn locked() {
+        let mut app = App::new();
+
+        app.insert_resource(WorldTime::default());
+        app.init_resource::<Weather>();
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::toggle_door);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        let door = app
+            .world
+            .spawn()
+            .insert_bundle(closed_door_bundle(DoorBundle {
+                y: 1,
+                ..Default::default()
+            }))
+            .insert(Lock {
+                key: "Brass Key".into(),
+                locked: true,
+                pick_chance: None,
+            })
+            .id();
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: "open".into(),
+            });
+
+        app.update();
+
+        assert!(app.world.get::<Collider>(door).is_some());
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(output.id, player_client_id);
+        assert_eq!(output.body, "It's locked.");
+    }
+
     #[test]
     fn already_open() {
         let mut app = App::new();
 
-        app.add_event::<NetworkInput>();
+        app.insert_resource(WorldTime::default());
+        app.init_resource::<Weather>();
+        app.add_event::<ActionEvent>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::toggle_door);
 
         let player = app
@@ -167,11 +290,10 @@ mod tests {
             }));
 
         app.world
-            .resource_mut::<Events<NetworkInput>>()
-            .send(NetworkInput {
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
                 id: player_client_id,
                 body: "open".into(),
-                internal: false,
             });
 
         app.update();
@@ -188,8 +310,12 @@ mod tests {
     fn close() {
         let mut app = App::new();
 
-        app.add_event::<NetworkInput>();
+        app.insert_resource(WorldTime::default());
+        app.init_resource::<Weather>();
+        app.add_event::<ActionEvent>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::toggle_door);
 
         let player = app
@@ -212,11 +338,10 @@ mod tests {
             .id();
 
         app.world
-            .resource_mut::<Events<NetworkInput>>()
-            .send(NetworkInput {
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
                 id: player_client_id,
                 body: "close".into(),
-                internal: false,
             });
 
         app.update();
@@ -235,8 +360,12 @@ mod tests {
     fn already_closed() {
         let mut app = App::new();
 
-        app.add_event::<NetworkInput>();
+        app.insert_resource(WorldTime::default());
+        app.init_resource::<Weather>();
+        app.add_event::<ActionEvent>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::toggle_door);
 
         let player = app
@@ -258,11 +387,10 @@ mod tests {
             }));
 
         app.world
-            .resource_mut::<Events<NetworkInput>>()
-            .send(NetworkInput {
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
                 id: player_client_id,
                 body: "close".into(),
-                internal: false,
             });
 
         app.update();
@@ -279,8 +407,12 @@ mod tests {
     fn no_nearby_door() {
         let mut app = App::new();
 
-        app.add_event::<NetworkInput>();
+        app.insert_resource(WorldTime::default());
+        app.init_resource::<Weather>();
+        app.add_event::<ActionEvent>();
         app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
         app.add_system(super::toggle_door);
 
         let player = app
@@ -294,11 +426,10 @@ mod tests {
         let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
 
         app.world
-            .resource_mut::<Events<NetworkInput>>()
-            .send(NetworkInput {
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
                 id: player_client_id,
                 body: "open".into(),
-                internal: false,
             });
 
         app.update();
@@ -310,4 +441,160 @@ mod tests {
         assert_eq!(output.id, player_client_id);
         assert_eq!(output.body, "There's no doors here!");
     }
+
+    #[test]
+    fn by_direction() {
+        let mut app = App::new();
+
+        app.insert_resource(WorldTime::default());
+        app.init_resource::<Weather>();
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::toggle_door);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                x: 1,
+                y: 1,
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        let north = app
+            .world
+            .spawn()
+            .insert_bundle(closed_door_bundle(DoorBundle {
+                x: 1,
+                y: 0,
+                ..Default::default()
+            }))
+            .id();
+
+        let southeast = app
+            .world
+            .spawn()
+            .insert_bundle(closed_door_bundle(DoorBundle {
+                x: 2,
+                y: 2,
+                ..Default::default()
+            }))
+            .id();
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: "open".into(),
+            });
+
+        app.update();
+
+        assert!(app.world.get::<Collider>(north).is_some());
+        assert!(app.world.get::<Collider>(southeast).is_some());
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: "open door se".into(),
+            });
+
+        app.update();
+
+        assert!(app.world.get::<Collider>(north).is_some());
+        assert!(app.world.get::<Collider>(southeast).is_none());
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let outputs = output_reader
+            .iter(output_events)
+            .map(|output| output.body.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            outputs,
+            vec![
+                "Which door? Try `open north`, for example.",
+                "The door opens."
+            ]
+        );
+    }
+
+    #[test]
+    fn onlookers() {
+        let mut app = App::new();
+
+        app.insert_resource(WorldTime::default());
+        app.init_resource::<Weather>();
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::toggle_door);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                name: "Amri".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        let onlooker = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                x: 3,
+                ..Default::default()
+            }))
+            .id();
+
+        let onlooker_client_id = app.world.get::<NetworkClient>(onlooker).unwrap().id;
+
+        let elsewhere = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                zone: "Elsewhere".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        let elsewhere_client_id = app.world.get::<NetworkClient>(elsewhere).unwrap().id;
+
+        app.world
+            .spawn()
+            .insert_bundle(closed_door_bundle(DoorBundle {
+                y: 1,
+                ..Default::default()
+            }));
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: "open south".into(),
+            });
+
+        app.update();
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let outputs = output_reader.iter(output_events).collect::<Vec<_>>();
+
+        assert_eq!(outputs.len(), 2);
+        assert!(outputs
+            .iter()
+            .any(|o| o.id == onlooker_client_id && o.body == "Amri opens the door."));
+        assert!(outputs.iter().all(|o| o.id != elsewhere_client_id));
+    }
 }
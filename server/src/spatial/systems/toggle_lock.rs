@@ -0,0 +1,354 @@
+use bevy::prelude::*;
+
+use crate::{
+    items::components::backpack::Backpack,
+    network::events::NetworkOutput,
+    player::{
+        components::{client::NetworkClient, online::Online},
+        events::action_event::ActionEvent,
+        utils::{actions::Action, players::Players},
+    },
+    spatial::{
+        components::{collider::Collider, lock::Lock, position::Position, zone::Zone},
+        utils::direction::adjacent,
+    },
+    visual::components::details::Details,
+    world::components::prototype::Prototype,
+};
+
+/// Handles locking, unlocking and picking the [`Lock`] on a door.
+///
+/// Locking and unlocking need the key in the player's [`Backpack`]. Like doors, a
+/// direction can be given to pick between nearby locks (`unlock east`).
+pub fn toggle_lock(
+    mut actions: EventReader<ActionEvent>,
+    mut output: EventWriter<NetworkOutput>,
+    players: Players<(&NetworkClient, &Position, &Zone, &Backpack), With<Online>>,
+    items: Query<(&Prototype, &Details)>,
+    mut locks: Query<(&mut Lock, &Position, &Zone, Option<&Collider>), Without<NetworkClient>>,
+) {
+    for message in actions.iter() {
+        if let Some(captures) = Action::ToggleLock.captures(&message.body.to_lowercase()) {
+            if let Some((client, position, zone, backpack)) = players.sender(&message.id) {
+                let targets = adjacent(position.0, captures.name("direction").map(|d| d.as_str()));
+
+                let mut found = locks
+                    .iter_mut()
+                    .filter(|(_, p, z, _)| z.0 == zone.0 && targets.contains(&p.0))
+                    .collect::<Vec<_>>();
+
+                if found.len() > 1 {
+                    output.send(NetworkOutput {
+                        id: client.id,
+                        body: format!(
+                            "Which door? Try `{} north`, for example.",
+                            captures.get(1).unwrap().as_str()
+                        ),
+                    });
+
+                    continue;
+                }
+
+                let (mut lock, collider) = if let Some((lock, _, _, collider)) = found.pop() {
+                    (lock, collider)
+                } else {
+                    output.send(NetworkOutput {
+                        id: client.id,
+                        body: "There's no locks here!".to_string(),
+                    });
+
+                    continue;
+                };
+
+                let key = backpack
+                    .0
+                    .iter()
+                    .filter_map(|item| items.get(*item).ok())
+                    .find(|(prototype, _)| prototype.0 == lock.key)
+                    .map(|(_, details)| details.name.clone());
+
+                let body = match captures.get(1).unwrap().as_str() {
+                    "lock" => match (lock.locked, collider, key) {
+                        (true, _, _) => "It's already locked!".to_string(),
+                        (false, None, _) => "You'll have to close it first.".to_string(),
+                        (false, Some(_), None) => "You don't have the key.".to_string(),
+                        (false, Some(_), Some(key)) => {
+                            lock.locked = true;
+
+                            format!("You lock the door with the {key}.")
+                        }
+                    },
+                    "unlock" => match (lock.locked, key) {
+                        (false, _) => "It's not locked!".to_string(),
+                        (true, None) => "You don't have the key.".to_string(),
+                        (true, Some(key)) => {
+                            lock.locked = false;
+
+                            format!("You unlock the door with the {key}.")
+                        }
+                    },
+                    _ => match (lock.locked, lock.pick_chance) {
+                        (false, _) => "It's not locked!".to_string(),
+                        (true, None) => "This lock can't be picked.".to_string(),
+                        (true, Some(chance)) => {
+                            if rand::random::<f32>() < chance {
+                                lock.locked = false;
+
+                                "You pick the lock.".to_string()
+                            } else {
+                                "You fail to pick the lock.".to_string()
+                            }
+                        }
+                    },
+                };
+
+                output.send(NetworkOutput {
+                    id: client.id,
+                    body,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::{
+        network::events::NetworkOutput,
+        player::{
+            components::client::NetworkClient, events::action_event::ActionEvent,
+            resources::player_index::PlayerIndex, systems::index_players::index_players,
+        },
+        spatial::components::lock::Lock,
+        test::bundles::utils::{
+            closed_door_bundle, item_in_backpack_bundle, player_bundle, DoorBundle, ItemBundle,
+            PlayerBundle,
+        },
+        world::components::prototype::Prototype,
+    };
+
+    #[test]
+    fn unlock() {
+        let mut app = App::new();
+
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::toggle_lock);
+
+        let key = app
+            .world
+            .spawn()
+            .insert_bundle(item_in_backpack_bundle(ItemBundle {
+                name: "Brass Key".into(),
+                ..Default::default()
+            }))
+            .insert(Prototype("Brass Key".into()))
+            .id();
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                items: vec![key],
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        let door = app
+            .world
+            .spawn()
+            .insert_bundle(closed_door_bundle(DoorBundle {
+                y: 1,
+                ..Default::default()
+            }))
+            .insert(Lock {
+                key: "Brass Key".into(),
+                locked: true,
+                pick_chance: None,
+            })
+            .id();
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: "unlock".into(),
+            });
+
+        app.update();
+
+        assert!(!app.world.get::<Lock>(door).unwrap().locked);
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(output.body, "You unlock the door with the Brass Key.");
+    }
+
+    #[test]
+    fn no_key() {
+        let mut app = App::new();
+
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::toggle_lock);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        let door = app
+            .world
+            .spawn()
+            .insert_bundle(closed_door_bundle(DoorBundle {
+                y: 1,
+                ..Default::default()
+            }))
+            .insert(Lock {
+                key: "Brass Key".into(),
+                locked: true,
+                pick_chance: None,
+            })
+            .id();
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: "unlock".into(),
+            });
+
+        app.update();
+
+        assert!(app.world.get::<Lock>(door).unwrap().locked);
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(output.body, "You don't have the key.");
+    }
+
+    #[test]
+    fn pick() {
+        let mut app = App::new();
+
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::toggle_lock);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        let door = app
+            .world
+            .spawn()
+            .insert_bundle(closed_door_bundle(DoorBundle {
+                y: 1,
+                ..Default::default()
+            }))
+            .insert(Lock {
+                key: "Brass Key".into(),
+                locked: true,
+                pick_chance: Some(1.0),
+            })
+            .id();
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: "pick lock".into(),
+            });
+
+        app.update();
+
+        assert!(!app.world.get::<Lock>(door).unwrap().locked);
+    }
+
+    #[test]
+    fn which_door() {
+        let mut app = App::new();
+
+        app.add_event::<ActionEvent>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::toggle_lock);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        let mut doors = Vec::new();
+
+        for y in [-1, 1] {
+            doors.push(
+                app.world
+                    .spawn()
+                    .insert_bundle(closed_door_bundle(DoorBundle {
+                        y,
+                        ..Default::default()
+                    }))
+                    .insert(Lock {
+                        key: "Brass Key".into(),
+                        locked: true,
+                        pick_chance: Some(1.0),
+                    })
+                    .id(),
+            );
+        }
+
+        app.world
+            .resource_mut::<Events<ActionEvent>>()
+            .send(ActionEvent {
+                id: player_client_id,
+                body: "pick lock".into(),
+            });
+
+        app.update();
+
+        for door in doors {
+            assert!(app.world.get::<Lock>(door).unwrap().locked);
+        }
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(
+            output.body,
+            "Which door? Try `pick lock north`, for example."
+        );
+    }
+}
@@ -0,0 +1,310 @@
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::{
+    network::events::{NetworkInput, NetworkOutput},
+    player::{
+        components::{client::NetworkClient, online::Online},
+        utils::players::Players,
+    },
+    spatial::{
+        components::{
+            collider::Collider, door::Door, landmark::Landmark, lock::Lock, position::Position,
+            tile::Tile, travelling::Travelling, zone::Zone,
+        },
+        utils::pathfinding::find_path,
+    },
+};
+
+/// Handles the `travel` command, finding a path to a [`Landmark`] or position
+/// and setting the player off along it. Any other input stops them where they are.
+pub fn travel(
+    mut commands: Commands,
+    mut input: EventReader<NetworkInput>,
+    mut output: EventWriter<NetworkOutput>,
+    players: Players<
+        (
+            Entity,
+            &NetworkClient,
+            &Position,
+            &Zone,
+            Option<&Travelling>,
+        ),
+        With<Online>,
+    >,
+    tiles: Query<(&Position, &Zone), (With<Tile>, Without<NetworkClient>)>,
+    colliders: Query<
+        (&Position, &Zone, Option<&Door>, Option<&Lock>),
+        (With<Collider>, Without<NetworkClient>),
+    >,
+    landmarks: Query<(&Position, &Zone, &Landmark)>,
+) {
+    lazy_static! {
+        static ref CMD: Regex =
+            Regex::new("^(travel|walk to) (?:(?P<x>-?\\d+) (?P<y>-?\\d+)|(?P<landmark>.+))$")
+                .unwrap();
+    }
+
+    for message in input.iter() {
+        if message.internal {
+            continue;
+        }
+
+        if let Some((entity, client, position, zone, travelling)) = players.sender(&message.id) {
+            let body = message.body.to_lowercase();
+
+            let captures = if let Some(captures) = CMD.captures(&body) {
+                captures
+            } else {
+                if travelling.is_some() {
+                    commands.entity(entity).remove::<Travelling>();
+
+                    output.send(NetworkOutput {
+                        id: client.id,
+                        body: "You stop travelling.".to_string(),
+                    });
+                }
+
+                continue;
+            };
+
+            let goal = match (captures.name("x"), captures.name("y")) {
+                (Some(x), Some(y)) => x
+                    .as_str()
+                    .parse()
+                    .ok()
+                    .zip(y.as_str().parse().ok())
+                    .map(|(x, y)| IVec2::new(x, y)),
+                _ => captures.name("landmark").and_then(|name| {
+                    landmarks
+                        .iter()
+                        .find(|(_, z, l)| z.0 == zone.0 && l.0.to_lowercase() == name.as_str())
+                        .map(|(p, _, _)| p.0)
+                }),
+            };
+
+            let goal = if let Some(goal) = goal {
+                goal
+            } else {
+                output.send(NetworkOutput {
+                    id: client.id,
+                    body: "You don't know where that is.".to_string(),
+                });
+
+                continue;
+            };
+
+            let tiles = tiles
+                .iter()
+                .filter(|(_, z)| z.0 == zone.0)
+                .map(|(p, _)| p.0)
+                .collect::<HashSet<_>>();
+
+            // Closed doors can be walked through by opening them on the way,
+            // as long as they aren't locked.
+            let blocked = colliders
+                .iter()
+                .filter(|(_, z, _, _)| z.0 == zone.0)
+                .map(|(p, _, door, lock)| {
+                    (
+                        p.0,
+                        door.is_some() && !lock.map_or(false, |lock| lock.locked),
+                    )
+                })
+                .collect::<HashMap<_, _>>();
+
+            let path = find_path(position.0, goal, |_, to| {
+                tiles.contains(&to) && blocked.get(&to).map_or(true, |is_door| *is_door)
+            });
+
+            match path {
+                Some(path) if !path.is_empty() => {
+                    commands
+                        .entity(entity)
+                        .insert(Travelling { path: path.into() });
+
+                    output.send(NetworkOutput {
+                        id: client.id,
+                        body: "You set off.".to_string(),
+                    });
+                }
+                Some(_) => output.send(NetworkOutput {
+                    id: client.id,
+                    body: "You're already there.".to_string(),
+                }),
+                None => output.send(NetworkOutput {
+                    id: client.id,
+                    body: "You can't find a way there.".to_string(),
+                }),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::{
+        network::events::{NetworkInput, NetworkOutput},
+        player::{
+            components::client::NetworkClient, resources::player_index::PlayerIndex,
+            systems::index_players::index_players,
+        },
+        spatial::components::{
+            collider::Collider, landmark::Landmark, position::Position, travelling::Travelling,
+            zone::Zone,
+        },
+        test::bundles::utils::{player_bundle, tile_bundle, PlayerBundle, TileBundle},
+    };
+
+    #[test]
+    fn to_position() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkInput>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::travel);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        for x in 0..3 {
+            app.world.spawn().insert_bundle(tile_bundle(TileBundle {
+                x,
+                ..Default::default()
+            }));
+
+            app.world.spawn().insert_bundle(tile_bundle(TileBundle {
+                x,
+                y: 1,
+                ..Default::default()
+            }));
+        }
+
+        app.world
+            .spawn()
+            .insert_bundle(tile_bundle(TileBundle {
+                x: 1,
+                ..Default::default()
+            }))
+            .insert(Collider);
+
+        app.world
+            .resource_mut::<Events<NetworkInput>>()
+            .send(NetworkInput {
+                id: player_client_id,
+                body: "travel 2 0".into(),
+                internal: false,
+            });
+
+        app.update();
+
+        let travelling = app.world.get::<Travelling>(player).unwrap();
+
+        assert_eq!(travelling.path, vec![IVec2::new(1, 1), IVec2::new(2, 0)]);
+    }
+
+    #[test]
+    fn to_landmark() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkInput>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::travel);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        for y in 0..3 {
+            app.world.spawn().insert_bundle(tile_bundle(TileBundle {
+                y,
+                ..Default::default()
+            }));
+        }
+
+        app.world.spawn().insert_bundle((
+            Landmark("Well".into()),
+            Position(IVec2::new(0, 2)),
+            Zone("Test".into()),
+        ));
+
+        app.world
+            .resource_mut::<Events<NetworkInput>>()
+            .send(NetworkInput {
+                id: player_client_id,
+                body: "walk to well".into(),
+                internal: false,
+            });
+
+        app.update();
+
+        let travelling = app.world.get::<Travelling>(player).unwrap();
+
+        assert_eq!(travelling.path.back(), Some(&IVec2::new(0, 2)));
+    }
+
+    #[test]
+    fn cancelled() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkInput>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::travel);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .insert(Travelling {
+                path: vec![IVec2::new(0, 1)].into(),
+            })
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        app.world
+            .resource_mut::<Events<NetworkInput>>()
+            .send(NetworkInput {
+                id: player_client_id,
+                body: "look".into(),
+                internal: false,
+            });
+
+        app.update();
+
+        assert!(app.world.get::<Travelling>(player).is_none());
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(output.body, "You stop travelling.");
+    }
+}
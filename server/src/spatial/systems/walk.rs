@@ -0,0 +1,194 @@
+use bevy::prelude::*;
+
+use crate::{
+    network::events::NetworkOutput,
+    player::components::{action_queue::ActionQueue, client::NetworkClient},
+    spatial::{
+        components::{
+            collider::Collider, door::Door, lock::Lock, position::Position, travelling::Travelling,
+            zone::Zone,
+        },
+        utils::direction::direction_name,
+    },
+};
+
+/// Walks [`Travelling`] players along their path one step at a time, by queueing
+/// the same movement commands they'd type themselves whenever their [`ActionQueue`]
+/// is idle.
+pub fn walk(
+    mut commands: Commands,
+    mut output: EventWriter<NetworkOutput>,
+    mut players: Query<(
+        Entity,
+        &NetworkClient,
+        &Position,
+        &Zone,
+        &mut Travelling,
+        &mut ActionQueue,
+    )>,
+    closed_doors: Query<
+        (&Position, &Zone, Option<&Lock>),
+        (With<Door>, With<Collider>, Without<NetworkClient>),
+    >,
+) {
+    for (entity, client, position, zone, mut travelling, mut queue) in players.iter_mut() {
+        if !queue.actions.is_empty() || !queue.is_ready() {
+            continue;
+        }
+
+        let next = if let Some(next) = travelling.path.front() {
+            *next
+        } else {
+            commands.entity(entity).remove::<Travelling>();
+
+            output.send(NetworkOutput {
+                id: client.id,
+                body: "You arrive.".to_string(),
+            });
+
+            continue;
+        };
+
+        let command = if let Some(command) = direction_name(next - position.0) {
+            command
+        } else {
+            // Something moved us off the path, like an exit or a step that got blocked.
+            commands.entity(entity).remove::<Travelling>();
+
+            output.send(NetworkOutput {
+                id: client.id,
+                body: "You lose your way.".to_string(),
+            });
+
+            continue;
+        };
+
+        // Open any door in the way first, and step through it next time.
+        if let Some((_, _, lock)) = closed_doors
+            .iter()
+            .find(|(p, z, _)| p.0 == next && z.0 == zone.0)
+        {
+            if lock.map_or(false, |lock| lock.locked) {
+                commands.entity(entity).remove::<Travelling>();
+
+                output.send(NetworkOutput {
+                    id: client.id,
+                    body: "A locked door blocks your way.".to_string(),
+                });
+            } else {
+                queue.actions.push_back(format!("open {command}"));
+            }
+
+            continue;
+        }
+
+        travelling.path.pop_front();
+
+        queue.actions.push_back(command.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{
+        network::events::NetworkOutput,
+        player::components::action_queue::ActionQueue,
+        spatial::components::travelling::Travelling,
+        test::bundles::utils::{closed_door_bundle, player_bundle, DoorBundle, PlayerBundle},
+    };
+
+    #[test]
+    fn step() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkOutput>();
+        app.add_system(super::walk);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .insert(Travelling {
+                path: vec![IVec2::new(1, 1), IVec2::new(2, 1)].into(),
+            })
+            .id();
+
+        app.update();
+
+        assert_eq!(
+            app.world.get::<ActionQueue>(player).unwrap().actions,
+            vec!["southeast"]
+        );
+        assert_eq!(app.world.get::<Travelling>(player).unwrap().path.len(), 1);
+    }
+
+    #[test]
+    fn waits_for_queue() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkOutput>();
+        app.add_system(super::walk);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .insert(Travelling {
+                path: vec![IVec2::new(0, 1)].into(),
+            })
+            .id();
+
+        app.world.get_mut::<ActionQueue>(player).unwrap().cooldown =
+            Timer::from_seconds(1.0, false);
+
+        app.update();
+
+        assert!(app
+            .world
+            .get::<ActionQueue>(player)
+            .unwrap()
+            .actions
+            .is_empty());
+        assert_eq!(app.world.get::<Travelling>(player).unwrap().path.len(), 1);
+    }
+
+    #[test]
+    fn opens_doors() {
+        let mut app = App::new();
+
+        app.add_event::<NetworkOutput>();
+        app.add_system(super::walk);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .insert(Travelling {
+                path: vec![IVec2::new(0, 1)].into(),
+            })
+            .id();
+
+        app.world
+            .spawn()
+            .insert_bundle(closed_door_bundle(DoorBundle {
+                y: 1,
+                ..Default::default()
+            }));
+
+        app.update();
+
+        assert_eq!(
+            app.world.get::<ActionQueue>(player).unwrap().actions,
+            vec!["open south"]
+        );
+        assert_eq!(app.world.get::<Travelling>(player).unwrap().path.len(), 1);
+    }
+}
@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+
+/// Every compass direction, its short form, and the offset it points to.
+pub const DIRECTIONS: [(&str, &str, IVec2); 8] = [
+    ("north", "n", IVec2::new(0, -1)),
+    ("northeast", "ne", IVec2::new(1, -1)),
+    ("east", "e", IVec2::new(1, 0)),
+    ("southeast", "se", IVec2::new(1, 1)),
+    ("south", "s", IVec2::new(0, 1)),
+    ("southwest", "sw", IVec2::new(-1, 1)),
+    ("west", "w", IVec2::new(-1, 0)),
+    ("northwest", "nw", IVec2::new(-1, -1)),
+];
+
+/// The offset for a direction, by either its full or short name.
+pub fn direction_offset(name: &str) -> Option<IVec2> {
+    DIRECTIONS
+        .iter()
+        .find(|(long, short, _)| *long == name || *short == name)
+        .map(|(_, _, offset)| *offset)
+}
+
+/// The full name of the direction an offset points in.
+pub fn direction_name(offset: IVec2) -> Option<&'static str> {
+    DIRECTIONS
+        .iter()
+        .find(|(_, _, o)| *o == offset)
+        .map(|(long, _, _)| *long)
+}
+
+/// Positions next to `position`, either just the one in `direction` or all
+/// eight if there isn't one.
+pub fn adjacent(position: IVec2, direction: Option<&str>) -> Vec<IVec2> {
+    match direction {
+        Some(direction) => direction_offset(direction)
+            .map(|offset| vec![position + offset])
+            .unwrap_or_default(),
+        None => DIRECTIONS
+            .iter()
+            .map(|(_, _, offset)| position + *offset)
+            .collect(),
+    }
+}
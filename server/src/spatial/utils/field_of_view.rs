@@ -0,0 +1,132 @@
+use bevy::{prelude::*, utils::HashSet};
+
+/// Multipliers to transform coordinates into each of the eight octants,
+/// as `(xx, xy, yx, yy)`.
+const OCTANTS: [(i32, i32, i32, i32); 8] = [
+    (1, 0, 0, 1),
+    (0, 1, 1, 0),
+    (0, -1, 1, 0),
+    (-1, 0, 0, 1),
+    (-1, 0, 0, -1),
+    (0, -1, -1, 0),
+    (0, 1, -1, 0),
+    (1, 0, 0, -1),
+];
+
+/// Work out which positions can be seen from `origin` using recursive shadowcasting.
+/// Opaque positions are visible themselves, but hide everything behind them.
+pub fn field_of_view(
+    origin: IVec2,
+    radius: i32,
+    is_opaque: impl Fn(IVec2) -> bool,
+) -> HashSet<IVec2> {
+    let mut visible = HashSet::default();
+
+    visible.insert(origin);
+
+    for octant in OCTANTS {
+        cast_light(
+            &mut visible,
+            &is_opaque,
+            origin,
+            radius,
+            1,
+            (1.0, 0.0),
+            octant,
+        );
+    }
+
+    visible
+}
+
+/// Scan one octant outwards from `row`, between the `(start, end)` slopes.
+fn cast_light(
+    visible: &mut HashSet<IVec2>,
+    is_opaque: &impl Fn(IVec2) -> bool,
+    origin: IVec2,
+    radius: i32,
+    row: i32,
+    (mut start, end): (f32, f32),
+    (xx, xy, yx, yy): (i32, i32, i32, i32),
+) {
+    if start < end {
+        return;
+    }
+
+    let mut new_start = 0.0;
+
+    for distance in row..=radius {
+        let dy = -distance;
+        let mut blocked = false;
+
+        for dx in -distance..=0 {
+            let left_slope = (dx as f32 - 0.5) / (dy as f32 + 0.5);
+            let right_slope = (dx as f32 + 0.5) / (dy as f32 - 0.5);
+
+            if start < right_slope {
+                continue;
+            } else if end > left_slope {
+                break;
+            }
+
+            let position = origin + IVec2::new(dx * xx + dy * xy, dx * yx + dy * yy);
+
+            if dx * dx + dy * dy <= radius * radius {
+                visible.insert(position);
+            }
+
+            if blocked {
+                if is_opaque(position) {
+                    new_start = right_slope;
+                } else {
+                    blocked = false;
+                    start = new_start;
+                }
+            } else if is_opaque(position) && distance < radius {
+                // Scan the part of the next row that isn't hidden by this blocker.
+                blocked = true;
+                cast_light(
+                    visible,
+                    is_opaque,
+                    origin,
+                    radius,
+                    distance + 1,
+                    (start, left_slope),
+                    (xx, xy, yx, yy),
+                );
+                new_start = right_slope;
+            }
+        }
+
+        if blocked {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::field_of_view;
+
+    #[test]
+    fn open() {
+        let visible = field_of_view(IVec2::ZERO, 2, |_| false);
+
+        assert!(visible.contains(&IVec2::new(0, 0)));
+        assert!(visible.contains(&IVec2::new(2, 0)));
+        assert!(visible.contains(&IVec2::new(-1, -1)));
+        assert!(!visible.contains(&IVec2::new(3, 0)));
+    }
+
+    #[test]
+    fn blocked() {
+        let visible = field_of_view(IVec2::ZERO, 4, |p| p == IVec2::new(1, 0));
+
+        assert!(visible.contains(&IVec2::new(1, 0)));
+        assert!(!visible.contains(&IVec2::new(2, 0)));
+        assert!(!visible.contains(&IVec2::new(3, 0)));
+        assert!(visible.contains(&IVec2::new(0, 3)));
+    }
+}
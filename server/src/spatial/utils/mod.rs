@@ -0,0 +1,5 @@
+pub mod direction;
+pub mod field_of_view;
+pub mod pathfinding;
+pub mod terrain;
+pub mod visibility;
@@ -0,0 +1,91 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use bevy::{prelude::*, utils::HashMap};
+
+use super::direction::DIRECTIONS;
+
+/// Find the shortest path from `start` to `goal` with A*, moving in any of the
+/// eight compass directions. `passable` decides whether a step from one position
+/// to the next is allowed.
+///
+/// The path doesn't include `start`, but does include `goal`.
+pub fn find_path(
+    start: IVec2,
+    goal: IVec2,
+    passable: impl Fn(IVec2, IVec2) -> bool,
+) -> Option<Vec<IVec2>> {
+    let mut open = BinaryHeap::new();
+    let mut came_from: HashMap<IVec2, IVec2> = HashMap::default();
+    let mut costs: HashMap<IVec2, i32> = HashMap::default();
+
+    open.push(Reverse((distance(start, goal), start.to_array())));
+    costs.insert(start, 0);
+
+    while let Some(Reverse((_, current))) = open.pop() {
+        let current = IVec2::from(current);
+
+        if current == goal {
+            let mut path = Vec::new();
+            let mut step = current;
+
+            while step != start {
+                path.push(step);
+                step = came_from[&step];
+            }
+
+            path.reverse();
+
+            return Some(path);
+        }
+
+        let cost = costs[&current] + 1;
+
+        for next in DIRECTIONS.iter().map(|(_, _, offset)| current + *offset) {
+            if !passable(current, next) || costs.get(&next).map_or(false, |c| *c <= cost) {
+                continue;
+            }
+
+            costs.insert(next, cost);
+            came_from.insert(next, current);
+            open.push(Reverse((cost + distance(next, goal), next.to_array())));
+        }
+    }
+
+    None
+}
+
+/// Chebyshev distance, since diagonal moves cost the same as straight ones.
+fn distance(a: IVec2, b: IVec2) -> i32 {
+    let difference = (a - b).abs();
+
+    difference.x.max(difference.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::find_path;
+
+    #[test]
+    fn around_wall() {
+        // A wall from (1, -2) to (1, 1), leaving a gap at (1, 2).
+        let path = find_path(IVec2::new(0, 0), IVec2::new(2, 0), |_, to| {
+            to.x >= -3 && to.x <= 3 && to.y >= -3 && to.y <= 3 && !(to.x == 1 && to.y < 2)
+        })
+        .unwrap();
+
+        assert_eq!(path.len(), 4);
+        assert_eq!(path.last(), Some(&IVec2::new(2, 0)));
+        assert!(path.contains(&IVec2::new(1, 2)));
+    }
+
+    #[test]
+    fn unreachable() {
+        let path = find_path(IVec2::new(0, 0), IVec2::new(5, 0), |_, to| {
+            to.x.abs() <= 2 && to.y.abs() <= 2
+        });
+
+        assert!(path.is_none());
+    }
+}
@@ -0,0 +1,63 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    player::components::client::NetworkClient,
+    spatial::components::{
+        collider::Collider, exit::Exit, movement_cost::MovementCost, position::Position,
+        stairs::Stairs, tile::Tile, zone::Zone,
+    },
+};
+
+/// The lay of the land: which [`Tile`]s there are to stand on, which spaces are
+/// blocked by a [`Collider`], and where any [`Exit`] or [`Stairs`] are.
+#[derive(SystemParam)]
+pub struct Terrain<'w, 's> {
+    tiles: Query<
+        'w,
+        's,
+        (
+            &'static Position,
+            &'static Zone,
+            Option<&'static MovementCost>,
+        ),
+        (With<Tile>, Without<NetworkClient>),
+    >,
+    colliders:
+        Query<'w, 's, (&'static Position, &'static Zone), (With<Collider>, Without<NetworkClient>)>,
+    exits: Query<'w, 's, (&'static Position, &'static Zone, &'static Exit), Without<NetworkClient>>,
+    stairs:
+        Query<'w, 's, (&'static Position, &'static Zone, &'static Stairs), Without<NetworkClient>>,
+}
+
+impl<'w, 's> Terrain<'w, 's> {
+    /// How many steps' time it takes to step onto the tile at `position`,
+    /// or `None` if there isn't one.
+    pub fn movement_cost(&self, zone: &str, position: IVec2) -> Option<f32> {
+        self.tiles
+            .iter()
+            .find(|(p, z, _)| p.0 == position && z.0 == zone)
+            .map(|(_, _, cost)| cost.map_or(1.0, |cost| cost.0))
+    }
+
+    /// Whether anything at `position` is in the way.
+    pub fn blocked(&self, zone: &str, position: IVec2) -> bool {
+        self.colliders
+            .iter()
+            .any(|(p, z)| p.0 == position && z.0 == zone)
+    }
+
+    /// Where stepping onto `position` leads instead, if anywhere.
+    pub fn exit(&self, zone: &str, position: IVec2) -> Option<&Exit> {
+        self.exits
+            .iter()
+            .find(|(p, z, _)| p.0 == position && z.0 == zone)
+            .map(|(_, _, exit)| exit)
+    }
+
+    /// Whether there are stairs at `position` going `up`, or down otherwise.
+    pub fn stairs(&self, zone: &str, position: IVec2, up: bool) -> bool {
+        self.stairs
+            .iter()
+            .any(|(p, z, s)| p.0 == position && z.0 == zone && if up { s.up } else { s.down })
+    }
+}
@@ -0,0 +1,76 @@
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashSet};
+
+use crate::{
+    player::components::client::NetworkClient,
+    spatial::components::{collider::Collider, position::Position, zone::Zone},
+    visual::components::light::Light,
+    world::resources::{weather::Weather, world_time::WorldTime},
+};
+
+use super::field_of_view::field_of_view;
+
+/// Everything visible from `origin`: in line of sight within `radius`, and
+/// either within `ambient` (how far daylight reaches) or lit by one of `lights`.
+pub fn visible_cells(
+    origin: IVec2,
+    radius: i32,
+    ambient: i32,
+    opaque: &HashSet<IVec2>,
+    lights: impl Iterator<Item = (IVec2, i32)>,
+) -> HashSet<IVec2> {
+    let mut lit = HashSet::default();
+
+    for (position, light_radius) in lights {
+        lit.extend(field_of_view(position, light_radius, |p| {
+            opaque.contains(&p)
+        }));
+    }
+
+    field_of_view(origin, radius, |p| opaque.contains(&p))
+        .into_iter()
+        .filter(|cell| {
+            let distance = *cell - origin;
+
+            distance.x * distance.x + distance.y * distance.y <= ambient * ambient
+                || lit.contains(cell)
+        })
+        .collect()
+}
+
+/// Everything that decides what players can see: the time of day and
+/// [`Weather`] for how far daylight reaches, anything with a [`Collider`]
+/// blocking line of sight, and any [`Light`] to see by in the dark.
+#[derive(SystemParam)]
+pub struct Sight<'w, 's> {
+    pub world_time: Res<'w, WorldTime>,
+    pub weather: Res<'w, Weather>,
+    colliders:
+        Query<'w, 's, (&'static Position, &'static Zone), (With<Collider>, Without<NetworkClient>)>,
+    lights:
+        Query<'w, 's, (&'static Position, &'static Zone, &'static Light), Without<NetworkClient>>,
+}
+
+impl<'w, 's> Sight<'w, 's> {
+    /// Everything visible from `origin` in `zone`, out to `radius`.
+    pub fn visible_from(&self, zone: &str, origin: IVec2, radius: i32) -> HashSet<IVec2> {
+        let opaque = self
+            .colliders
+            .iter()
+            .filter(|(_, z)| z.0 == zone)
+            .map(|(p, _)| p.0)
+            .collect::<HashSet<_>>();
+
+        visible_cells(
+            origin,
+            radius,
+            self.weather
+                .get(zone)
+                .sight_radius(self.world_time.part.light_radius()),
+            &opaque,
+            self.lights
+                .iter()
+                .filter(|(_, z, _)| z.0 == zone)
+                .map(|(p, _, light)| (p.0, light.radius)),
+        )
+    }
+}
@@ -12,15 +12,25 @@ pub mod utils {
     };
 
     use crate::{
-        items::components::{backpack::Backpack, can_take::CanTake, item::Item},
+        items::components::{
+            backpack::Backpack, can_take::CanTake, capacity::Capacity, equipment::Equipment,
+            item::Item, stack::Stack,
+        },
         network::server::ConnectionId,
-        player::components::{character::Character, client::NetworkClient, online::Online},
-        spatial::components::{collider::Collider, door::Door, position::Position, tile::Tile},
+        player::components::{
+            action_queue::ActionQueue, buffs::Buffs, character::Character, client::NetworkClient,
+            online::Online, vitals::Vitals,
+        },
+        spatial::components::{
+            collider::Collider, door::Door, position::Position, tile::Tile, zone::Zone,
+        },
         visual::components::{details::Details, sprite::Sprite},
+        world::components::prototype::Prototype,
     };
 
     pub struct PlayerBundle {
         pub name: String,
+        pub zone: String,
         pub x: i32,
         pub y: i32,
         pub items: Vec<Entity>,
@@ -30,6 +40,7 @@ pub mod utils {
         fn default() -> Self {
             Self {
                 name: Name().fake::<String>(),
+                zone: "Test".into(),
                 x: 0,
                 y: 0,
                 items: Vec::new(),
@@ -38,8 +49,27 @@ pub mod utils {
     }
 
     pub fn player_bundle(
-        PlayerBundle { name, x, y, items }: PlayerBundle,
-    ) -> (NetworkClient, Character, Position, Sprite, Backpack, Online) {
+        PlayerBundle {
+            name,
+            zone,
+            x,
+            y,
+            items,
+        }: PlayerBundle,
+    ) -> (
+        NetworkClient,
+        Character,
+        Position,
+        Zone,
+        Sprite,
+        Backpack,
+        Equipment,
+        Capacity,
+        ActionQueue,
+        Vitals,
+        Buffs,
+        Online,
+    ) {
         (
             NetworkClient {
                 id: ConnectionId {
@@ -55,12 +85,18 @@ pub mod utils {
                 id: 1,
             },
             Position(IVec2::new(x, y)),
+            Zone(zone),
             Sprite {
                 character: "@".to_string(),
                 color: "FFFFFF".to_string(),
                 background: None,
             },
             Backpack(items),
+            Equipment::default(),
+            Capacity::default(),
+            ActionQueue::default(),
+            Vitals::default(),
+            Buffs::default(),
             Online,
         )
     }
@@ -70,6 +106,7 @@ pub mod utils {
         pub description: String,
         pub character: String,
         pub color: String,
+        pub zone: String,
         pub x: i32,
         pub y: i32,
     }
@@ -81,6 +118,7 @@ pub mod utils {
                 description: Paragraph(1..2).fake::<String>(),
                 character: ".".into(),
                 color: "0F172A".to_string(),
+                zone: "Test".into(),
                 x: 0,
                 y: 0,
             }
@@ -93,15 +131,17 @@ pub mod utils {
             description,
             character,
             color,
+            zone,
             x,
             y,
         }: TileBundle,
-    ) -> (Tile, Details, Sprite, Position) {
+    ) -> (Tile, Details, Sprite, Position, Zone) {
         (
             Tile,
             Details {
                 name: name.into(),
                 description: description.into(),
+                times: Default::default(),
             },
             Sprite {
                 character,
@@ -109,6 +149,7 @@ pub mod utils {
                 background: None,
             },
             Position(IVec2::new(x, y)),
+            Zone(zone),
         )
     }
 
@@ -117,6 +158,8 @@ pub mod utils {
         pub description: String,
         pub character: String,
         pub color: String,
+        pub quantity: u32,
+        pub zone: String,
         pub x: i32,
         pub y: i32,
     }
@@ -128,6 +171,8 @@ pub mod utils {
                 description: Paragraph(1..2).fake::<String>(),
                 character: "x".into(),
                 color: "0F172A".to_string(),
+                quantity: 1,
+                zone: "Test".into(),
                 x: 0,
                 y: 0,
             }
@@ -140,23 +185,38 @@ pub mod utils {
             description,
             character,
             color,
+            quantity,
+            zone,
             x,
             y,
         }: ItemBundle,
-    ) -> (Item, Details, Sprite, Position, CanTake) {
+    ) -> (
+        Item,
+        Details,
+        Sprite,
+        Stack,
+        Position,
+        Zone,
+        CanTake,
+        Prototype,
+    ) {
         (
             Item,
             Details {
-                name: name.into(),
+                name: name.clone(),
                 description: description.into(),
+                times: Default::default(),
             },
             Sprite {
                 character,
                 color,
                 background: None,
             },
+            Stack(quantity),
             Position(IVec2::new(x, y)),
+            Zone(zone),
             CanTake,
+            Prototype(name),
         )
     }
 
@@ -166,22 +226,27 @@ pub mod utils {
             description,
             character,
             color,
+            quantity,
+            zone: _,
             x: _,
             y: _,
         }: ItemBundle,
-    ) -> (Item, Details, Sprite, CanTake) {
+    ) -> (Item, Details, Sprite, Stack, CanTake, Prototype) {
         (
             Item,
             Details {
-                name: name.into(),
+                name: name.clone(),
                 description: description.into(),
+                times: Default::default(),
             },
             Sprite {
                 character,
                 color,
                 background: None,
             },
+            Stack(quantity),
             CanTake,
+            Prototype(name),
         )
     }
 
@@ -189,6 +254,7 @@ pub mod utils {
         pub name: String,
         pub description: String,
         pub is_horizontal: bool,
+        pub zone: String,
         pub x: i32,
         pub y: i32,
     }
@@ -199,6 +265,7 @@ pub mod utils {
                 name: Sentence(1..2).fake::<String>(),
                 description: Paragraph(1..2).fake::<String>(),
                 is_horizontal: true,
+                zone: "Test".into(),
                 x: 0,
                 y: 0,
             }
@@ -210,10 +277,11 @@ pub mod utils {
             name,
             description,
             is_horizontal,
+            zone,
             x,
             y,
         }: DoorBundle,
-    ) -> (Door, Details, Sprite, Position, Collider) {
+    ) -> (Door, Details, Sprite, Position, Zone, Collider) {
         (
             Door {
                 opened_character: "/".to_string(),
@@ -222,6 +290,7 @@ pub mod utils {
             Details {
                 name: name.into(),
                 description: description.into(),
+                times: Default::default(),
             },
             Sprite {
                 character: if is_horizontal { "|" } else { "-" }.into(),
@@ -229,6 +298,7 @@ pub mod utils {
                 background: None,
             },
             Position(IVec2::new(x, y)),
+            Zone(zone),
             Collider,
         )
     }
@@ -238,10 +308,11 @@ pub mod utils {
             name,
             description,
             is_horizontal,
+            zone,
             x,
             y,
         }: DoorBundle,
-    ) -> (Door, Details, Sprite, Position) {
+    ) -> (Door, Details, Sprite, Position, Zone) {
         (
             Door {
                 opened_character: "/".to_string(),
@@ -250,6 +321,7 @@ pub mod utils {
             Details {
                 name: name.into(),
                 description: description.into(),
+                times: Default::default(),
             },
             Sprite {
                 character: "/".into(),
@@ -257,6 +329,7 @@ pub mod utils {
                 background: None,
             },
             Position(IVec2::new(x, y)),
+            Zone(zone),
         )
     }
 }
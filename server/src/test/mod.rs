@@ -1 +1,2 @@
 pub mod bundles;
+pub mod prototypes;
@@ -0,0 +1,20 @@
+#[cfg(test)]
+pub mod utils {
+    use bevy::{asset::FileAssetIo, prelude::*, tasks::TaskPool};
+    use bevy_proto::prelude::{ProtoData, ProtoDataOptions};
+
+    /// Load the real prototypes from `assets/`, for tests where items get
+    /// built from them, like splitting a stack.
+    pub fn insert_prototypes(app: &mut App) {
+        app.insert_resource(ProtoDataOptions {
+            directories: vec![String::from("assets/prototypes")],
+            recursive_loading: true,
+            ..Default::default()
+        });
+        app.init_resource::<ProtoData>();
+        app.insert_resource(AssetServer::new(
+            FileAssetIo::new("assets"),
+            TaskPool::new(),
+        ));
+    }
+}
@@ -1,9 +1,24 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
 use bevy_proto::ProtoComponent;
 use serde::{Deserialize, Serialize};
 
+use crate::world::resources::world_time::WorldTimeTag;
+
 #[derive(Clone, Serialize, Deserialize, ProtoComponent, Component)]
 pub struct Details {
     pub name: String,
     pub description: String,
+    /// Descriptions to use instead at certain times of day, when outdoors.
+    #[serde(default)]
+    pub times: HashMap<WorldTimeTag, String>,
+}
+
+impl Details {
+    /// The description to show at this time of day.
+    pub fn describe(&self, part: WorldTimeTag, indoors: bool) -> &str {
+        self.times
+            .get(&part)
+            .filter(|_| !indoors)
+            .unwrap_or(&self.description)
+    }
 }
@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+use bevy_proto::ProtoComponent;
+use serde::{Deserialize, Serialize};
+
+/// Lights up everything it can see within `radius`, no matter the time of day.
+#[derive(Clone, Serialize, Deserialize, ProtoComponent, Component, Debug)]
+pub struct Light {
+    pub radius: i32,
+}
@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+use bevy_proto::ProtoComponent;
+use serde::{Deserialize, Serialize};
+
+/// Keeps its colors on the map at any time of day, for things like a
+/// [`Light`](super::light::Light) that should stand out in the dark.
+#[derive(Clone, Serialize, Deserialize, ProtoComponent, Component, Debug)]
+pub struct Luminous;
@@ -1,2 +1,4 @@
 pub mod details;
+pub mod light;
+pub mod luminous;
 pub mod sprite;
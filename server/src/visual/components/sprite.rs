@@ -14,14 +14,21 @@ pub struct Sprite {
 
 pub trait SpritePaint {
     fn paint(&self) -> Paint<&str>;
+    /// Paint with every color passed through `shift` first.
+    fn paint_with(&self, shift: impl Fn([u8; 3]) -> [u8; 3]) -> Paint<&str>;
 }
 
 impl SpritePaint for Sprite {
     fn paint(&self) -> Paint<&str> {
-        let painted = Paint::new(self.character.as_str()).fg(rgb_to_color(hex_to_rgb(&self.color)));
+        self.paint_with(|rgb| rgb)
+    }
+
+    fn paint_with(&self, shift: impl Fn([u8; 3]) -> [u8; 3]) -> Paint<&str> {
+        let painted =
+            Paint::new(self.character.as_str()).fg(rgb_to_color(shift(hex_to_rgb(&self.color))));
 
         if let Some(background) = &self.background {
-            painted.bg(rgb_to_color(hex_to_rgb(background)))
+            painted.bg(rgb_to_color(shift(hex_to_rgb(background))))
         } else {
             painted
         }
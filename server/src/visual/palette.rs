@@ -138,3 +138,16 @@ pub fn hex_to_rgb(hex: &str) -> [u8; 3] {
 pub fn rgb_to_color(rgb: [u8; 3]) -> Color {
     Color::RGB(rgb[0], rgb[1], rgb[2])
 }
+
+/// Mix `amount` (from 0 to 1) of `tint` into `color`. Anything but RGB colors
+/// is left as is.
+pub fn blend(color: Color, tint: Color, amount: f32) -> Color {
+    match (color, tint) {
+        (Color::RGB(r, g, b), Color::RGB(tint_r, tint_g, tint_b)) => {
+            let mix = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * amount) as u8;
+
+            Color::RGB(mix(r, tint_r), mix(g, tint_g), mix(b, tint_b))
+        }
+        _ => color,
+    }
+}
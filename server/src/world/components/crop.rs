@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+use bevy_proto::ProtoComponent;
+use serde::{Deserialize, Serialize};
+
+/// Something planted that grows through `stages` over time, drawn with each
+/// stage's character in turn. How quickly depends on the weather.
+#[derive(Clone, Serialize, Deserialize, ProtoComponent, Component, Debug)]
+pub struct Crop {
+    pub stages: Vec<String>,
+    /// Game hours spent in each stage in fair weather.
+    pub hours_per_stage: f64,
+    /// Game hours grown so far, with the weather taken into account.
+    #[serde(default)]
+    pub grown: f64,
+}
+
+impl Crop {
+    /// The character for how far along it is, staying on the last once fully grown.
+    pub fn stage(&self) -> Option<&String> {
+        let index = (self.grown / self.hours_per_stage) as usize;
+
+        self.stages.get(index).or_else(|| self.stages.last())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Crop;
+
+    #[test]
+    fn stages() {
+        let mut crop = Crop {
+            stages: vec![".".into(), ",".into(), "Y".into()],
+            hours_per_stage: 2.0,
+            grown: 0.0,
+        };
+
+        assert_eq!(crop.stage().unwrap(), ".");
+
+        crop.grown = 2.5;
+        assert_eq!(crop.stage().unwrap(), ",");
+
+        crop.grown = 100.0;
+        assert_eq!(crop.stage().unwrap(), "Y");
+    }
+}
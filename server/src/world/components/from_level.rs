@@ -0,0 +1,6 @@
+use bevy::prelude::*;
+
+/// Spawned from a level, rather than dropped by a player, so it's
+/// kept in line with the level whenever it's reloaded.
+#[derive(Component, Debug)]
+pub struct FromLevel;
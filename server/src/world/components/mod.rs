@@ -0,0 +1,5 @@
+pub mod crop;
+pub mod from_level;
+pub mod overridden;
+pub mod prototype;
+pub mod refresh;
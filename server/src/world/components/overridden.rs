@@ -0,0 +1,6 @@
+use bevy::prelude::*;
+
+/// Changed from its [`Prototype`](super::prototype::Prototype) by the level it
+/// was placed in, so it's no longer the same as others of its kind.
+#[derive(Clone, Component, Debug)]
+pub struct Overridden;
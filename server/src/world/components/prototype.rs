@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// Name of the prototype an entity was spawned from.
+#[derive(Clone, Component, Debug)]
+pub struct Prototype(pub String);
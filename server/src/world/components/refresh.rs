@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+
+/// A throwaway copy of a reloaded prototype, whose [`Details`] and [`Sprite`]
+/// get copied onto the entity it's for.
+///
+/// [`Details`]: crate::visual::components::details::Details
+/// [`Sprite`]: crate::visual::components::sprite::Sprite
+#[derive(Component, Debug)]
+pub struct Refresh(pub Entity);
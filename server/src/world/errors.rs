@@ -0,0 +1,13 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum LevelError {
+    #[error("Could not read `server/assets`: {0}")]
+    Assets(std::io::Error),
+    #[error("Could not read {0:?}: {1}")]
+    Read(PathBuf, std::io::Error),
+    #[error("Could not parse {0:?}: {1}")]
+    Parse(PathBuf, serde_json::Error),
+}
@@ -0,0 +1,3 @@
+pub mod reload_event;
+pub mod weather_event;
+pub mod world_time_event;
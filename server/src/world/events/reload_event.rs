@@ -0,0 +1,4 @@
+/// Asks for every prototype and level to be read from disk again, either because
+/// they've changed or an admin asked.
+#[derive(Debug)]
+pub struct ReloadEvent;
@@ -0,0 +1,9 @@
+use crate::world::resources::weather::WeatherKind;
+
+/// Sent by the [`update_weather`] system when the weather in a zone changes,
+/// for anything that should react to it.
+#[derive(Debug)]
+pub struct WeatherEvent {
+    pub zone: String,
+    pub weather: WeatherKind,
+}
@@ -0,0 +1,9 @@
+use crate::world::resources::world_time::WorldTimeTag;
+
+/// Sent by the [`update_world_time`] system whenever the clock moves on
+/// to a new part of the day, like dawn breaking.
+#[derive(Debug)]
+pub struct WorldTimeEvent {
+    pub previous: WorldTimeTag,
+    pub part: WorldTimeTag,
+}
@@ -1,11 +1,26 @@
+pub mod components;
+pub mod errors;
+pub mod events;
 pub mod resources;
 mod systems;
+pub mod utils;
 
 use bevy::prelude::*;
 
 use self::{
-    resources::{new_player_spawn::NewPlayerSpawn, world_time::WorldTime},
-    systems::{setup_world::*, update_world_time::*},
+    events::{
+        reload_event::ReloadEvent, weather_event::WeatherEvent, world_time_event::WorldTimeEvent,
+    },
+    resources::{
+        asset_watcher::AssetWatcher, clock_settings::ClockSettings,
+        new_player_spawn::NewPlayerSpawn, taken_placements::TakenPlacements, weather::Weather,
+        world_time::WorldTime, zones::Zones,
+    },
+    systems::{
+        ambience::*, apply_refresh::*, calendar::*, forecast::*, grow_crops::*, load_world_time::*,
+        operate_doors::*, reload::*, reload_levels::*, reload_prototypes::*, save_world_time::*,
+        setup_world::*, update_weather::*, update_world_time::*, watch_assets::*,
+    },
 };
 
 pub struct WorldPlugin;
@@ -14,13 +29,39 @@ impl Plugin for WorldPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(NewPlayerSpawn::default());
         app.insert_resource(WorldTime::default());
+        app.insert_resource(Zones::default());
+        app.init_resource::<ClockSettings>();
+        app.init_resource::<Weather>();
+        app.init_resource::<TakenPlacements>();
+        app.init_resource::<AssetWatcher>();
+
+        app.add_event::<WorldTimeEvent>();
+        app.add_event::<WeatherEvent>();
+        app.add_event::<ReloadEvent>();
 
         app.add_startup_system(setup_world);
+        app.add_startup_system(load_world_time);
 
         app.add_system_set(
             SystemSet::new()
                 .label("world")
-                .with_system(update_world_time),
+                .with_system(update_world_time)
+                .with_system(update_weather)
+                .with_system(grow_crops)
+                .with_system(save_world_time)
+                .with_system(operate_doors)
+                .with_system(calendar)
+                .with_system(ambience)
+                .with_system(forecast)
+                .with_system(watch_assets.label("watch_assets"))
+                .with_system(reload.label("reload"))
+                // After anything that might ask for a reload this frame, so
+                // levels aren't rebuilt against the old prototypes.
+                .with_system(reload_prototypes.after("watch_assets").after("reload"))
+                .with_system(apply_refresh),
         );
+
+        // Levels are reloaded a stage later, once any prototypes have been.
+        app.add_system_to_stage(CoreStage::PostUpdate, reload_levels);
     }
 }
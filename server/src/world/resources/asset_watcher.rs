@@ -0,0 +1,21 @@
+use std::{env, time::SystemTime};
+
+use bevy::prelude::*;
+
+/// Watches `server/assets` for changes, if `WATCH_ASSETS` is `true` in env.
+pub struct AssetWatcher {
+    pub enabled: bool,
+    pub timer: Timer,
+    /// When anything in `server/assets` was last changed.
+    pub modified: Option<SystemTime>,
+}
+
+impl Default for AssetWatcher {
+    fn default() -> Self {
+        Self {
+            enabled: env::var("WATCH_ASSETS").map_or(false, |watch| watch == "true"),
+            timer: Timer::from_seconds(1.0, true),
+            modified: None,
+        }
+    }
+}
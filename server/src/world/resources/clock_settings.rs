@@ -0,0 +1,19 @@
+use std::env;
+
+/// How many real minutes a day in the game lasts. Defaults to 120,
+/// or `GAME_DAY_MINUTES` from env.
+pub struct ClockSettings {
+    pub day_minutes: f64,
+}
+
+impl Default for ClockSettings {
+    fn default() -> Self {
+        Self {
+            day_minutes: env::var("GAME_DAY_MINUTES")
+                .ok()
+                .and_then(|minutes| minutes.parse().ok())
+                .filter(|minutes| *minutes > 0.0)
+                .unwrap_or(120.0),
+        }
+    }
+}
@@ -1,2 +1,7 @@
+pub mod asset_watcher;
+pub mod clock_settings;
 pub mod new_player_spawn;
+pub mod taken_placements;
+pub mod weather;
 pub mod world_time;
+pub mod zones;
@@ -1,9 +1,15 @@
 use bevy::prelude::*;
 
-pub struct NewPlayerSpawn(pub IVec2);
+pub struct NewPlayerSpawn {
+    pub zone: String,
+    pub position: IVec2,
+}
 
 impl Default for NewPlayerSpawn {
     fn default() -> Self {
-        Self(IVec2::new(0, 0))
+        Self {
+            zone: "".to_string(),
+            position: IVec2::new(0, 0),
+        }
     }
 }
@@ -0,0 +1,8 @@
+use bevy::{prelude::*, utils::HashMap};
+
+/// How many of each placement, by [`Placement::key`], players have taken out of
+/// their levels for good, so reloading levels doesn't put them back.
+///
+/// [`Placement::key`]: crate::world::utils::levels::Placement::key
+#[derive(Default)]
+pub struct TakenPlacements(pub HashMap<(String, IVec2, String), u32>);
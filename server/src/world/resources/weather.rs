@@ -0,0 +1,142 @@
+use bevy::utils::HashMap;
+use yansi::Color;
+
+use crate::visual::palette::Palette;
+
+use super::world_time::Season;
+
+/// How many game hours pass between each chance for the weather to change.
+pub const WEATHER_HOURS: f64 = 2.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WeatherKind {
+    Clear,
+    Cloudy,
+    Rain,
+    Storm,
+    Snow,
+}
+
+impl WeatherKind {
+    /// What the weather turns into next, picked by `roll` (from 0 to 1).
+    /// Anything falling from the sky in winter falls as snow.
+    pub fn next(self, season: Season, roll: f32) -> Self {
+        let chances: &[(WeatherKind, f32)] = match self {
+            WeatherKind::Clear => &[(WeatherKind::Clear, 0.7), (WeatherKind::Cloudy, 0.3)],
+            WeatherKind::Cloudy => &[
+                (WeatherKind::Clear, 0.3),
+                (WeatherKind::Cloudy, 0.4),
+                (WeatherKind::Rain, 0.3),
+            ],
+            WeatherKind::Rain | WeatherKind::Snow => &[
+                (WeatherKind::Cloudy, 0.4),
+                (WeatherKind::Rain, 0.4),
+                (WeatherKind::Storm, 0.2),
+            ],
+            WeatherKind::Storm => &[(WeatherKind::Rain, 0.7), (WeatherKind::Storm, 0.3)],
+        };
+
+        let mut total = 0.0;
+
+        let next = chances
+            .iter()
+            .find(|(_, chance)| {
+                total += chance;
+                roll < total
+            })
+            .map_or(self, |(kind, _)| *kind);
+
+        match (next, season) {
+            (WeatherKind::Rain | WeatherKind::Storm, Season::Winter) => WeatherKind::Snow,
+            (next, _) => next,
+        }
+    }
+
+    /// How far daylight reaches through the weather.
+    pub fn sight_radius(self, ambient: i32) -> i32 {
+        match self {
+            WeatherKind::Clear | WeatherKind::Cloudy => ambient,
+            WeatherKind::Rain => ambient.min(12),
+            WeatherKind::Snow => ambient.min(8),
+            WeatherKind::Storm => ambient.min(5),
+        }
+    }
+
+    /// The color outdoor backgrounds are tinted towards, and by how much.
+    pub fn tint(self, palette: &Palette) -> Option<(Color, f32)> {
+        match self {
+            WeatherKind::Clear => None,
+            WeatherKind::Cloudy => Some((palette.slate[5], 0.15)),
+            WeatherKind::Rain => Some((palette.blue[9], 0.25)),
+            WeatherKind::Storm => Some((palette.slate[9], 0.4)),
+            WeatherKind::Snow => Some((palette.slate[1], 0.3)),
+        }
+    }
+
+    /// How much faster than usual a [`Crop`](crate::world::components::crop::Crop)
+    /// grows. Rain helps, storms flatten and snow stops it.
+    pub fn growth_rate(self) -> f64 {
+        match self {
+            WeatherKind::Clear | WeatherKind::Cloudy => 1.0,
+            WeatherKind::Rain => 1.5,
+            WeatherKind::Storm => 0.5,
+            WeatherKind::Snow => 0.0,
+        }
+    }
+
+    /// How much likelier than usual a bite is at a
+    /// [`FishingSpot`](crate::items::components::fishing_spot::FishingSpot).
+    pub fn bite_rate(self) -> f32 {
+        match self {
+            WeatherKind::Clear => 1.0,
+            WeatherKind::Cloudy | WeatherKind::Rain => 1.25,
+            WeatherKind::Storm => 0.25,
+            WeatherKind::Snow => 0.5,
+        }
+    }
+
+    pub fn describe(self) -> &'static str {
+        match self {
+            WeatherKind::Clear => "The sky is clear.",
+            WeatherKind::Cloudy => "Grey clouds hang overhead.",
+            WeatherKind::Rain => "It's raining.",
+            WeatherKind::Storm => "A storm is raging.",
+            WeatherKind::Snow => "Snow is falling.",
+        }
+    }
+}
+
+/// The weather in each zone. Zones without any are clear.
+#[derive(Default)]
+pub struct Weather(pub HashMap<String, WeatherKind>);
+
+impl Weather {
+    pub fn get(&self, zone: &str) -> WeatherKind {
+        self.0.get(zone).copied().unwrap_or(WeatherKind::Clear)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Season, WeatherKind};
+
+    #[test]
+    fn next() {
+        assert_eq!(
+            WeatherKind::Clear.next(Season::Summer, 0.1),
+            WeatherKind::Clear
+        );
+        assert_eq!(
+            WeatherKind::Clear.next(Season::Summer, 0.9),
+            WeatherKind::Cloudy
+        );
+        assert_eq!(
+            WeatherKind::Cloudy.next(Season::Spring, 0.9),
+            WeatherKind::Rain
+        );
+        assert_eq!(
+            WeatherKind::Cloudy.next(Season::Winter, 0.9),
+            WeatherKind::Snow
+        );
+    }
+}
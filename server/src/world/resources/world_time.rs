@@ -1,22 +1,200 @@
-use chrono::{DateTime, Local as LocalTime};
+use serde::{Deserialize, Serialize};
 
+pub const MINUTES_PER_HOUR: f64 = 60.0;
+pub const HOURS_PER_DAY: f64 = 24.0;
+pub const DAYS_PER_MONTH: i64 = 30;
+
+/// Every month of the year in order, three to a [`Season`].
+pub const MONTHS: [&str; 12] = [
+    "Thaw",
+    "Bloom",
+    "Rain",
+    "Highsun",
+    "Haymaking",
+    "Harvest",
+    "Fall",
+    "Frost",
+    "Fog",
+    "Snow",
+    "Ice",
+    "Wolf",
+];
+
+/// The in-game clock, counted in minutes since the world began.
 pub struct WorldTime {
-    pub time: DateTime<LocalTime>,
+    pub minutes: f64,
     pub part: WorldTimeTag,
 }
 
 impl Default for WorldTime {
+    /// The world begins at 5am on its first day.
     fn default() -> Self {
         Self {
-            time: LocalTime::now(),
+            minutes: 5.0 * MINUTES_PER_HOUR,
             part: WorldTimeTag::Dawn,
         }
     }
 }
 
+impl WorldTime {
+    fn days(&self) -> i64 {
+        (self.minutes / MINUTES_PER_HOUR / HOURS_PER_DAY) as i64
+    }
+
+    pub fn hour(&self) -> u32 {
+        ((self.minutes / MINUTES_PER_HOUR) % HOURS_PER_DAY) as u32
+    }
+
+    pub fn minute(&self) -> u32 {
+        (self.minutes % MINUTES_PER_HOUR) as u32
+    }
+
+    /// Day of the month, starting from 1.
+    pub fn day(&self) -> u32 {
+        (self.days() % DAYS_PER_MONTH) as u32 + 1
+    }
+
+    /// Index into [`MONTHS`].
+    pub fn month(&self) -> usize {
+        ((self.days() / DAYS_PER_MONTH) % MONTHS.len() as i64) as usize
+    }
+
+    /// Year, starting from 1.
+    pub fn year(&self) -> u32 {
+        (self.days() / DAYS_PER_MONTH / MONTHS.len() as i64) as u32 + 1
+    }
+
+    pub fn season(&self) -> Season {
+        match self.month() / 3 {
+            0 => Season::Spring,
+            1 => Season::Summer,
+            2 => Season::Autumn,
+            _ => Season::Winter,
+        }
+    }
+
+    /// The time on a 12 hour clock, like `5:07am`.
+    pub fn clock(&self) -> String {
+        let hour = self.hour();
+
+        format!(
+            "{}:{:02}{}",
+            match hour % 12 {
+                0 => 12,
+                hour => hour,
+            },
+            self.minute(),
+            if hour < 12 { "am" } else { "pm" }
+        )
+    }
+
+    /// The date, like `3rd of Bloom, year 1`.
+    pub fn date(&self) -> String {
+        let day = self.day();
+
+        let suffix = match (day % 10, day % 100) {
+            (_, 11..=13) => "th",
+            (1, _) => "st",
+            (2, _) => "nd",
+            (3, _) => "rd",
+            _ => "th",
+        };
+
+        format!(
+            "{day}{suffix} of {}, year {}",
+            MONTHS[self.month()],
+            self.year()
+        )
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
 pub enum WorldTimeTag {
     Dawn,
     Day,
     Night,
     Dusk,
 }
+
+impl WorldTimeTag {
+    /// Which part of the day an hour, from 0 to 23, falls in.
+    pub fn from_hour(hour: u32) -> Self {
+        match hour {
+            // 5am - 6am is Dawn
+            5 => WorldTimeTag::Dawn,
+            // 6am - 7pm is Day
+            6..=19 => WorldTimeTag::Day,
+            // 8pm is Dusk
+            20 => WorldTimeTag::Dusk,
+            // 8pm - 4am is Night
+            _ => WorldTimeTag::Night,
+        }
+    }
+
+    /// Shift an RGB color for this time of day: darker and bluer at night,
+    /// and warmer at dawn and dusk.
+    pub fn shift(&self, rgb: [u8; 3]) -> [u8; 3] {
+        let [r, g, b] = match self {
+            WorldTimeTag::Day => return rgb,
+            WorldTimeTag::Dawn | WorldTimeTag::Dusk => [1.15, 0.95, 0.8],
+            WorldTimeTag::Night => [0.4, 0.45, 0.7],
+        };
+
+        [
+            (rgb[0] as f32 * r).min(255.0) as u8,
+            (rgb[1] as f32 * g).min(255.0) as u8,
+            (rgb[2] as f32 * b).min(255.0) as u8,
+        ]
+    }
+
+    /// How far a player can see without a [`Light`](crate::visual::components::light::Light) nearby.
+    pub fn light_radius(&self) -> i32 {
+        match self {
+            WorldTimeTag::Day => 32,
+            WorldTimeTag::Dawn | WorldTimeTag::Dusk => 8,
+            WorldTimeTag::Night => 3,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Season, WorldTime, WorldTimeTag};
+
+    #[test]
+    fn shift() {
+        assert_eq!(WorldTimeTag::Day.shift([100, 100, 100]), [100, 100, 100]);
+        assert_eq!(WorldTimeTag::Dusk.shift([100, 100, 100]), [115, 95, 80]);
+        assert_eq!(WorldTimeTag::Night.shift([100, 100, 100]), [40, 45, 70]);
+        assert_eq!(WorldTimeTag::Dawn.shift([250, 0, 0]), [255, 0, 0]);
+    }
+
+    #[test]
+    fn calendar() {
+        let time = WorldTime {
+            // Day 73 (the 13th of the 3rd month), at 5:07pm.
+            minutes: (72.0 * 24.0 + 17.0) * 60.0 + 7.0,
+            part: WorldTimeTag::Day,
+        };
+
+        assert_eq!(time.clock(), "5:07pm");
+        assert_eq!(time.date(), "13th of Rain, year 1");
+        assert_eq!(time.season(), Season::Spring);
+
+        let time = WorldTime {
+            minutes: 360.0 * 24.0 * 60.0,
+            part: WorldTimeTag::Night,
+        };
+
+        assert_eq!(time.clock(), "12:00am");
+        assert_eq!(time.date(), "1st of Thaw, year 2");
+    }
+}
@@ -0,0 +1,76 @@
+use bevy::{prelude::*, utils::HashMap};
+
+/// Where a zone sits in the LDtk world.
+pub struct ZoneLayout {
+    /// The level's `worldDepth`, which we treat as its floor.
+    pub depth: i32,
+    /// Top-left corner of the level in world grid cells.
+    pub offset: IVec2,
+    /// Width and height of the level in grid cells.
+    pub size: IVec2,
+}
+
+/// Layout of every zone, used to work out which zones are stacked
+/// on top of each other.
+#[derive(Default)]
+pub struct Zones(pub HashMap<String, ZoneLayout>);
+
+impl Zones {
+    /// Find the zone `floors` above (or below, if negative) a position,
+    /// along with where that position lands in it.
+    pub fn stacked(&self, zone: &str, position: IVec2, floors: i32) -> Option<(String, IVec2)> {
+        let layout = self.0.get(zone)?;
+        let world = layout.offset + position;
+
+        self.0
+            .iter()
+            .find(|(_, other)| {
+                other.depth == layout.depth + floors
+                    && world.x >= other.offset.x
+                    && world.y >= other.offset.y
+                    && world.x < other.offset.x + other.size.x
+                    && world.y < other.offset.y + other.size.y
+            })
+            .map(|(name, other)| (name.clone(), world - other.offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::{ZoneLayout, Zones};
+
+    #[test]
+    fn stacked() {
+        let mut zones = Zones::default();
+
+        zones.0.insert(
+            "Ground".into(),
+            ZoneLayout {
+                depth: 0,
+                offset: IVec2::new(0, 0),
+                size: IVec2::new(10, 10),
+            },
+        );
+
+        zones.0.insert(
+            "Attic".into(),
+            ZoneLayout {
+                depth: 1,
+                offset: IVec2::new(2, 2),
+                size: IVec2::new(4, 4),
+            },
+        );
+
+        assert_eq!(
+            zones.stacked("Ground", IVec2::new(3, 4), 1),
+            Some(("Attic".into(), IVec2::new(1, 2)))
+        );
+        assert_eq!(
+            zones.stacked("Attic", IVec2::new(1, 2), -1),
+            Some(("Ground".into(), IVec2::new(3, 4)))
+        );
+        assert_eq!(zones.stacked("Ground", IVec2::new(8, 8), 1), None);
+    }
+}
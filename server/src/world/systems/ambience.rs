@@ -0,0 +1,183 @@
+use bevy::prelude::*;
+
+use crate::{
+    network::events::NetworkOutput,
+    player::components::{client::NetworkClient, online::Online},
+    spatial::components::{indoors::Indoors, position::Position, tile::Tile, zone::Zone},
+    world::{
+        events::{weather_event::WeatherEvent, world_time_event::WorldTimeEvent},
+        resources::{weather::WeatherKind, world_time::WorldTimeTag},
+    },
+};
+
+/// Let everyone outdoors know when the sun comes up or goes down,
+/// or the weather where they are turns.
+pub fn ambience(
+    mut world_time_events: EventReader<WorldTimeEvent>,
+    mut weather_events: EventReader<WeatherEvent>,
+    mut output: EventWriter<NetworkOutput>,
+    players: Query<(&NetworkClient, &Position, &Zone), With<Online>>,
+    indoors: Query<(&Position, &Zone), (With<Tile>, With<Indoors>)>,
+) {
+    // Messages, and the zone they're limited to if they're not for everyone.
+    let mut messages = Vec::new();
+
+    for event in world_time_events.iter() {
+        messages.push((
+            None,
+            match event.part {
+                WorldTimeTag::Dawn => "The sky to the east begins to pale as dawn breaks.",
+                WorldTimeTag::Day => "The sun climbs clear of the horizon.",
+                WorldTimeTag::Dusk => "The sun sinks low and the shadows stretch out.",
+                WorldTimeTag::Night => "Darkness falls, and the first stars come out.",
+            },
+        ));
+    }
+
+    for event in weather_events.iter() {
+        messages.push((
+            Some(&event.zone),
+            match event.weather {
+                WeatherKind::Clear => "The clouds part and the sky clears.",
+                WeatherKind::Cloudy => "Clouds gather overhead.",
+                WeatherKind::Rain => "It starts to rain.",
+                WeatherKind::Storm => "Thunder rumbles as a storm rolls in.",
+                WeatherKind::Snow => "Snow begins to fall.",
+            },
+        ));
+    }
+
+    for (zone_name, body) in messages {
+        for (client, position, zone) in players
+            .iter()
+            .filter(|(_, _, z)| zone_name.map_or(true, |name| *name == z.0))
+        {
+            if indoors
+                .iter()
+                .any(|(p, z)| p.0 == position.0 && z.0 == zone.0)
+            {
+                continue;
+            }
+
+            output.send(NetworkOutput {
+                id: client.id,
+                body: body.to_string(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::{
+        network::events::NetworkOutput,
+        player::components::client::NetworkClient,
+        spatial::components::indoors::Indoors,
+        test::bundles::utils::{player_bundle, tile_bundle, PlayerBundle, TileBundle},
+        world::{
+            events::{weather_event::WeatherEvent, world_time_event::WorldTimeEvent},
+            resources::{weather::WeatherKind, world_time::WorldTimeTag},
+        },
+    };
+
+    #[test]
+    fn outdoors_only() {
+        let mut app = App::new();
+
+        app.add_event::<WorldTimeEvent>();
+        app.add_event::<WeatherEvent>();
+        app.add_event::<NetworkOutput>();
+        app.add_system(super::ambience);
+
+        let outside = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let outside_client_id = app.world.get::<NetworkClient>(outside).unwrap().id;
+
+        app.world.spawn().insert_bundle(player_bundle(PlayerBundle {
+            x: 1,
+            ..Default::default()
+        }));
+
+        app.world.spawn().insert_bundle(tile_bundle(TileBundle {
+            ..Default::default()
+        }));
+
+        app.world
+            .spawn()
+            .insert_bundle(tile_bundle(TileBundle {
+                x: 1,
+                ..Default::default()
+            }))
+            .insert(Indoors);
+
+        app.world
+            .resource_mut::<Events<WorldTimeEvent>>()
+            .send(WorldTimeEvent {
+                previous: WorldTimeTag::Day,
+                part: WorldTimeTag::Dusk,
+            });
+
+        app.update();
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let outputs = output_reader.iter(output_events).collect::<Vec<_>>();
+
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(outputs[0].id, outside_client_id);
+        assert_eq!(
+            outputs[0].body,
+            "The sun sinks low and the shadows stretch out."
+        );
+    }
+
+    #[test]
+    fn weather_in_zone() {
+        let mut app = App::new();
+
+        app.add_event::<WorldTimeEvent>();
+        app.add_event::<WeatherEvent>();
+        app.add_event::<NetworkOutput>();
+        app.add_system(super::ambience);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        app.world.spawn().insert_bundle(player_bundle(PlayerBundle {
+            zone: "Elsewhere".into(),
+            ..Default::default()
+        }));
+
+        app.world
+            .resource_mut::<Events<WeatherEvent>>()
+            .send(WeatherEvent {
+                zone: "Test".into(),
+                weather: WeatherKind::Rain,
+            });
+
+        app.update();
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let outputs = output_reader.iter(output_events).collect::<Vec<_>>();
+
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(outputs[0].id, player_client_id);
+        assert_eq!(outputs[0].body, "It starts to rain.");
+    }
+}
@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+
+use crate::{
+    spatial::components::{collider::Collider, door::Door},
+    visual::components::{details::Details, sprite::Sprite},
+    world::components::refresh::Refresh,
+};
+
+/// Copy [`Details`] and [`Sprite`] from each [`Refresh`] onto the entity it's for,
+/// then get rid of it. Doors keep looking open or closed, whatever their prototype says.
+pub fn apply_refresh(
+    mut commands: Commands,
+    refreshes: Query<(
+        Entity,
+        &Refresh,
+        Option<&Details>,
+        Option<&Sprite>,
+        Option<&Door>,
+    )>,
+    mut targets: Query<
+        (
+            Option<&mut Details>,
+            Option<&mut Sprite>,
+            Option<&mut Door>,
+            Option<&Collider>,
+        ),
+        Without<Refresh>,
+    >,
+) {
+    for (entity, refresh, details, sprite, door) in refreshes.iter() {
+        if let Ok((target_details, target_sprite, target_door, collider)) =
+            targets.get_mut(refresh.0)
+        {
+            if let (Some(mut target), Some(details)) = (target_details, details) {
+                *target = details.clone();
+            }
+
+            if let (Some(mut target), Some(door)) = (target_door, door) {
+                *target = door.clone();
+            }
+
+            if let (Some(mut target), Some(sprite)) = (target_sprite, sprite) {
+                let mut sprite = sprite.clone();
+
+                if let Some(door) = door {
+                    sprite.character = if collider.is_some() {
+                        door.closed_character.clone()
+                    } else {
+                        door.opened_character.clone()
+                    };
+                }
+
+                *target = sprite;
+            }
+        }
+
+        commands.entity(entity).despawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{
+        spatial::components::door::Door,
+        test::bundles::utils::{open_door_bundle, DoorBundle},
+        visual::components::{details::Details, sprite::Sprite},
+        world::components::refresh::Refresh,
+    };
+
+    #[test]
+    fn open_door() {
+        let mut app = App::new();
+
+        app.add_system(super::apply_refresh);
+
+        let door = app
+            .world
+            .spawn()
+            .insert_bundle(open_door_bundle(DoorBundle {
+                name: "Door".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        let refresh = app
+            .world
+            .spawn()
+            .insert_bundle((
+                Refresh(door),
+                Door {
+                    opened_character: "'".into(),
+                    closed_character: "+".into(),
+                },
+                Details {
+                    name: "Oak Door".into(),
+                    description: "A sturdy oak door.".into(),
+                    times: Default::default(),
+                },
+                Sprite {
+                    character: "+".into(),
+                    color: "92400E".into(),
+                    background: None,
+                },
+            ))
+            .id();
+
+        app.update();
+
+        assert!(app.world.get_entity(refresh).is_none());
+        assert_eq!(app.world.get::<Details>(door).unwrap().name, "Oak Door");
+
+        let sprite = app.world.get::<Sprite>(door).unwrap();
+
+        assert_eq!(sprite.character, "'");
+        assert_eq!(sprite.color, "92400E");
+    }
+}
@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::{
+    network::events::{NetworkInput, NetworkOutput},
+    player::{
+        components::{client::NetworkClient, online::Online},
+        utils::players::Players,
+    },
+    world::resources::world_time::WorldTime,
+};
+
+/// Handles the `time` command, telling the player the time, date and season.
+pub fn calendar(
+    world_time: Res<WorldTime>,
+    mut input: EventReader<NetworkInput>,
+    mut output: EventWriter<NetworkOutput>,
+    players: Players<&NetworkClient, With<Online>>,
+) {
+    lazy_static! {
+        static ref CMD: Regex = Regex::new("^(time|date|calendar)$").unwrap();
+    }
+
+    for message in input.iter() {
+        if CMD.is_match(&message.body.to_lowercase()) {
+            if let Some(client) = players.sender(&message.id) {
+                let season = format!("{:?}", world_time.season()).to_lowercase();
+
+                output.send(NetworkOutput {
+                    id: client.id,
+                    body: format!(
+                        "It's {} on the {}, in {season}.",
+                        world_time.clock(),
+                        world_time.date(),
+                    ),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::{
+        network::events::{NetworkInput, NetworkOutput},
+        player::{
+            components::client::NetworkClient, resources::player_index::PlayerIndex,
+            systems::index_players::index_players,
+        },
+        test::bundles::utils::{player_bundle, PlayerBundle},
+        world::resources::world_time::{WorldTime, WorldTimeTag},
+    };
+
+    #[test]
+    fn calendar() {
+        let mut app = App::new();
+
+        app.insert_resource(WorldTime {
+            minutes: (100.0 * 24.0 + 9.0) * 60.0 + 30.0,
+            part: WorldTimeTag::Day,
+        });
+        app.add_event::<NetworkInput>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::calendar);
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        app.world
+            .resource_mut::<Events<NetworkInput>>()
+            .send(NetworkInput {
+                id: player_client_id,
+                body: "time".into(),
+                internal: false,
+            });
+
+        app.update();
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let output = output_reader.iter(output_events).next().unwrap();
+
+        assert_eq!(
+            output.body,
+            "It's 9:30am on the 11th of Highsun, year 1, in summer."
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::{
+    network::events::{NetworkInput, NetworkOutput},
+    player::{
+        components::{client::NetworkClient, online::Online},
+        utils::players::Players,
+    },
+    spatial::components::{indoors::Indoors, position::Position, tile::Tile, zone::Zone},
+    world::resources::weather::Weather,
+};
+
+/// Handles the `weather` command, as long as the player can see the sky.
+pub fn forecast(
+    weather: Res<Weather>,
+    mut input: EventReader<NetworkInput>,
+    mut output: EventWriter<NetworkOutput>,
+    players: Players<(&NetworkClient, &Position, &Zone), With<Online>>,
+    indoors: Query<(&Position, &Zone), (With<Tile>, With<Indoors>)>,
+) {
+    lazy_static! {
+        static ref CMD: Regex = Regex::new("^(weather|sky)$").unwrap();
+    }
+
+    for message in input.iter() {
+        if CMD.is_match(&message.body.to_lowercase()) {
+            if let Some((client, position, zone)) = players.sender(&message.id) {
+                let body = if indoors
+                    .iter()
+                    .any(|(p, z)| p.0 == position.0 && z.0 == zone.0)
+                {
+                    "You'll have to go outside to see the sky."
+                } else {
+                    weather.get(&zone.0).describe()
+                };
+
+                output.send(NetworkOutput {
+                    id: client.id,
+                    body: body.to_string(),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::{
+        network::events::{NetworkInput, NetworkOutput},
+        player::{
+            components::client::NetworkClient, resources::player_index::PlayerIndex,
+            systems::index_players::index_players,
+        },
+        spatial::components::indoors::Indoors,
+        test::bundles::utils::{player_bundle, tile_bundle, PlayerBundle, TileBundle},
+        world::resources::weather::{Weather, WeatherKind},
+    };
+
+    #[test]
+    fn forecast() {
+        let mut app = App::new();
+
+        app.init_resource::<Weather>();
+        app.add_event::<NetworkInput>();
+        app.add_event::<NetworkOutput>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::forecast);
+
+        app.world
+            .resource_mut::<Weather>()
+            .0
+            .insert("Test".into(), WeatherKind::Rain);
+
+        let outside = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                ..Default::default()
+            }))
+            .id();
+
+        let outside_client_id = app.world.get::<NetworkClient>(outside).unwrap().id;
+
+        let inside = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                x: 1,
+                ..Default::default()
+            }))
+            .id();
+
+        let inside_client_id = app.world.get::<NetworkClient>(inside).unwrap().id;
+
+        app.world
+            .spawn()
+            .insert_bundle(tile_bundle(TileBundle {
+                x: 1,
+                ..Default::default()
+            }))
+            .insert(Indoors);
+
+        for id in [outside_client_id, inside_client_id] {
+            app.world
+                .resource_mut::<Events<NetworkInput>>()
+                .send(NetworkInput {
+                    id,
+                    body: "weather".into(),
+                    internal: false,
+                });
+        }
+
+        app.update();
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let outputs = output_reader.iter(output_events).collect::<Vec<_>>();
+
+        assert_eq!(outputs[0].body, "It's raining.");
+        assert_eq!(outputs[1].body, "You'll have to go outside to see the sky.");
+    }
+}
@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+
+use crate::{
+    spatial::components::zone::Zone,
+    visual::components::sprite::Sprite,
+    world::{
+        components::crop::Crop,
+        resources::{
+            weather::Weather,
+            world_time::{WorldTime, MINUTES_PER_HOUR},
+        },
+    },
+};
+
+/// Grow every [`Crop`] as the clock moves on, faster or slower with the
+/// [`Weather`] in its zone, and redraw any that reach a new stage.
+pub fn grow_crops(
+    world_time: Res<WorldTime>,
+    weather: Res<Weather>,
+    mut last_minutes: Local<Option<f64>>,
+    mut crops: Query<(&Zone, &mut Crop, &mut Sprite)>,
+) {
+    let hours =
+        (world_time.minutes - *last_minutes.get_or_insert(world_time.minutes)) / MINUTES_PER_HOUR;
+
+    if hours <= 0.0 {
+        return;
+    }
+
+    *last_minutes = Some(world_time.minutes);
+
+    for (zone, mut crop, mut sprite) in crops.iter_mut() {
+        crop.grown += hours * weather.get(&zone.0).growth_rate();
+
+        if let Some(stage) = crop.stage().filter(|stage| **stage != sprite.character) {
+            sprite.character = stage.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{
+        spatial::components::zone::Zone,
+        visual::components::sprite::Sprite,
+        world::{
+            components::crop::Crop,
+            resources::{
+                weather::{Weather, WeatherKind},
+                world_time::WorldTime,
+            },
+        },
+    };
+
+    #[test]
+    fn grows_faster_in_rain() {
+        let mut app = App::new();
+
+        app.insert_resource(WorldTime::default());
+        app.init_resource::<Weather>();
+        app.add_system(super::grow_crops);
+
+        app.world
+            .resource_mut::<Weather>()
+            .0
+            .insert("Wet".into(), WeatherKind::Rain);
+
+        let mut crops = Vec::new();
+
+        for zone in ["Dry", "Wet"] {
+            crops.push(
+                app.world
+                    .spawn()
+                    .insert_bundle((
+                        Zone(zone.into()),
+                        Crop {
+                            stages: vec![".".into(), ",".into(), "Y".into()],
+                            hours_per_stage: 2.0,
+                            grown: 0.0,
+                        },
+                        Sprite {
+                            character: ".".into(),
+                            color: "65A30D".into(),
+                            background: None,
+                        },
+                    ))
+                    .id(),
+            );
+        }
+
+        app.update();
+
+        app.world.resource_mut::<WorldTime>().minutes += 3.0 * 60.0;
+
+        app.update();
+
+        let characters = crops
+            .iter()
+            .map(|crop| app.world.get::<Sprite>(*crop).unwrap().character.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(characters, vec![",", "Y"]);
+    }
+}
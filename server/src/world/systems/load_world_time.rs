@@ -0,0 +1,42 @@
+use std::thread;
+
+use api::world_time::handlers::GetWorldTimeResponse;
+use bevy::{prelude::*, utils::Duration};
+
+use crate::world::{
+    resources::world_time::{WorldTime, WorldTimeTag},
+    utils::api::get_world_time,
+};
+
+/// How many times to ask the API for the world time before giving up.
+pub const WORLD_TIME_ATTEMPTS: u32 = 3;
+
+/// How long to wait between attempts to reach the API.
+pub const WORLD_TIME_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Pick the clock up from wherever it was when the server last stopped, starting
+/// a new one if the API can't be reached.
+pub fn load_world_time(mut world_time: ResMut<WorldTime>) {
+    for attempt in 1..=WORLD_TIME_ATTEMPTS {
+        match get_world_time().and_then(|response| response.json::<GetWorldTimeResponse>()) {
+            Ok(response) => {
+                if response.minutes > 0.0 {
+                    world_time.minutes = response.minutes;
+                    world_time.part = WorldTimeTag::from_hour(world_time.hour());
+                }
+
+                break;
+            }
+            Err(error) if attempt < WORLD_TIME_ATTEMPTS => {
+                warn!("Could not load world time, trying again: {error}");
+
+                thread::sleep(WORLD_TIME_RETRY_DELAY);
+            }
+            Err(error) => {
+                warn!("Could not load world time, starting a new clock: {error}");
+            }
+        }
+    }
+
+    info!("It's {} on the {}", world_time.clock(), world_time.date());
+}
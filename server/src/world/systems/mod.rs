@@ -1,2 +1,15 @@
+pub mod ambience;
+pub mod apply_refresh;
+pub mod calendar;
+pub mod forecast;
+pub mod grow_crops;
+pub mod load_world_time;
+pub mod operate_doors;
+pub mod reload;
+pub mod reload_levels;
+pub mod reload_prototypes;
+pub mod save_world_time;
 pub mod setup_world;
+pub mod update_weather;
 pub mod update_world_time;
+pub mod watch_assets;
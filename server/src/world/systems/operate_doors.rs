@@ -0,0 +1,203 @@
+use bevy::prelude::*;
+
+use crate::{
+    player::components::character::Character,
+    spatial::components::{
+        auto_close::AutoClose, collider::Collider, door::Door, gate_schedule::GateSchedule,
+        position::Position, zone::Zone,
+    },
+    visual::components::sprite::Sprite,
+    world::resources::world_time::WorldTime,
+};
+
+/// Opens and closes doors with an [`AutoClose`] or [`GateSchedule`].
+///
+/// Doors won't close on anyone standing in them, and will try again once they've moved.
+pub fn operate_doors(
+    mut commands: Commands,
+    time: Res<Time>,
+    world_time: Res<WorldTime>,
+    characters: Query<(&Position, &Zone), With<Character>>,
+    mut doors: Query<
+        (
+            Entity,
+            &Door,
+            &Position,
+            &Zone,
+            &mut Sprite,
+            Option<&Collider>,
+            Option<&mut AutoClose>,
+            Option<&mut GateSchedule>,
+        ),
+        Without<Character>,
+    >,
+) {
+    for (entity, door, position, zone, mut sprite, collider, auto_close, schedule) in
+        doors.iter_mut()
+    {
+        let closed = collider.is_some();
+
+        let mut auto_close = auto_close;
+
+        if let Some(auto_close) = &mut auto_close {
+            if closed {
+                auto_close.elapsed = 0.0;
+            } else {
+                auto_close.elapsed += time.delta_seconds();
+            }
+        }
+
+        // A change in the time of day wins over a door that's due to close.
+        let close = schedule
+            .as_ref()
+            .filter(|schedule| schedule.applied != Some(world_time.part))
+            .map(|schedule| !schedule.open.contains(&world_time.part))
+            .or_else(|| {
+                auto_close
+                    .as_ref()
+                    .filter(|auto_close| auto_close.elapsed >= auto_close.after)
+                    .map(|_| true)
+            });
+
+        match close {
+            Some(true) if !closed => {
+                if characters
+                    .iter()
+                    .any(|(p, z)| p.0 == position.0 && z.0 == zone.0)
+                {
+                    continue;
+                }
+
+                sprite.character = door.closed_character.clone();
+                commands.entity(entity).insert(Collider);
+
+                if let Some(mut auto_close) = auto_close {
+                    auto_close.elapsed = 0.0;
+                }
+            }
+            Some(false) if closed => {
+                sprite.character = door.opened_character.clone();
+                commands.entity(entity).remove::<Collider>();
+            }
+            _ => {}
+        }
+
+        if let Some(mut schedule) = schedule {
+            schedule.applied = Some(world_time.part);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{
+        spatial::components::{
+            auto_close::AutoClose, collider::Collider, gate_schedule::GateSchedule,
+        },
+        test::bundles::utils::{
+            closed_door_bundle, open_door_bundle, player_bundle, DoorBundle, PlayerBundle,
+        },
+        visual::components::sprite::Sprite,
+        world::resources::world_time::{WorldTime, WorldTimeTag},
+    };
+
+    #[test]
+    fn auto_close() {
+        let mut app = App::new();
+
+        app.init_resource::<Time>();
+        app.insert_resource(WorldTime::default());
+        app.add_system(super::operate_doors);
+
+        let door = app
+            .world
+            .spawn()
+            .insert_bundle(open_door_bundle(DoorBundle {
+                ..Default::default()
+            }))
+            .insert(AutoClose {
+                after: 5.0,
+                elapsed: 5.0,
+            })
+            .id();
+
+        app.update();
+
+        assert!(app.world.get::<Collider>(door).is_some());
+        assert_eq!(app.world.get::<Sprite>(door).unwrap().character, "|");
+        assert_eq!(app.world.get::<AutoClose>(door).unwrap().elapsed, 0.0);
+    }
+
+    #[test]
+    fn blocked() {
+        let mut app = App::new();
+
+        app.init_resource::<Time>();
+        app.insert_resource(WorldTime::default());
+        app.add_system(super::operate_doors);
+
+        app.world.spawn().insert_bundle(player_bundle(PlayerBundle {
+            ..Default::default()
+        }));
+
+        let door = app
+            .world
+            .spawn()
+            .insert_bundle(open_door_bundle(DoorBundle {
+                ..Default::default()
+            }))
+            .insert(AutoClose {
+                after: 5.0,
+                elapsed: 5.0,
+            })
+            .id();
+
+        app.update();
+
+        assert!(app.world.get::<Collider>(door).is_none());
+    }
+
+    #[test]
+    fn schedule() {
+        let mut app = App::new();
+
+        app.init_resource::<Time>();
+        app.insert_resource(WorldTime {
+            part: WorldTimeTag::Night,
+            ..Default::default()
+        });
+        app.add_system(super::operate_doors);
+
+        let gate = app
+            .world
+            .spawn()
+            .insert_bundle(closed_door_bundle(DoorBundle {
+                ..Default::default()
+            }))
+            .insert(GateSchedule {
+                open: vec![WorldTimeTag::Day],
+                applied: None,
+            })
+            .id();
+
+        app.update();
+
+        assert!(app.world.get::<Collider>(gate).is_some());
+
+        app.world.resource_mut::<WorldTime>().part = WorldTimeTag::Day;
+
+        app.update();
+
+        assert!(app.world.get::<Collider>(gate).is_none());
+        assert_eq!(app.world.get::<Sprite>(gate).unwrap().character, "/");
+
+        // Closing it by hand sticks until the time of day changes again.
+        app.world.entity_mut(gate).insert(Collider);
+
+        app.update();
+
+        assert!(app.world.get::<Collider>(gate).is_some());
+    }
+}
@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::{
+    network::events::{NetworkInput, NetworkOutput},
+    player::{
+        components::{character::Character, client::NetworkClient, online::Online},
+        resources::admins::Admins,
+        utils::players::Players,
+    },
+    world::events::reload_event::ReloadEvent,
+};
+
+/// Handles the `reload` command, letting [`Admins`] pick up changes
+/// to levels and prototypes without a restart.
+pub fn reload(
+    admins: Res<Admins>,
+    mut input: EventReader<NetworkInput>,
+    mut output: EventWriter<NetworkOutput>,
+    mut reloads: EventWriter<ReloadEvent>,
+    players: Players<(&NetworkClient, &Character), With<Online>>,
+) {
+    lazy_static! {
+        static ref CMD: Regex = Regex::new("^reload$").unwrap();
+    }
+
+    for message in input.iter() {
+        if CMD.is_match(&message.body.to_lowercase()) {
+            if let Some((client, character)) = players.sender(&message.id) {
+                if !admins.0.contains(&character.name) {
+                    output.send(NetworkOutput {
+                        id: client.id,
+                        body: "You can't do that.".to_string(),
+                    });
+
+                    continue;
+                }
+
+                reloads.send(ReloadEvent);
+
+                output.send(NetworkOutput {
+                    id: client.id,
+                    body: "Reloading levels and prototypes.".to_string(),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::{
+        network::events::{NetworkInput, NetworkOutput},
+        player::{
+            components::client::NetworkClient,
+            resources::{admins::Admins, player_index::PlayerIndex},
+            systems::index_players::index_players,
+        },
+        test::bundles::utils::{player_bundle, PlayerBundle},
+        world::events::reload_event::ReloadEvent,
+    };
+
+    #[test]
+    fn admins_only() {
+        let mut app = App::new();
+
+        app.insert_resource(Admins(vec!["Amri".into()]));
+        app.add_event::<NetworkInput>();
+        app.add_event::<NetworkOutput>();
+        app.add_event::<ReloadEvent>();
+        app.init_resource::<PlayerIndex>();
+        app.add_system_to_stage(CoreStage::PreUpdate, index_players);
+        app.add_system(super::reload);
+
+        let admin = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                name: "Amri".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        let admin_client_id = app.world.get::<NetworkClient>(admin).unwrap().id;
+
+        let player = app
+            .world
+            .spawn()
+            .insert_bundle(player_bundle(PlayerBundle {
+                name: "Bex".into(),
+                ..Default::default()
+            }))
+            .id();
+
+        let player_client_id = app.world.get::<NetworkClient>(player).unwrap().id;
+
+        for id in [player_client_id, admin_client_id] {
+            app.world
+                .resource_mut::<Events<NetworkInput>>()
+                .send(NetworkInput {
+                    id,
+                    body: "reload".into(),
+                    internal: false,
+                });
+        }
+
+        app.update();
+
+        let output_events = app.world.resource::<Events<NetworkOutput>>();
+        let mut output_reader = output_events.get_reader();
+        let outputs = output_reader.iter(output_events).collect::<Vec<_>>();
+
+        assert_eq!(outputs[0].body, "You can't do that.");
+        assert_eq!(outputs[1].body, "Reloading levels and prototypes.");
+
+        let reload_events = app.world.resource::<Events<ReloadEvent>>();
+        let mut reload_reader = reload_events.get_reader();
+
+        assert_eq!(reload_reader.iter(reload_events).count(), 1);
+    }
+}
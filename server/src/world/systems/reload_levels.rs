@@ -0,0 +1,140 @@
+use std::mem;
+
+use bevy::{prelude::*, utils::HashMap};
+use bevy_proto::prelude::ProtoData;
+
+use crate::{
+    items::components::container::Container,
+    spatial::components::{exit::Exit, landmark::Landmark, position::Position, zone::Zone},
+    world::{
+        components::{from_level::FromLevel, prototype::Prototype, refresh::Refresh},
+        events::reload_event::ReloadEvent,
+        resources::{
+            new_player_spawn::NewPlayerSpawn, taken_placements::TakenPlacements, zones::Zones,
+        },
+        utils::{
+            levels::{load_levels, spawn_placement, spawn_prototype},
+            validation::check,
+        },
+    },
+};
+
+/// Bring everything spawned from levels in line with the `.ldtk` files, after
+/// `reload_prototypes` has picked up any changes to prototypes.
+///
+/// Anything no longer in a level is despawned, anything new is spawned, and
+/// anything still there gets a [`Refresh`]. Players, and whatever they've
+/// picked up or dropped, are left alone, and anything in [`TakenPlacements`]
+/// stays gone. Whatever was inside a despawned [`Container`] is left on the
+/// ground where it stood.
+pub fn reload_levels(
+    mut commands: Commands,
+    mut reloads: EventReader<ReloadEvent>,
+    (prototypes, asset_server): (Res<ProtoData>, Res<AssetServer>),
+    (mut zones, mut new_player_spawn, taken): (
+        ResMut<Zones>,
+        ResMut<NewPlayerSpawn>,
+        Res<TakenPlacements>,
+    ),
+    spawned: Query<(Entity, &Prototype, &Position, &Zone, Option<&Container>), With<FromLevel>>,
+    markers: Query<Entity, Or<(With<Exit>, With<Landmark>)>>,
+) {
+    if reloads.iter().count() == 0 {
+        return;
+    }
+
+    let levels = match load_levels() {
+        Ok(levels) => levels,
+        Err(error) => {
+            warn!("Could not reload levels, keeping the old ones: {error}");
+
+            return;
+        }
+    };
+
+    for problem in check(&levels, &prototypes) {
+        warn!("{problem}");
+    }
+
+    *zones = levels.zones;
+
+    if let Some((zone, position)) = levels.spawn {
+        new_player_spawn.zone = zone;
+        new_player_spawn.position = position;
+    }
+
+    // Exits and landmarks don't hold onto anything, so they can just be replaced.
+    for entity in markers.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    for (exit, zone, position) in levels.exits {
+        commands.spawn_bundle((exit, Position(position), Zone(zone)));
+    }
+
+    for (name, zone, position) in levels.landmarks {
+        commands.spawn_bundle((Landmark(name), Position(position), Zone(zone)));
+    }
+
+    // How many more of each placement there should be, in case some are on top of each other.
+    let mut missing = HashMap::default();
+
+    for placement in &levels.placements {
+        missing.entry(placement.key()).or_insert((placement, 0)).1 += 1;
+    }
+
+    // Less any players have taken away.
+    for (key, count) in &taken.0 {
+        if let Some(entry) = missing.get_mut(key) {
+            entry.1 = entry.1.saturating_sub(*count);
+        }
+    }
+
+    let (mut refreshed, mut despawned) = (0, 0);
+
+    for (entity, prototype, position, zone, container) in spawned.iter() {
+        let key = (zone.0.clone(), position.0, prototype.0.clone());
+
+        match missing.get_mut(&key).filter(|entry| entry.1 > 0) {
+            Some(entry) => {
+                entry.1 -= 1;
+
+                if let Some(mut copy) =
+                    spawn_prototype(&mut commands, &prototypes, &asset_server, entry.0)
+                {
+                    copy.insert(Refresh(entity));
+
+                    refreshed += 1;
+                }
+            }
+            None => {
+                for item in container.map_or(&[][..], |container| &container.items) {
+                    commands
+                        .entity(*item)
+                        .insert_bundle((position.clone(), zone.clone()));
+                }
+
+                commands.entity(entity).despawn();
+
+                despawned += 1;
+            }
+        }
+    }
+
+    let mut added = 0;
+
+    // Spawn in the order they're laid out, so they stack the same way as at startup.
+    for placement in &levels.placements {
+        let remaining = missing
+            .get_mut(&placement.key())
+            .map_or(0, |entry| mem::take(&mut entry.1));
+
+        for _ in 0..remaining {
+            if spawn_placement(&mut commands, &prototypes, &asset_server, placement) {
+                added += 1;
+            }
+        }
+    }
+
+    info!("Reloaded levels: {added} spawned, {despawned} despawned, {refreshed} refreshed");
+}
@@ -0,0 +1,26 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use bevy::{ecs::system::Command, prelude::*};
+use bevy_proto::prelude::ProtoData;
+
+use crate::world::events::reload_event::ReloadEvent;
+
+/// Read every prototype from disk again, keeping the old ones if any are broken.
+struct ReloadPrototypes;
+
+impl Command for ReloadPrototypes {
+    fn write(self, world: &mut World) {
+        match panic::catch_unwind(AssertUnwindSafe(|| ProtoData::from_world(world))) {
+            Ok(prototypes) => world.insert_resource(prototypes),
+            Err(_) => warn!("Could not reload prototypes, keeping the old ones"),
+        }
+    }
+}
+
+/// Replace [`ProtoData`] when asked to reload, in time for `reload_levels`
+/// to use it later in the frame.
+pub fn reload_prototypes(mut commands: Commands, mut reloads: EventReader<ReloadEvent>) {
+    if reloads.iter().count() > 0 {
+        commands.add(ReloadPrototypes);
+    }
+}
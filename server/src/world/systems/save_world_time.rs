@@ -0,0 +1,39 @@
+use std::thread;
+
+use bevy::prelude::*;
+
+use crate::world::{
+    resources::world_time::{WorldTime, MINUTES_PER_HOUR},
+    systems::load_world_time::{WORLD_TIME_ATTEMPTS, WORLD_TIME_RETRY_DELAY},
+    utils::api::save_world_time as save,
+};
+
+/// Save the clock once every game hour, so a restart loses at most an hour.
+/// Saving happens on a thread of its own, so neither the world nor the task
+/// pools wait on the API while it blocks and retries.
+pub fn save_world_time(world_time: Res<WorldTime>, mut last_saved: Local<Option<i64>>) {
+    let hours = (world_time.minutes / MINUTES_PER_HOUR) as i64;
+
+    // There's nothing new to save on the first run, since it was just loaded.
+    if *last_saved.get_or_insert(hours) == hours {
+        return;
+    }
+
+    *last_saved = Some(hours);
+
+    let minutes = world_time.minutes;
+
+    thread::spawn(move || {
+        for attempt in 1..=WORLD_TIME_ATTEMPTS {
+            match save(minutes) {
+                Ok(response) if response.status().is_success() => return,
+                Ok(response) => warn!("Could not save world time: {}", response.status()),
+                Err(error) => warn!("Could not save world time: {error}"),
+            }
+
+            if attempt < WORLD_TIME_ATTEMPTS {
+                thread::sleep(WORLD_TIME_RETRY_DELAY);
+            }
+        }
+    });
+}
@@ -1,22 +1,27 @@
 use bevy::prelude::*;
 use bevy_proto::prelude::ProtoData;
-use ldtk_rust::Project;
 
 use crate::{
-    spatial::components::position::Position, world::resources::new_player_spawn::NewPlayerSpawn,
+    spatial::components::{landmark::Landmark, position::Position, zone::Zone},
+    world::{
+        resources::{new_player_spawn::NewPlayerSpawn, zones::Zones},
+        utils::{
+            levels::{load_levels, spawn_placement},
+            validation::check,
+        },
+    },
 };
 
-/// Load `assets/world.ldtk` and spawn a whole lot of entities.
+/// Load every `.ldtk` project in `assets/` and spawn a whole lot of entities,
+/// with each level becoming its own [`Zone`]. Anything wrong with the levels or
+/// prototypes is logged, and whatever can't be spawned is left out.
 pub fn setup_world(
     mut commands: Commands,
     prototypes: Res<ProtoData>,
     asset_server: Res<AssetServer>,
     mut new_player_spawn: ResMut<NewPlayerSpawn>,
+    mut zones: ResMut<Zones>,
 ) {
-    let project = Project::new("server/assets/world.ldtk");
-    let level = project.get_level(0).unwrap();
-    let layers = level.layer_instances.as_ref().unwrap();
-
     debug!(
         "Known Prototypes: {:?}",
         prototypes
@@ -26,42 +31,32 @@ pub fn setup_world(
             .join(", ")
     );
 
-    for layer in layers.iter().rev() {
-        for entity in &layer.entity_instances {
-            match layer.identifier.as_str() {
-                "Spawn" => {
-                    let x = *entity.grid.get(0).unwrap() as i32;
-                    let y = *entity.grid.get(1).unwrap() as i32;
+    // There's no world to run without any levels.
+    let levels = match load_levels() {
+        Ok(levels) => levels,
+        Err(error) => panic!("Could not load levels: {error}"),
+    };
+
+    for problem in check(&levels, &prototypes) {
+        warn!("{problem}");
+    }
 
-                    new_player_spawn.0 = IVec2::new(x, y);
-                }
-                _ => {
-                    let key = entity
-                        .field_instances
-                        .get(0)
-                        .unwrap_or_else(|| panic!("Could not get `prototype` field"))
-                        .value
-                        .as_ref()
-                        .unwrap_or_else(|| panic!("Could not get `prototype` value"))
-                        .as_str()
-                        .unwrap_or_else(|| panic!("Could not get `prototype` as bool"));
+    *zones = levels.zones;
 
-                    let prototype = prototypes
-                        .get_prototype(key)
-                        .unwrap_or_else(|| panic!("Could not find `{key}` prototype"));
+    if let Some((zone, position)) = levels.spawn {
+        new_player_spawn.zone = zone;
+        new_player_spawn.position = position;
+    }
 
-                    for x in 0..(entity.width / layer.grid_size) {
-                        for y in 0..(entity.height / layer.grid_size) {
-                            let x: i32 = (x + entity.grid.get(0).unwrap()).try_into().unwrap();
-                            let y: i32 = (y + entity.grid.get(1).unwrap()).try_into().unwrap();
+    for (exit, zone, position) in levels.exits {
+        commands.spawn_bundle((exit, Position(position), Zone(zone)));
+    }
+
+    for (name, zone, position) in levels.landmarks {
+        commands.spawn_bundle((Landmark(name), Position(position), Zone(zone)));
+    }
 
-                            prototype
-                                .spawn(&mut commands, &prototypes, &asset_server)
-                                .insert(Position(IVec2::new(x, y)));
-                        }
-                    }
-                }
-            }
-        }
+    for placement in &levels.placements {
+        spawn_placement(&mut commands, &prototypes, &asset_server, placement);
     }
 }
@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+
+use crate::world::{
+    events::weather_event::WeatherEvent,
+    resources::{
+        weather::{Weather, WEATHER_HOURS},
+        world_time::{WorldTime, MINUTES_PER_HOUR},
+        zones::Zones,
+    },
+};
+
+/// Give the weather in every zone a chance to change every [`WEATHER_HOURS`].
+pub fn update_weather(
+    world_time: Res<WorldTime>,
+    zones: Res<Zones>,
+    mut weather: ResMut<Weather>,
+    mut events: EventWriter<WeatherEvent>,
+    mut last_changed: Local<Option<i64>>,
+) {
+    let period = (world_time.minutes / MINUTES_PER_HOUR / WEATHER_HOURS) as i64;
+
+    // The weather stays as it is for the rest of the period the server started in.
+    if *last_changed.get_or_insert(period) == period {
+        return;
+    }
+
+    *last_changed = Some(period);
+
+    let season = world_time.season();
+
+    for zone in zones.0.keys() {
+        let current = weather.get(zone);
+        let next = current.next(season, rand::random());
+
+        if next != current {
+            weather.0.insert(zone.clone(), next);
+
+            events.send(WeatherEvent {
+                zone: zone.clone(),
+                weather: next,
+            });
+        }
+    }
+}
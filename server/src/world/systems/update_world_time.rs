@@ -1,19 +1,71 @@
 use bevy::prelude::*;
-use chrono::{Local as LocalTime, Timelike};
-
-use crate::world::resources::world_time::{WorldTime, WorldTimeTag};
-
-pub fn update_world_time(mut time: ResMut<WorldTime>) {
-    time.time = LocalTime::now();
-
-    time.part = match time.time.hour() {
-        // 5am - 6am is Dawn
-        5 => WorldTimeTag::Dawn,
-        // 6am - 7pm is Day
-        6..=19 => WorldTimeTag::Day,
-        // 8pm is Dusk
-        20 => WorldTimeTag::Dusk,
-        // 8pm - 4am is Night
-        _ => WorldTimeTag::Night,
+
+use crate::world::{
+    events::world_time_event::WorldTimeEvent,
+    resources::{
+        clock_settings::ClockSettings,
+        world_time::{WorldTime, WorldTimeTag, HOURS_PER_DAY, MINUTES_PER_HOUR},
+    },
+};
+
+/// Move the game clock along, so a whole day passes every
+/// [`ClockSettings::day_minutes`] real minutes.
+pub fn update_world_time(
+    time: Res<Time>,
+    settings: Res<ClockSettings>,
+    mut world_time: ResMut<WorldTime>,
+    mut events: EventWriter<WorldTimeEvent>,
+) {
+    world_time.minutes +=
+        time.delta_seconds_f64() * HOURS_PER_DAY * MINUTES_PER_HOUR / (settings.day_minutes * 60.0);
+
+    let part = WorldTimeTag::from_hour(world_time.hour());
+
+    if part != world_time.part {
+        events.send(WorldTimeEvent {
+            previous: world_time.part,
+            part,
+        });
+
+        world_time.part = part;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::world::{
+        events::world_time_event::WorldTimeEvent,
+        resources::{
+            clock_settings::ClockSettings,
+            world_time::{WorldTime, WorldTimeTag},
+        },
     };
+
+    #[test]
+    fn dawn_breaks() {
+        let mut app = App::new();
+
+        app.init_resource::<Time>();
+        app.insert_resource(ClockSettings { day_minutes: 120.0 });
+        app.insert_resource(WorldTime {
+            // Just past 5am, but nothing's noticed yet.
+            minutes: 5.0 * 60.0 + 1.0,
+            part: WorldTimeTag::Night,
+        });
+        app.add_event::<WorldTimeEvent>();
+        app.add_system(super::update_world_time);
+
+        app.update();
+
+        assert_eq!(app.world.resource::<WorldTime>().part, WorldTimeTag::Dawn);
+
+        let events = app.world.resource::<Events<WorldTimeEvent>>();
+        let mut reader = events.get_reader();
+        let event = reader.iter(events).next().unwrap();
+
+        assert_eq!(event.previous, WorldTimeTag::Night);
+        assert_eq!(event.part, WorldTimeTag::Dawn);
+    }
 }
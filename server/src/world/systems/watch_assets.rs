@@ -0,0 +1,51 @@
+use std::{fs, path::Path, time::SystemTime};
+
+use bevy::prelude::*;
+
+use crate::world::{events::reload_event::ReloadEvent, resources::asset_watcher::AssetWatcher};
+
+/// Every so often, check whether any levels or prototypes have changed
+/// since we last looked, and reload them if they have.
+pub fn watch_assets(
+    time: Res<Time>,
+    mut watcher: ResMut<AssetWatcher>,
+    mut reloads: EventWriter<ReloadEvent>,
+) {
+    if !watcher.enabled || !watcher.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let modified = last_modified(Path::new("server/assets"));
+
+    if watcher.modified.is_some() && modified != watcher.modified {
+        info!("Assets have changed, reloading");
+
+        reloads.send(ReloadEvent);
+    }
+
+    watcher.modified = modified;
+}
+
+/// When a level or prototype under `path` was last changed. Directories count too,
+/// so removing a file is noticed.
+fn last_modified(path: &Path) -> Option<SystemTime> {
+    let modified = fs::metadata(path).ok()?.modified().ok();
+
+    fs::read_dir(path)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter_map(|path| {
+            if path.is_dir() {
+                last_modified(&path)
+            } else if path
+                .extension()
+                .map_or(false, |ext| ext == "ldtk" || ext == "yaml")
+            {
+                fs::metadata(&path).ok()?.modified().ok()
+            } else {
+                None
+            }
+        })
+        .chain(modified)
+        .max()
+}
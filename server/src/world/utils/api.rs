@@ -0,0 +1,21 @@
+use std::env;
+
+use api::world_time::handlers::SaveWorldTimeRequest;
+use reqwest::blocking::Response;
+
+pub fn get_world_time() -> reqwest::Result<Response> {
+    let api_url = env::var("API_URL").expect("Could not read API_URL from env");
+    let client = reqwest::blocking::Client::new();
+
+    client.post(format!("http://{api_url}/world_time")).send()
+}
+
+pub fn save_world_time(minutes: f64) -> reqwest::Result<Response> {
+    let api_url = env::var("API_URL").expect("Could not read API_URL from env");
+    let client = reqwest::blocking::Client::new();
+
+    client
+        .post(format!("http://{api_url}/save_world_time"))
+        .json(&SaveWorldTimeRequest { minutes })
+        .send()
+}
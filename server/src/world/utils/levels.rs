@@ -0,0 +1,545 @@
+use std::fs;
+
+use bevy::{
+    ecs::system::{Command, EntityCommands},
+    prelude::*,
+};
+use bevy_proto::prelude::ProtoData;
+use ldtk_rust::{EntityInstance, Project};
+
+use crate::{
+    items::components::stack::Stack,
+    spatial::components::{exit::Exit, lock::Lock, position::Position, zone::Zone},
+    visual::components::{details::Details, sprite::Sprite},
+    world::{
+        components::{from_level::FromLevel, overridden::Overridden, prototype::Prototype},
+        errors::LevelError,
+        resources::{
+            taken_placements::TakenPlacements,
+            zones::{ZoneLayout, Zones},
+        },
+    },
+};
+
+/// A prototype to spawn at a position in a zone.
+#[derive(Clone)]
+pub struct Placement {
+    pub zone: String,
+    pub position: IVec2,
+    pub prototype: String,
+    /// How many items the spawned [`Stack`] holds, for piles of items.
+    pub quantity: u32,
+    pub overrides: Overrides,
+}
+
+impl Placement {
+    /// What tells one placement apart from another when levels are reloaded.
+    pub fn key(&self) -> (String, IVec2, String) {
+        (self.zone.clone(), self.position, self.prototype.clone())
+    }
+}
+
+/// Changes made to a single placement through its LDtk fields, on top of
+/// whatever its prototype has.
+#[derive(Clone, Default)]
+pub struct Overrides {
+    pub name: Option<String>,
+    /// Replaces the prototype's description, including any for times of day.
+    pub description: Option<String>,
+    pub color: Option<String>,
+    /// A new lock, replacing any the prototype has.
+    pub lock: Option<Lock>,
+    /// Whether the prototype's own lock starts locked.
+    pub locked: Option<bool>,
+}
+
+/// Apply [`Overrides`] to an entity once its prototype's components are in.
+struct ApplyOverrides {
+    entity: Entity,
+    overrides: Overrides,
+}
+
+impl Overrides {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.description.is_none()
+            && self.color.is_none()
+            && self.lock.is_none()
+            && self.locked.is_none()
+    }
+}
+
+impl Command for ApplyOverrides {
+    fn write(self, world: &mut World) {
+        let mut entity = if let Some(entity) = world.get_entity_mut(self.entity) {
+            entity
+        } else {
+            return;
+        };
+
+        if !self.overrides.is_empty() {
+            entity.insert(Overridden);
+        }
+
+        if let Some(mut details) = entity.get_mut::<Details>() {
+            if let Some(name) = self.overrides.name {
+                details.name = name;
+            }
+
+            if let Some(description) = self.overrides.description {
+                details.description = description;
+                details.times.clear();
+            }
+        }
+
+        if let (Some(mut sprite), Some(color)) = (entity.get_mut::<Sprite>(), self.overrides.color)
+        {
+            sprite.color = color;
+        }
+
+        if let Some(lock) = self.overrides.lock {
+            entity.insert(lock);
+        } else if let (Some(mut lock), Some(locked)) =
+            (entity.get_mut::<Lock>(), self.overrides.locked)
+        {
+            lock.locked = locked;
+        }
+    }
+}
+
+/// Take an entity out of its level for good, counting it in [`TakenPlacements`]
+/// so it isn't spawned again when levels are reloaded. Anything that didn't come
+/// from a level is left alone.
+pub struct LeaveLevel(pub Entity);
+
+impl Command for LeaveLevel {
+    fn write(self, world: &mut World) {
+        let key = match world.get_entity(self.0) {
+            Some(entity) if entity.contains::<FromLevel>() => match (
+                entity.get::<Zone>(),
+                entity.get::<Position>(),
+                entity.get::<Prototype>(),
+            ) {
+                (Some(zone), Some(position), Some(prototype)) => {
+                    (zone.0.clone(), position.0, prototype.0.clone())
+                }
+                _ => return,
+            },
+            _ => return,
+        };
+
+        *world
+            .get_resource_or_insert_with(TakenPlacements::default)
+            .0
+            .entry(key)
+            .or_default() += 1;
+
+        world.entity_mut(self.0).remove::<FromLevel>();
+    }
+}
+
+/// Spawn a single copy of a placement's prototype with its [`Overrides`] applied,
+/// as long as there is one.
+pub fn spawn_prototype<'w, 's, 'a>(
+    commands: &'a mut Commands<'w, 's>,
+    prototypes: &Res<ProtoData>,
+    asset_server: &Res<AssetServer>,
+    placement: &Placement,
+) -> Option<EntityCommands<'w, 's, 'a>> {
+    let prototype = prototypes.get_prototype(&placement.prototype)?;
+
+    let entity = prototype.spawn(commands, prototypes, asset_server).id();
+
+    commands.add(ApplyOverrides {
+        entity,
+        overrides: placement.overrides.clone(),
+    });
+
+    Some(commands.entity(entity))
+}
+
+/// Everything read from the `.ldtk` projects in `assets/`.
+#[derive(Default)]
+pub struct Levels {
+    pub zones: Zones,
+    pub spawn: Option<(String, IVec2)>,
+    pub exits: Vec<(Exit, String, IVec2)>,
+    pub landmarks: Vec<(String, String, IVec2)>,
+    pub placements: Vec<Placement>,
+    /// Anything that had to be skipped over, like entities with missing fields.
+    pub problems: Vec<String>,
+}
+
+/// Read every `.ldtk` project in `assets/`, with each level becoming its own zone.
+/// Levels and entities that can't be read are skipped and noted in
+/// [`Levels::problems`], but a project that can't be read at all is an error.
+pub fn load_levels() -> Result<Levels, LevelError> {
+    let mut levels = Levels::default();
+
+    let mut paths = fs::read_dir("server/assets")
+        .map_err(LevelError::Assets)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().map_or(false, |ext| ext == "ldtk"))
+        .collect::<Vec<_>>();
+
+    // Sort so levels, and the spawn point, are always picked up in the same order.
+    paths.sort();
+
+    for path in paths {
+        let project =
+            fs::read_to_string(&path).map_err(|error| LevelError::Read(path.clone(), error))?;
+        let project = serde_json::from_str::<Project>(&project)
+            .map_err(|error| LevelError::Parse(path.clone(), error))?;
+
+        if project.external_levels {
+            levels.problems.push(format!(
+                "{path:?}: Levels saved in separate files aren't supported"
+            ));
+
+            continue;
+        }
+
+        for level in &project.levels {
+            if levels.zones.0.contains_key(&level.identifier) {
+                levels.problems.push(format!(
+                    "Level `{}` in {path:?} shares a name with another level",
+                    level.identifier
+                ));
+            }
+
+            let layers = if let Some(layers) = &level.layer_instances {
+                layers
+            } else {
+                levels.problems.push(format!(
+                    "Level `{}` in {path:?} has no layers",
+                    level.identifier
+                ));
+
+                continue;
+            };
+
+            // Levels are laid out in pixels, but everything else works in grid cells.
+            let grid_size = layers
+                .first()
+                .map_or(project.default_grid_size, |l| l.grid_size);
+
+            levels.zones.0.insert(
+                level.identifier.clone(),
+                ZoneLayout {
+                    depth: level.world_depth as i32,
+                    offset: IVec2::new(
+                        (level.world_x / grid_size) as i32,
+                        (level.world_y / grid_size) as i32,
+                    ),
+                    size: IVec2::new(
+                        (level.px_wid / grid_size) as i32,
+                        (level.px_hei / grid_size) as i32,
+                    ),
+                },
+            );
+
+            for layer in layers.iter().rev() {
+                for entity in &layer.entity_instances {
+                    let (x, y) = match entity.grid.as_slice() {
+                        [x, y, ..] => (*x as i32, *y as i32),
+                        _ => {
+                            levels.problems.push(format!(
+                                "`{}` in level `{}` has no grid position",
+                                entity.identifier, level.identifier
+                            ));
+
+                            continue;
+                        }
+                    };
+
+                    // Every cell the entity covers.
+                    let cells = (0..(entity.width / layer.grid_size) as i32).flat_map(|dx| {
+                        (0..(entity.height / layer.grid_size) as i32)
+                            .map(move |dy| IVec2::new(x + dx, y + dy))
+                    });
+
+                    // Where to point at when something's wrong with this entity.
+                    let location = format!("{} ({x}, {y})", level.identifier);
+
+                    match layer.identifier.as_str() {
+                        "Spawn" => {
+                            if let Some((zone, _)) = &levels.spawn {
+                                levels.problems.push(format!(
+                                    "{location}: Ignoring spawn point, already using one in `{zone}`"
+                                ));
+
+                                continue;
+                            }
+
+                            levels.spawn = Some((level.identifier.clone(), IVec2::new(x, y)));
+                        }
+                        "Exits" => {
+                            let exit = match (
+                                optional_field(entity, "level").and_then(|level| level.as_str()),
+                                optional_field(entity, "x").and_then(|x| x.as_i64()),
+                                optional_field(entity, "y").and_then(|y| y.as_i64()),
+                            ) {
+                                (Some(zone), Some(x), Some(y)) => Exit {
+                                    zone: zone.to_string(),
+                                    position: IVec2::new(x as i32, y as i32),
+                                },
+                                _ => {
+                                    levels.problems.push(format!(
+                                        "{location}: Exit needs a `level`, `x` and `y`"
+                                    ));
+
+                                    continue;
+                                }
+                            };
+
+                            for cell in cells {
+                                levels
+                                    .exits
+                                    .push((exit.clone(), level.identifier.clone(), cell));
+                            }
+                        }
+                        "Landmarks" => {
+                            let name = if let Some(name) =
+                                optional_field(entity, "name").and_then(|name| name.as_str())
+                            {
+                                name
+                            } else {
+                                levels
+                                    .problems
+                                    .push(format!("{location}: Landmark needs a `name`"));
+
+                                continue;
+                            };
+
+                            levels.landmarks.push((
+                                name.to_string(),
+                                level.identifier.clone(),
+                                IVec2::new(x, y),
+                            ));
+                        }
+                        _ => {
+                            let key = if let Some(key) =
+                                optional_field(entity, "prototype").and_then(|key| key.as_str())
+                            {
+                                key
+                            } else {
+                                levels.problems.push(format!(
+                                    "{location}: `{}` needs a `prototype`",
+                                    entity.identifier
+                                ));
+
+                                continue;
+                            };
+
+                            let string = |identifier| {
+                                optional_field(entity, identifier)
+                                    .and_then(|value| value.as_str())
+                                    .map(|value| value.to_string())
+                            };
+
+                            let locked = optional_field(entity, "locked")
+                                .and_then(|locked| locked.as_bool());
+
+                            let mut overrides = Overrides {
+                                name: string("name"),
+                                description: string("description"),
+                                color: None,
+                                // Doors can be given a lock, replacing any their prototype has.
+                                lock: string("key").map(|key| Lock {
+                                    key,
+                                    locked: locked.unwrap_or(true),
+                                    pick_chance: optional_field(entity, "pick_chance")
+                                        .and_then(|chance| chance.as_f64())
+                                        .map(|chance| chance as f32),
+                                }),
+                                locked,
+                            };
+
+                            // LDtk colors come as `#RRGGBB`, where sprites just want the hex.
+                            if let Some(color) = string("color") {
+                                let hex = color.trim_start_matches('#');
+
+                                if hex.len() == 6 && u32::from_str_radix(hex, 16).is_ok() {
+                                    overrides.color = Some(hex.to_uppercase());
+                                } else {
+                                    levels.problems.push(format!(
+                                        "{location}: `{color}` isn't a color, like `#DC2626`"
+                                    ));
+                                }
+                            }
+
+                            let quantity = match optional_field(entity, "quantity")
+                                .and_then(|quantity| quantity.as_i64())
+                            {
+                                Some(quantity) if quantity < 1 => {
+                                    levels.problems.push(format!(
+                                        "{location}: `quantity` needs to be at least 1"
+                                    ));
+
+                                    1
+                                }
+                                Some(quantity) => quantity as u32,
+                                None => 1,
+                            };
+
+                            for cell in cells {
+                                levels.placements.push(Placement {
+                                    zone: level.identifier.clone(),
+                                    position: cell,
+                                    prototype: key.to_string(),
+                                    quantity,
+                                    overrides: overrides.clone(),
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(levels)
+}
+
+/// Spawn an entity for a [`Placement`] in its spot, as long as there's a prototype for it.
+pub fn spawn_placement(
+    commands: &mut Commands,
+    prototypes: &Res<ProtoData>,
+    asset_server: &Res<AssetServer>,
+    placement: &Placement,
+) -> bool {
+    if let Some(mut spawned) = spawn_prototype(commands, prototypes, asset_server, placement) {
+        spawned.insert_bundle((
+            Position(placement.position),
+            Zone(placement.zone.clone()),
+            Prototype(placement.prototype.clone()),
+            FromLevel,
+        ));
+
+        if placement.quantity > 1 {
+            spawned.insert(Stack(placement.quantity));
+        }
+
+        true
+    } else {
+        false
+    }
+}
+
+/// Get the value of an optional field on an entity, if it's been set.
+fn optional_field<'a>(
+    entity: &'a EntityInstance,
+    identifier: &str,
+) -> Option<&'a serde_json::Value> {
+    entity
+        .field_instances
+        .iter()
+        .find(|f| f.identifier == identifier)
+        .and_then(|f| f.value.as_ref())
+        .filter(|value| !value.is_null())
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::Command, prelude::*};
+
+    use crate::{
+        spatial::components::{lock::Lock, position::Position, zone::Zone},
+        visual::components::{details::Details, sprite::Sprite},
+        world::{
+            components::{from_level::FromLevel, overridden::Overridden, prototype::Prototype},
+            resources::{taken_placements::TakenPlacements, world_time::WorldTimeTag},
+        },
+    };
+
+    use super::{ApplyOverrides, LeaveLevel, Overrides};
+
+    #[test]
+    fn overrides() {
+        let mut world = World::new();
+
+        let door = world
+            .spawn()
+            .insert_bundle((
+                Details {
+                    name: "Door".into(),
+                    description: "A plain door.".into(),
+                    times: [(WorldTimeTag::Night, "A dark door.".to_string())]
+                        .into_iter()
+                        .collect(),
+                },
+                Sprite {
+                    character: "+".into(),
+                    color: "854D0E".into(),
+                    background: None,
+                },
+                Lock {
+                    key: "Brass Key".into(),
+                    locked: true,
+                    pick_chance: None,
+                },
+            ))
+            .id();
+
+        ApplyOverrides {
+            entity: door,
+            overrides: Overrides {
+                name: Some("Cellar Door".into()),
+                description: Some("A door down to the cellar.".into()),
+                color: Some("DC2626".into()),
+                lock: None,
+                locked: Some(false),
+            },
+        }
+        .write(&mut world);
+
+        let details = world.get::<Details>(door).unwrap();
+
+        assert_eq!(details.name, "Cellar Door");
+        assert_eq!(
+            details.describe(WorldTimeTag::Night, false),
+            "A door down to the cellar."
+        );
+        assert_eq!(world.get::<Sprite>(door).unwrap().color, "DC2626");
+        assert!(!world.get::<Lock>(door).unwrap().locked);
+        assert!(world.get::<Overridden>(door).is_some());
+    }
+
+    #[test]
+    fn leave_level() {
+        let mut world = World::new();
+
+        let apple = world
+            .spawn()
+            .insert_bundle((
+                FromLevel,
+                Zone("Void".into()),
+                Position(IVec2::new(1, 2)),
+                Prototype("Apple".into()),
+            ))
+            .id();
+
+        let dropped = world
+            .spawn()
+            .insert_bundle((
+                Zone("Void".into()),
+                Position(IVec2::new(1, 2)),
+                Prototype("Apple".into()),
+            ))
+            .id();
+
+        LeaveLevel(apple).write(&mut world);
+        LeaveLevel(dropped).write(&mut world);
+
+        assert!(world.get::<FromLevel>(apple).is_none());
+        assert_eq!(
+            world.resource::<TakenPlacements>().0,
+            [(
+                ("Void".to_string(), IVec2::new(1, 2), "Apple".to_string()),
+                1
+            )]
+            .into_iter()
+            .collect()
+        );
+    }
+}
@@ -0,0 +1,432 @@
+use std::{
+    collections::VecDeque,
+    panic::{self, AssertUnwindSafe},
+};
+
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use bevy_proto::prelude::{ProtoData, ProtoDataOptions};
+use serde::Deserialize;
+use serde_yaml::Value;
+
+use crate::{
+    spatial::{components::stairs::Stairs, utils::direction::DIRECTIONS},
+    world::utils::levels::{load_levels, Levels},
+};
+
+/// Components a prototype can't do without.
+const REQUIRED: [&str; 2] = ["Details", "Sprite"];
+
+/// Components of every prototype, by name, as they're written in `assets/prototypes`.
+pub type PrototypeDefs = HashMap<String, HashMap<String, Value>>;
+
+/// A component the way it's written in a prototype file.
+#[derive(Deserialize)]
+struct ComponentDef {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    value: Value,
+}
+
+/// Every component of every prototype bevy_proto loaded, written back out so
+/// they can be checked by name.
+pub fn prototype_defs(prototypes: &ProtoData, problems: &mut Vec<String>) -> PrototypeDefs {
+    let mut defs = PrototypeDefs::default();
+
+    for prototype in prototypes.iter() {
+        let mut components = HashMap::default();
+
+        for component in prototype.iter_components() {
+            match serde_yaml::to_value(component).and_then(serde_yaml::from_value::<ComponentDef>) {
+                Ok(def) => {
+                    components.insert(def.kind, def.value);
+                }
+                Err(err) => problems.push(format!("Prototype `{}`: {err}", prototype.name())),
+            }
+        }
+
+        defs.insert(prototype.name().to_string(), components);
+    }
+
+    defs
+}
+
+/// Load levels and prototypes from `assets/`, the same way the server does, and
+/// report everything wrong with them.
+pub fn validate_assets(options: ProtoDataOptions) -> Vec<String> {
+    let mut world = World::new();
+
+    world.insert_resource(options);
+
+    // bevy_proto panics on a prototype it can't read.
+    let prototypes =
+        match panic::catch_unwind(AssertUnwindSafe(|| ProtoData::from_world(&mut world))) {
+            Ok(prototypes) => prototypes,
+            Err(_) => return vec!["Could not load prototypes".to_string()],
+        };
+
+    match load_levels() {
+        Ok(levels) => check(&levels, &prototypes),
+        Err(error) => vec![error.to_string()],
+    }
+}
+
+/// Report everything wrong with already loaded levels and prototypes.
+pub fn check(levels: &Levels, prototypes: &ProtoData) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    let defs = prototype_defs(prototypes, &mut problems);
+
+    problems.extend(validate(levels, &defs));
+
+    problems
+}
+
+/// Every problem with `levels` and the prototypes they use, rather than just the first.
+pub fn validate(levels: &Levels, defs: &PrototypeDefs) -> Vec<String> {
+    let mut problems = levels.problems.clone();
+
+    let mut names = defs.keys().collect::<Vec<_>>();
+    names.sort();
+
+    for name in names {
+        let components = &defs[name];
+
+        for required in REQUIRED {
+            if !components.contains_key(required) {
+                problems.push(format!("Prototype `{name}` is missing `{required}`"));
+            }
+        }
+
+        if let Some(key) = components
+            .get("Lock")
+            .and_then(|lock| lock.get("key"))
+            .and_then(|key| key.as_str())
+        {
+            if !defs.contains_key(key) {
+                problems.push(format!(
+                    "Prototype `{name}` has a lock with an unknown key `{key}`"
+                ));
+            }
+        }
+    }
+
+    // Everything placed in each cell, in the order it was laid out.
+    let mut cells: HashMap<(&str, IVec2), Vec<&str>> = HashMap::default();
+
+    for placement in &levels.placements {
+        let location = format!(
+            "{} ({}, {})",
+            placement.zone, placement.position.x, placement.position.y
+        );
+
+        if !defs.contains_key(&placement.prototype) {
+            problems.push(format!(
+                "{location}: Unknown prototype `{}`",
+                placement.prototype
+            ));
+
+            continue;
+        }
+
+        let components = &defs[&placement.prototype];
+
+        if let Some(lock) = &placement.overrides.lock {
+            if !defs.contains_key(&lock.key) {
+                problems.push(format!(
+                    "{location}: Lock has an unknown key `{}`",
+                    lock.key
+                ));
+            }
+        } else if placement.overrides.locked.is_some() && !components.contains_key("Lock") {
+            problems.push(format!(
+                "{location}: `locked` is set, but `{}` has no lock",
+                placement.prototype
+            ));
+        }
+
+        if placement.quantity > 1 && !components.contains_key("Item") {
+            problems.push(format!(
+                "{location}: `quantity` is set, but `{}` isn't an item",
+                placement.prototype
+            ));
+        }
+
+        let cell = cells
+            .entry((placement.zone.as_str(), placement.position))
+            .or_default();
+
+        if components.contains_key("Tile") {
+            if let Some(other) = cell.iter().find(|other| defs[**other].contains_key("Tile")) {
+                problems.push(format!(
+                    "{location}: `{}` overlaps `{other}`",
+                    placement.prototype
+                ));
+            }
+        }
+
+        cell.push(&placement.prototype);
+    }
+
+    for (exit, zone, position) in &levels.exits {
+        if !levels.zones.0.contains_key(&exit.zone) {
+            problems.push(format!(
+                "{zone} ({}, {}): Exit leads to unknown level `{}`",
+                position.x, position.y, exit.zone
+            ));
+        }
+    }
+
+    let has = |zone: &str, position: IVec2, component: &str| {
+        cells.get(&(zone, position)).map_or(false, |cell| {
+            cell.iter()
+                .any(|prototype| defs[*prototype].contains_key(component))
+        })
+    };
+
+    // Closed doors have a collider too, but can still be walked through once opened.
+    let passable = |zone: &str, position: IVec2| {
+        has(zone, position, "Tile")
+            && (!has(zone, position, "Collider") || has(zone, position, "Door"))
+    };
+
+    let (spawn_zone, spawn) = match &levels.spawn {
+        Some((zone, spawn)) => (zone.as_str(), *spawn),
+        None => {
+            problems.push("There's no spawn point".to_string());
+
+            return problems;
+        }
+    };
+
+    // A bad spawn point is still searched from, so whatever else can't be
+    // reached is reported too.
+    if has(spawn_zone, spawn, "Collider") {
+        problems.push(format!(
+            "{spawn_zone} ({}, {}): Spawn point is on a collider",
+            spawn.x, spawn.y
+        ));
+    } else if !passable(spawn_zone, spawn) {
+        problems.push(format!(
+            "{spawn_zone} ({}, {}): Spawn point isn't on a tile",
+            spawn.x, spawn.y
+        ));
+    }
+
+    let mut reached: HashSet<(String, IVec2)> = HashSet::default();
+    let mut queue = VecDeque::from([(spawn_zone.to_string(), spawn)]);
+
+    reached.insert((spawn_zone.to_string(), spawn));
+
+    while let Some((zone, position)) = queue.pop_front() {
+        let mut next = DIRECTIONS
+            .iter()
+            .map(|(_, _, offset)| (zone.clone(), position + *offset))
+            .collect::<Vec<_>>();
+
+        next.extend(
+            levels
+                .exits
+                .iter()
+                .filter(|(_, z, p)| *z == zone && *p == position)
+                .map(|(exit, _, _)| (exit.zone.clone(), exit.position)),
+        );
+
+        for prototype in cells.get(&(zone.as_str(), position)).into_iter().flatten() {
+            let stairs = if let Some(stairs) = defs[*prototype]
+                .get("Stairs")
+                .and_then(|value| serde_yaml::from_value::<Stairs>(value.clone()).ok())
+            {
+                stairs
+            } else {
+                continue;
+            };
+
+            for (floors, allowed) in [(1, stairs.up), (-1, stairs.down)] {
+                if let Some(stacked) = levels
+                    .zones
+                    .stacked(&zone, position, floors)
+                    .filter(|_| allowed)
+                {
+                    next.push(stacked);
+                }
+            }
+        }
+
+        for cell in next {
+            if passable(cell.0.as_str(), cell.1) && reached.insert(cell.clone()) {
+                queue.push_back(cell);
+            }
+        }
+    }
+
+    let mut unreachable: HashMap<&str, Vec<IVec2>> = HashMap::default();
+
+    for &(zone, position) in cells.keys() {
+        if passable(zone, position) && !reached.contains(&(zone.to_string(), position)) {
+            unreachable.entry(zone).or_default().push(position);
+        }
+    }
+
+    let mut zones = unreachable.into_iter().collect::<Vec<_>>();
+    zones.sort_by_key(|(zone, _)| *zone);
+
+    for (zone, positions) in zones {
+        let first = positions
+            .iter()
+            .min_by_key(|position| (position.y, position.x))
+            .unwrap();
+
+        problems.push(format!(
+            "{zone}: {} cells can't be reached from the spawn point, like ({}, {})",
+            positions.len(),
+            first.x,
+            first.y
+        ));
+    }
+
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use serde_yaml::Value;
+
+    use crate::world::{
+        resources::zones::ZoneLayout,
+        utils::levels::{Levels, Placement},
+    };
+
+    use super::{validate, PrototypeDefs};
+
+    fn defs() -> PrototypeDefs {
+        let mut defs = PrototypeDefs::default();
+
+        for (name, components) in [
+            ("Floor", vec!["Tile", "Details", "Sprite"]),
+            ("Wall", vec!["Tile", "Collider", "Details", "Sprite"]),
+            ("Door", vec!["Door", "Collider", "Details", "Sprite"]),
+            ("Rock", vec!["Details"]),
+        ] {
+            defs.insert(
+                name.to_string(),
+                components
+                    .into_iter()
+                    .map(|component| (component.to_string(), Value::Null))
+                    .collect(),
+            );
+        }
+
+        defs
+    }
+
+    fn place(levels: &mut Levels, x: i32, prototype: &str) {
+        levels.placements.push(Placement {
+            zone: "Town".to_string(),
+            position: IVec2::new(x, 0),
+            prototype: prototype.to_string(),
+            quantity: 1,
+            overrides: Default::default(),
+        });
+    }
+
+    fn levels() -> Levels {
+        let mut levels = Levels::default();
+
+        levels.zones.0.insert(
+            "Town".into(),
+            ZoneLayout {
+                depth: 0,
+                offset: IVec2::ZERO,
+                size: IVec2::new(6, 1),
+            },
+        );
+
+        levels.spawn = Some(("Town".to_string(), IVec2::new(0, 0)));
+
+        levels
+    }
+
+    #[test]
+    fn valid() {
+        let mut levels = levels();
+
+        place(&mut levels, 0, "Floor");
+        place(&mut levels, 1, "Floor");
+        place(&mut levels, 2, "Floor");
+        place(&mut levels, 2, "Door");
+        place(&mut levels, 3, "Floor");
+
+        let mut defs = defs();
+        defs.remove("Rock");
+
+        assert_eq!(validate(&levels, &defs), Vec::<String>::new());
+    }
+
+    #[test]
+    fn every_problem() {
+        let mut levels = levels();
+
+        levels
+            .problems
+            .push("Town (5, 0): Landmark needs a `name`".into());
+
+        place(&mut levels, 0, "Floor");
+        place(&mut levels, 1, "Floor");
+        place(&mut levels, 1, "Wall");
+        place(&mut levels, 2, "Wall");
+        place(&mut levels, 3, "Floor");
+        place(&mut levels, 4, "Flor");
+
+        assert_eq!(
+            validate(&levels, &defs()),
+            vec![
+                "Town (5, 0): Landmark needs a `name`",
+                "Prototype `Rock` is missing `Sprite`",
+                "Town (1, 0): `Wall` overlaps `Floor`",
+                "Town (4, 0): Unknown prototype `Flor`",
+                "Town: 1 cells can't be reached from the spawn point, like (3, 0)",
+            ]
+        );
+    }
+
+    #[test]
+    fn spawn_on_collider() {
+        let mut levels = levels();
+
+        place(&mut levels, 0, "Wall");
+
+        let mut defs = defs();
+        defs.remove("Rock");
+
+        assert_eq!(
+            validate(&levels, &defs),
+            vec!["Town (0, 0): Spawn point is on a collider"]
+        );
+    }
+
+    #[test]
+    fn spawn_on_collider_still_reports_the_rest() {
+        let mut levels = levels();
+
+        place(&mut levels, 0, "Wall");
+        place(&mut levels, 1, "Floor");
+        place(&mut levels, 2, "Wall");
+        place(&mut levels, 3, "Floor");
+
+        let mut defs = defs();
+        defs.remove("Rock");
+
+        assert_eq!(
+            validate(&levels, &defs),
+            vec![
+                "Town (0, 0): Spawn point is on a collider",
+                "Town: 1 cells can't be reached from the spawn point, like (3, 0)",
+            ]
+        );
+    }
+}